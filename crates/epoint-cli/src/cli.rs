@@ -88,23 +88,12 @@ pub struct FilterArguments {
 pub enum PointCloudFormat {
     Epoint,
     EpointTar,
+    EpointDirectory,
     E57,
     Las,
     Laz,
+    Pcd,
+    Ply,
     Xyz,
     XyzZst,
 }
-
-impl PointCloudFormat {
-    pub fn to_epoint_format(&self) -> epoint::io::PointCloudFormat {
-        match self {
-            PointCloudFormat::Epoint => epoint::io::PointCloudFormat::Epoint,
-            PointCloudFormat::EpointTar => epoint::io::PointCloudFormat::EpointTar,
-            PointCloudFormat::E57 => epoint::io::PointCloudFormat::E57,
-            PointCloudFormat::Las => epoint::io::PointCloudFormat::Las,
-            PointCloudFormat::Laz => epoint::io::PointCloudFormat::Laz,
-            PointCloudFormat::Xyz => epoint::io::PointCloudFormat::Xyz,
-            PointCloudFormat::XyzZst => epoint::io::PointCloudFormat::XyzZst,
-        }
-    }
-}
@@ -3,14 +3,14 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum Error {
     #[error(transparent)]
-    EpointError(#[from] epoint::Error),
+    Epoint(#[from] epoint::Error),
     #[error(transparent)]
-    EpointIoError(#[from] epoint::io::Error),
+    EpointIo(#[from] epoint::io::Error),
     #[error(transparent)]
-    EpointTransformError(#[from] epoint::transform::Error),
+    EpointTransform(#[from] epoint::transform::Error),
 
     #[error(transparent)]
-    StdIoError(#[from] std::io::Error),
+    StdIo(#[from] std::io::Error),
     #[error(transparent)]
     PolarsResult(#[from] polars::error::PolarsError),
     #[error(transparent)]
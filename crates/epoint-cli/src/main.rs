@@ -1,7 +1,6 @@
 mod cli;
 mod commands;
 mod error;
-mod utility;
 
 use anyhow::Result;
 
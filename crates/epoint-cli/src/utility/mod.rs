@@ -1,2 +0,0 @@
-pub mod filter;
-pub mod terminal;
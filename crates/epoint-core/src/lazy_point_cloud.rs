@@ -0,0 +1,49 @@
+use crate::error::Error;
+use crate::{PointCloud, PointCloudInfo};
+use ecoord::TransformTree;
+use polars::prelude::{IntoLazy, LazyFrame};
+
+/// Point cloud with lazily evaluated point data.
+///
+/// Projections and filters added to the [`LazyFrame`] are only evaluated, and possibly pushed down
+/// into the scan of the source, when the point cloud is [collected](Self::collect).
+#[derive(Clone)]
+pub struct LazyPointCloud {
+    pub point_data: LazyFrame,
+    pub info: PointCloudInfo,
+    pub transform_tree: TransformTree,
+}
+
+impl LazyPointCloud {
+    pub fn new(point_data: LazyFrame, info: PointCloudInfo, transform_tree: TransformTree) -> Self {
+        Self {
+            point_data,
+            info,
+            transform_tree,
+        }
+    }
+
+    pub fn info(&self) -> &PointCloudInfo {
+        &self.info
+    }
+
+    pub fn transform_tree(&self) -> &TransformTree {
+        &self.transform_tree
+    }
+
+    /// Evaluates the point data and validates the resulting point cloud.
+    pub fn collect(self) -> Result<PointCloud, Error> {
+        let data_frame = self.point_data.collect()?;
+        PointCloud::from_data_frame(data_frame, self.info, self.transform_tree)
+    }
+}
+
+impl From<PointCloud> for LazyPointCloud {
+    fn from(item: PointCloud) -> Self {
+        Self::new(
+            item.point_data.data_frame.lazy(),
+            item.info,
+            item.transform_tree,
+        )
+    }
+}
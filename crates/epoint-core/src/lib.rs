@@ -1,10 +1,10 @@
 mod error;
+mod lazy_point_cloud;
 pub mod octree;
 pub mod point_cloud;
 mod point_cloud_info;
 mod point_data;
 mod point_data_columns;
-mod utility;
 
 #[doc(inline)]
 pub use crate::error::Error;
@@ -12,6 +12,9 @@ pub use crate::error::Error;
 #[doc(inline)]
 pub use crate::point_cloud::PointCloud;
 
+#[doc(inline)]
+pub use crate::lazy_point_cloud::LazyPointCloud;
+
 #[doc(inline)]
 pub use crate::point_data::PointData;
 
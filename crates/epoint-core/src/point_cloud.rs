@@ -125,6 +125,10 @@ impl PointCloud {
         self.point_data.contains_colors()
     }
 
+    pub fn contains_normals(&self) -> bool {
+        self.point_data.contains_normals()
+    }
+
     pub fn set_transform_tree(&mut self, transform_tree: TransformTree) {
         self.transform_tree = transform_tree;
     }
@@ -224,7 +228,7 @@ impl PointCloud {
         Ok(filtered_point_cloud)
     }
 
-    pub fn filter_by_boolean_mask(&self, mask: &Vec<bool>) -> Result<PointCloud, Error> {
+    pub fn filter_by_boolean_mask(&self, mask: &[bool]) -> Result<PointCloud, Error> {
         let mask_series: Series = mask.iter().collect();
         let filtered_point_data = self
             .point_data
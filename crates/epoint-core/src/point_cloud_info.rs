@@ -1,12 +1,23 @@
 use ecoord::FrameId;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct PointCloudInfo {
     pub frame_id: Option<FrameId>,
+    /// Free-form metadata at the user's disposal.
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl PointCloudInfo {
     pub fn new(frame_id: Option<FrameId>) -> Self {
-        Self { frame_id }
+        Self {
+            frame_id,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: BTreeMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
     }
 }
@@ -41,6 +41,24 @@ const COLUMN_NAME_OCTANT_INDEX_X_STR: &str = "octant_index_x";
 const COLUMN_NAME_OCTANT_INDEX_Y_STR: &str = "octant_index_y";
 const COLUMN_NAME_OCTANT_INDEX_Z_STR: &str = "octant_index_z";
 const COLUMN_NAME_POINT_SOURCE_ID_STR: &str = "point_source_id";
+const COLUMN_NAME_NORMAL_X_STR: &str = "normal_x";
+const COLUMN_NAME_NORMAL_Y_STR: &str = "normal_y";
+const COLUMN_NAME_NORMAL_Z_STR: &str = "normal_z";
+const COLUMN_NAME_ROW_INDEX_STR: &str = "row_index";
+const COLUMN_NAME_COLUMN_INDEX_STR: &str = "column_index";
+const COLUMN_NAME_CLASSIFICATION_STR: &str = "classification";
+const COLUMN_NAME_RETURN_NUMBER_STR: &str = "return_number";
+const COLUMN_NAME_NUMBER_OF_RETURNS_STR: &str = "number_of_returns";
+const COLUMN_NAME_SCAN_ANGLE_STR: &str = "scan_angle";
+const COLUMN_NAME_SCAN_DIRECTION_FLAG_STR: &str = "scan_direction_flag";
+const COLUMN_NAME_EDGE_OF_FLIGHT_LINE_STR: &str = "edge_of_flight_line";
+const COLUMN_NAME_SYNTHETIC_FLAG_STR: &str = "synthetic_flag";
+const COLUMN_NAME_KEY_POINT_FLAG_STR: &str = "key_point_flag";
+const COLUMN_NAME_WITHHELD_FLAG_STR: &str = "withheld_flag";
+const COLUMN_NAME_OVERLAP_FLAG_STR: &str = "overlap_flag";
+const COLUMN_NAME_USER_DATA_STR: &str = "user_data";
+const COLUMN_NAME_SCANNER_CHANNEL_STR: &str = "scanner_channel";
+const COLUMN_NAME_NIR_STR: &str = "nir";
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum PointDataColumnType {
@@ -97,6 +115,42 @@ pub enum PointDataColumnType {
     /// Indicates the source from which this point originated (e.g., flight line, sortie number, route number, or setup identifier)
     /// Valid values: 1-65,535; zero is reserved.
     PointSourceId,
+    /// X component of the surface normal
+    NormalX,
+    /// Y component of the surface normal
+    NormalY,
+    /// Z component of the surface normal
+    NormalZ,
+    /// Row index of the point within a structured (organized) scan grid
+    RowIndex,
+    /// Column index of the point within a structured (organized) scan grid
+    ColumnIndex,
+    /// Class the point has been assigned to, as defined by the ASPRS LAS specification
+    Classification,
+    /// Pulse return number of the point
+    ReturnNumber,
+    /// Total number of returns of the pulse the point belongs to
+    NumberOfReturns,
+    /// Scan angle in degrees, with zero at nadir and negative values to the left
+    ScanAngle,
+    /// Direction of the scanner mirror, true for the positive scan direction (left to right)
+    ScanDirectionFlag,
+    /// Indicates that the point is the last one of a scan line before the direction changes
+    EdgeOfFlightLine,
+    /// Indicates that the point was created by a technique other than direct observation
+    SyntheticFlag,
+    /// Indicates that the point is a model key-point and should not be thinned out
+    KeyPointFlag,
+    /// Indicates that the point should be considered as deleted
+    WithheldFlag,
+    /// Indicates that the point is within the overlap region of two or more swaths
+    OverlapFlag,
+    /// Custom data at the user's disposal
+    UserData,
+    /// Channel of a multi-channel scanner the point was recorded with
+    ScannerChannel,
+    /// Near-infrared channel value
+    Nir,
 }
 
 impl std::str::FromStr for PointDataColumnType {
@@ -122,6 +176,24 @@ impl std::str::FromStr for PointDataColumnType {
             COLUMN_NAME_SPHERICAL_ELEVATION_STR => Ok(PointDataColumnType::SphericalElevation),
             COLUMN_NAME_SPHERICAL_RANGE_STR => Ok(PointDataColumnType::SphericalRange),
             COLUMN_NAME_POINT_SOURCE_ID_STR => Ok(PointDataColumnType::PointSourceId),
+            COLUMN_NAME_NORMAL_X_STR => Ok(PointDataColumnType::NormalX),
+            COLUMN_NAME_NORMAL_Y_STR => Ok(PointDataColumnType::NormalY),
+            COLUMN_NAME_NORMAL_Z_STR => Ok(PointDataColumnType::NormalZ),
+            COLUMN_NAME_ROW_INDEX_STR => Ok(PointDataColumnType::RowIndex),
+            COLUMN_NAME_COLUMN_INDEX_STR => Ok(PointDataColumnType::ColumnIndex),
+            COLUMN_NAME_CLASSIFICATION_STR => Ok(PointDataColumnType::Classification),
+            COLUMN_NAME_RETURN_NUMBER_STR => Ok(PointDataColumnType::ReturnNumber),
+            COLUMN_NAME_NUMBER_OF_RETURNS_STR => Ok(PointDataColumnType::NumberOfReturns),
+            COLUMN_NAME_SCAN_ANGLE_STR => Ok(PointDataColumnType::ScanAngle),
+            COLUMN_NAME_SCAN_DIRECTION_FLAG_STR => Ok(PointDataColumnType::ScanDirectionFlag),
+            COLUMN_NAME_EDGE_OF_FLIGHT_LINE_STR => Ok(PointDataColumnType::EdgeOfFlightLine),
+            COLUMN_NAME_SYNTHETIC_FLAG_STR => Ok(PointDataColumnType::SyntheticFlag),
+            COLUMN_NAME_KEY_POINT_FLAG_STR => Ok(PointDataColumnType::KeyPointFlag),
+            COLUMN_NAME_WITHHELD_FLAG_STR => Ok(PointDataColumnType::WithheldFlag),
+            COLUMN_NAME_OVERLAP_FLAG_STR => Ok(PointDataColumnType::OverlapFlag),
+            COLUMN_NAME_USER_DATA_STR => Ok(PointDataColumnType::UserData),
+            COLUMN_NAME_SCANNER_CHANNEL_STR => Ok(PointDataColumnType::ScannerChannel),
+            COLUMN_NAME_NIR_STR => Ok(PointDataColumnType::Nir),
             _ => Err(()),
         }
     }
@@ -156,6 +228,24 @@ impl PointDataColumnType {
             PointDataColumnType::OctantIndexY => COLUMN_NAME_OCTANT_INDEX_Y_STR,
             PointDataColumnType::OctantIndexZ => COLUMN_NAME_OCTANT_INDEX_Z_STR,
             PointDataColumnType::PointSourceId => COLUMN_NAME_POINT_SOURCE_ID_STR,
+            PointDataColumnType::NormalX => COLUMN_NAME_NORMAL_X_STR,
+            PointDataColumnType::NormalY => COLUMN_NAME_NORMAL_Y_STR,
+            PointDataColumnType::NormalZ => COLUMN_NAME_NORMAL_Z_STR,
+            PointDataColumnType::RowIndex => COLUMN_NAME_ROW_INDEX_STR,
+            PointDataColumnType::ColumnIndex => COLUMN_NAME_COLUMN_INDEX_STR,
+            PointDataColumnType::Classification => COLUMN_NAME_CLASSIFICATION_STR,
+            PointDataColumnType::ReturnNumber => COLUMN_NAME_RETURN_NUMBER_STR,
+            PointDataColumnType::NumberOfReturns => COLUMN_NAME_NUMBER_OF_RETURNS_STR,
+            PointDataColumnType::ScanAngle => COLUMN_NAME_SCAN_ANGLE_STR,
+            PointDataColumnType::ScanDirectionFlag => COLUMN_NAME_SCAN_DIRECTION_FLAG_STR,
+            PointDataColumnType::EdgeOfFlightLine => COLUMN_NAME_EDGE_OF_FLIGHT_LINE_STR,
+            PointDataColumnType::SyntheticFlag => COLUMN_NAME_SYNTHETIC_FLAG_STR,
+            PointDataColumnType::KeyPointFlag => COLUMN_NAME_KEY_POINT_FLAG_STR,
+            PointDataColumnType::WithheldFlag => COLUMN_NAME_WITHHELD_FLAG_STR,
+            PointDataColumnType::OverlapFlag => COLUMN_NAME_OVERLAP_FLAG_STR,
+            PointDataColumnType::UserData => COLUMN_NAME_USER_DATA_STR,
+            PointDataColumnType::ScannerChannel => COLUMN_NAME_SCANNER_CHANNEL_STR,
+            PointDataColumnType::Nir => COLUMN_NAME_NIR_STR,
         }
     }
 
@@ -194,6 +284,130 @@ impl PointDataColumnType {
             PointDataColumnType::OctantIndexY => DataType::UInt64,
             PointDataColumnType::OctantIndexZ => DataType::UInt64,
             PointDataColumnType::PointSourceId => DataType::UInt16,
+            PointDataColumnType::NormalX => DataType::Float32,
+            PointDataColumnType::NormalY => DataType::Float32,
+            PointDataColumnType::NormalZ => DataType::Float32,
+            PointDataColumnType::RowIndex => DataType::UInt32,
+            PointDataColumnType::ColumnIndex => DataType::UInt32,
+            PointDataColumnType::Classification => DataType::UInt8,
+            PointDataColumnType::ReturnNumber => DataType::UInt8,
+            PointDataColumnType::NumberOfReturns => DataType::UInt8,
+            PointDataColumnType::ScanAngle => DataType::Float32,
+            PointDataColumnType::ScanDirectionFlag => DataType::Boolean,
+            PointDataColumnType::EdgeOfFlightLine => DataType::Boolean,
+            PointDataColumnType::SyntheticFlag => DataType::Boolean,
+            PointDataColumnType::KeyPointFlag => DataType::Boolean,
+            PointDataColumnType::WithheldFlag => DataType::Boolean,
+            PointDataColumnType::OverlapFlag => DataType::Boolean,
+            PointDataColumnType::UserData => DataType::UInt8,
+            PointDataColumnType::ScannerChannel => DataType::UInt8,
+            PointDataColumnType::Nir => DataType::UInt16,
+        }
+    }
+
+    /// Returns the unit of the values, if the values have a physical unit.
+    ///
+    /// Coordinates are expected in the unit of the frame's coordinate reference system, which is
+    /// usually metre.
+    pub fn unit(&self) -> Option<&'static str> {
+        match self {
+            PointDataColumnType::X
+            | PointDataColumnType::Y
+            | PointDataColumnType::Z
+            | PointDataColumnType::SensorTranslationX
+            | PointDataColumnType::SensorTranslationY
+            | PointDataColumnType::SensorTranslationZ
+            | PointDataColumnType::SphericalRange => Some("m"),
+            PointDataColumnType::TimestampSecond => Some("s"),
+            PointDataColumnType::TimestampNanoSecond => Some("ns"),
+            PointDataColumnType::SphericalAzimuth | PointDataColumnType::SphericalElevation => {
+                Some("rad")
+            }
+            PointDataColumnType::ScanAngle => Some("deg"),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PointDataColumnType::X => "X coordinate",
+            PointDataColumnType::Y => "Y coordinate",
+            PointDataColumnType::Z => "Z coordinate",
+            PointDataColumnType::Id => "Identifier for an individual point",
+            PointDataColumnType::FrameId => "Coordinate frame the point is defined in",
+            PointDataColumnType::TimestampSecond => {
+                "UNIX timestamp: non-leap seconds since January 1, 1970 0:00:00 UTC"
+            }
+            PointDataColumnType::TimestampNanoSecond => {
+                "Nanoseconds since the last whole non-leap second"
+            }
+            PointDataColumnType::Intensity => "Representation of the pulse return magnitude",
+            PointDataColumnType::SensorTranslationX => "Sensor translation X coordinate",
+            PointDataColumnType::SensorTranslationY => "Sensor translation Y coordinate",
+            PointDataColumnType::SensorTranslationZ => "Sensor translation Z coordinate",
+            PointDataColumnType::SensorRotationX => "Sensor rotation X coordinate",
+            PointDataColumnType::SensorRotationY => "Sensor rotation Y coordinate",
+            PointDataColumnType::SensorRotationZ => "Sensor rotation Z coordinate",
+            PointDataColumnType::SensorRotationW => "Sensor rotation W coordinate",
+            PointDataColumnType::ColorRed => "Red image channel value",
+            PointDataColumnType::ColorGreen => "Green image channel value",
+            PointDataColumnType::ColorBlue => "Blue image channel value",
+            PointDataColumnType::SphericalAzimuth => {
+                "Azimuth in the context of spherical coordinates"
+            }
+            PointDataColumnType::SphericalElevation => {
+                "Elevation in the context of spherical coordinates"
+            }
+            PointDataColumnType::SphericalRange => "Range in the context of spherical coordinates",
+            PointDataColumnType::OctantIndexLevel => "Level of octant index",
+            PointDataColumnType::OctantIndexX => "X index of octant",
+            PointDataColumnType::OctantIndexY => "Y index of octant",
+            PointDataColumnType::OctantIndexZ => "Z index of octant",
+            PointDataColumnType::PointSourceId => {
+                "Indicates the source from which this point originated"
+            }
+            PointDataColumnType::NormalX => "X component of the surface normal",
+            PointDataColumnType::NormalY => "Y component of the surface normal",
+            PointDataColumnType::NormalZ => "Z component of the surface normal",
+            PointDataColumnType::RowIndex => {
+                "Row index of the point within a structured (organized) scan grid"
+            }
+            PointDataColumnType::ColumnIndex => {
+                "Column index of the point within a structured (organized) scan grid"
+            }
+            PointDataColumnType::Classification => {
+                "Class the point has been assigned to, as defined by the ASPRS LAS specification"
+            }
+            PointDataColumnType::ReturnNumber => "Pulse return number of the point",
+            PointDataColumnType::NumberOfReturns => {
+                "Total number of returns of the pulse the point belongs to"
+            }
+            PointDataColumnType::ScanAngle => {
+                "Scan angle, with zero at nadir and negative values to the left"
+            }
+            PointDataColumnType::ScanDirectionFlag => {
+                "Direction of the scanner mirror, true for the positive scan direction (left to right)"
+            }
+            PointDataColumnType::EdgeOfFlightLine => {
+                "Indicates that the point is the last one of a scan line before the direction changes"
+            }
+            PointDataColumnType::SyntheticFlag => {
+                "Indicates that the point was created by a technique other than direct observation"
+            }
+            PointDataColumnType::KeyPointFlag => {
+                "Indicates that the point is a model key-point and should not be thinned out"
+            }
+            PointDataColumnType::WithheldFlag => {
+                "Indicates that the point should be considered as deleted"
+            }
+            PointDataColumnType::OverlapFlag => {
+                "Indicates that the point is within the overlap region of two or more swaths"
+            }
+            PointDataColumnType::UserData => "Custom data at the user's disposal",
+            PointDataColumnType::ScannerChannel => {
+                "Channel of a multi-channel scanner the point was recorded with"
+            }
+            PointDataColumnType::Nir => "Near-infrared channel value",
         }
     }
 }
@@ -211,6 +425,11 @@ pub struct PointData {
 }
 
 impl PointData {
+    /// Creates the point data from a data frame.
+    ///
+    /// Columns named after a [`PointDataColumnType`] must have its data type, otherwise
+    /// [`Error::TypeMismatch`] is returned. Use other names for custom columns, such as a
+    /// `classification` column with a data type other than `u8`.
     pub fn new(data_frame: DataFrame) -> Result<Self, Error> {
         if data_frame.is_empty() {
             return Err(NoData("point_data"));
@@ -445,6 +664,168 @@ impl PointData {
             .expect("type must be f64");
         Ok(values)
     }
+
+    pub fn get_normal_x_values(&self) -> Result<&Float32Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::NormalX.as_str())?
+            .f32()
+            .expect("type must be f32");
+        Ok(values)
+    }
+
+    pub fn get_normal_y_values(&self) -> Result<&Float32Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::NormalY.as_str())?
+            .f32()
+            .expect("type must be f32");
+        Ok(values)
+    }
+
+    pub fn get_normal_z_values(&self) -> Result<&Float32Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::NormalZ.as_str())?
+            .f32()
+            .expect("type must be f32");
+        Ok(values)
+    }
+
+    pub fn get_row_index_values(&self) -> Result<&UInt32Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::RowIndex.as_str())?
+            .u32()
+            .expect("type must be u32");
+        Ok(values)
+    }
+
+    pub fn get_column_index_values(&self) -> Result<&UInt32Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::ColumnIndex.as_str())?
+            .u32()
+            .expect("type must be u32");
+        Ok(values)
+    }
+
+    pub fn get_classification_values(&self) -> Result<&UInt8Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::Classification.as_str())?
+            .u8()
+            .expect("type must be u8");
+        Ok(values)
+    }
+
+    pub fn get_return_number_values(&self) -> Result<&UInt8Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::ReturnNumber.as_str())?
+            .u8()
+            .expect("type must be u8");
+        Ok(values)
+    }
+
+    pub fn get_number_of_returns_values(&self) -> Result<&UInt8Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::NumberOfReturns.as_str())?
+            .u8()
+            .expect("type must be u8");
+        Ok(values)
+    }
+
+    pub fn get_scan_angle_values(&self) -> Result<&Float32Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::ScanAngle.as_str())?
+            .f32()
+            .expect("type must be f32");
+        Ok(values)
+    }
+
+    pub fn get_scan_direction_flag_values(&self) -> Result<&BooleanChunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::ScanDirectionFlag.as_str())?
+            .bool()
+            .expect("type must be bool");
+        Ok(values)
+    }
+
+    pub fn get_edge_of_flight_line_values(&self) -> Result<&BooleanChunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::EdgeOfFlightLine.as_str())?
+            .bool()
+            .expect("type must be bool");
+        Ok(values)
+    }
+
+    pub fn get_synthetic_flag_values(&self) -> Result<&BooleanChunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::SyntheticFlag.as_str())?
+            .bool()
+            .expect("type must be bool");
+        Ok(values)
+    }
+
+    pub fn get_key_point_flag_values(&self) -> Result<&BooleanChunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::KeyPointFlag.as_str())?
+            .bool()
+            .expect("type must be bool");
+        Ok(values)
+    }
+
+    pub fn get_withheld_flag_values(&self) -> Result<&BooleanChunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::WithheldFlag.as_str())?
+            .bool()
+            .expect("type must be bool");
+        Ok(values)
+    }
+
+    pub fn get_overlap_flag_values(&self) -> Result<&BooleanChunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::OverlapFlag.as_str())?
+            .bool()
+            .expect("type must be bool");
+        Ok(values)
+    }
+
+    pub fn get_user_data_values(&self) -> Result<&UInt8Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::UserData.as_str())?
+            .u8()
+            .expect("type must be u8");
+        Ok(values)
+    }
+
+    pub fn get_scanner_channel_values(&self) -> Result<&UInt8Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::ScannerChannel.as_str())?
+            .u8()
+            .expect("type must be u8");
+        Ok(values)
+    }
+
+    pub fn get_nir_values(&self) -> Result<&UInt16Chunked, Error> {
+        let values = self
+            .data_frame
+            .column(PointDataColumnType::Nir.as_str())?
+            .u16()
+            .expect("type must be u16");
+        Ok(values)
+    }
 }
 
 impl PointData {
@@ -585,6 +966,114 @@ impl PointData {
             .column(PointDataColumnType::OctantIndexZ.as_str())
             .is_ok()
     }
+
+    pub fn contains_normal_x_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::NormalX.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_normal_y_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::NormalY.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_normal_z_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::NormalZ.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_row_index_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::RowIndex.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_column_index_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::ColumnIndex.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_classification_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::Classification.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_return_number_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::ReturnNumber.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_number_of_returns_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::NumberOfReturns.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_scan_angle_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::ScanAngle.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_scan_direction_flag_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::ScanDirectionFlag.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_edge_of_flight_line_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::EdgeOfFlightLine.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_synthetic_flag_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::SyntheticFlag.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_key_point_flag_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::KeyPointFlag.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_withheld_flag_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::WithheldFlag.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_overlap_flag_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::OverlapFlag.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_user_data_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::UserData.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_scanner_channel_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::ScannerChannel.as_str())
+            .is_ok()
+    }
+
+    pub fn contains_nir_column(&self) -> bool {
+        self.data_frame
+            .column(PointDataColumnType::Nir.as_str())
+            .is_ok()
+    }
 }
 
 impl PointData {
@@ -621,6 +1110,16 @@ impl PointData {
             && self.contains_octant_index_y_column()
             && self.contains_octant_index_z_column()
     }
+
+    pub fn contains_normals(&self) -> bool {
+        self.contains_normal_x_column()
+            && self.contains_normal_y_column()
+            && self.contains_normal_z_column()
+    }
+
+    pub fn contains_grid_indices(&self) -> bool {
+        self.contains_row_index_column() && self.contains_column_index_column()
+    }
 }
 
 impl PointData {
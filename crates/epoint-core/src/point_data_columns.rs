@@ -4,7 +4,7 @@ use chrono::{DateTime, Timelike, Utc};
 use nalgebra::Point3;
 use palette::Srgb;
 use polars::frame::DataFrame;
-use polars::prelude::{Column, NamedFrom};
+use polars::prelude::Column;
 
 pub struct PointDataColumns {
     pub point: Vec<Point3<f64>>,
@@ -1,6 +1,6 @@
 use crate::Error::{FormatNotSupported, InvalidFileExtension};
 use crate::format::PointCloudFormat;
-use crate::{E57Reader, EpointReader, Error, LasReader, XyzReader};
+use crate::{E57Reader, EpointReader, Error, LasReader, PcdReader, PlyReader, XyzReader};
 use epoint_core::PointCloud;
 use std::path::{Path, PathBuf};
 
@@ -35,9 +35,12 @@ impl AutoReader {
         match self.format {
             PointCloudFormat::Epoint => EpointReader::from_path(&self.path)?.finish(),
             PointCloudFormat::EpointTar => EpointReader::from_path(&self.path)?.finish(),
+            PointCloudFormat::EpointDirectory => EpointReader::from_path(&self.path)?.finish(),
             PointCloudFormat::E57 => E57Reader::from_path(&self.path)?.finish(),
             PointCloudFormat::Las => Ok(LasReader::from_path(&self.path)?.finish()?.0),
             PointCloudFormat::Laz => Ok(LasReader::from_path(&self.path)?.finish()?.0),
+            PointCloudFormat::Pcd => PcdReader::from_path(&self.path)?.finish(),
+            PointCloudFormat::Ply => PlyReader::from_path(&self.path)?.finish(),
             PointCloudFormat::Xyz => XyzReader::from_path(&self.path)?.finish(),
             PointCloudFormat::XyzZst => Err(FormatNotSupported(
                 "XyzZst not supported for writing".to_string(),
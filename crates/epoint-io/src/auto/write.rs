@@ -1,6 +1,6 @@
-use crate::Error::{FormatNotSupported, InvalidFileExtension};
+use crate::Error::InvalidFileExtension;
 use crate::format::PointCloudFormat;
-use crate::{EpointWriter, Error, LasWriter, XyzWriter};
+use crate::{E57Writer, EpointWriter, Error, LasWriter, PcdWriter, PlyWriter, XyzWriter};
 use epoint_core::PointCloud;
 use std::path::{Path, PathBuf};
 
@@ -55,11 +55,14 @@ impl AutoWriter {
             PointCloudFormat::EpointTar => EpointWriter::from_path(self.path)?
                 .with_compressed(false)
                 .finish(point_cloud),
-            PointCloudFormat::E57 => Err(FormatNotSupported(
-                "E57 not supported for reading".to_string(),
-            )),
+            PointCloudFormat::EpointDirectory => {
+                EpointWriter::from_path(self.path)?.finish(point_cloud)
+            }
+            PointCloudFormat::E57 => E57Writer::from_path(self.path)?.finish(point_cloud),
             PointCloudFormat::Las => LasWriter::from_path(self.path)?.finish(point_cloud),
             PointCloudFormat::Laz => LasWriter::from_path(self.path)?.finish(point_cloud),
+            PointCloudFormat::Pcd => PcdWriter::from_path(self.path)?.finish(point_cloud),
+            PointCloudFormat::Ply => PlyWriter::from_path(self.path)?.finish(point_cloud),
             PointCloudFormat::Xyz => XyzWriter::from_path(self.path)?
                 .with_compressed(false)
                 .finish(point_cloud),
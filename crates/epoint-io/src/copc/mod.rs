@@ -0,0 +1,170 @@
+use crate::Error;
+use crate::Error::InvalidCopcFile;
+use ecoord::AxisAlignedBoundingBox;
+use ecoord::octree::OctantIndex;
+use nalgebra::{Point3, Vector3};
+
+pub mod read;
+mod read_impl;
+pub mod write;
+mod write_impl;
+
+pub const FILE_EXTENSION_COPC_FORMAT: &str = "copc.laz";
+
+const COPC_VLR_USER_ID: &str = "copc";
+const COPC_INFO_VLR_RECORD_ID: u16 = 1;
+const COPC_HIERARCHY_EVLR_RECORD_ID: u16 = 1000;
+const COPC_INFO_VLR_SIZE: usize = 160;
+const HIERARCHY_ENTRY_SIZE: usize = 32;
+
+/// Content of the COPC info VLR, which describes the octree of a COPC file.
+///
+/// See also: <https://copc.io>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CopcInfo {
+    /// Center of the root octant.
+    pub center: Point3<f64>,
+    /// Distance from the center to the sides of the root octant.
+    pub halfsize: f64,
+    /// Spacing between the points of the root octant, which is halved with each level.
+    pub spacing: f64,
+    /// File offset of the root hierarchy page.
+    pub root_hierarchy_offset: u64,
+    /// Size of the root hierarchy page in bytes.
+    pub root_hierarchy_size: u64,
+    pub gps_time_minimum: f64,
+    pub gps_time_maximum: f64,
+}
+
+impl CopcInfo {
+    fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < COPC_INFO_VLR_SIZE {
+            return Err(InvalidCopcFile("info VLR is too short".to_string()));
+        }
+        let f64_at = |i: usize| f64::from_le_bytes(data[i..i + 8].try_into().unwrap());
+        let u64_at = |i: usize| u64::from_le_bytes(data[i..i + 8].try_into().unwrap());
+
+        let copc_info = Self {
+            center: Point3::new(f64_at(0), f64_at(8), f64_at(16)),
+            halfsize: f64_at(24),
+            spacing: f64_at(32),
+            root_hierarchy_offset: u64_at(40),
+            root_hierarchy_size: u64_at(48),
+            gps_time_minimum: f64_at(56),
+            gps_time_maximum: f64_at(64),
+        };
+        if copc_info.halfsize.is_nan() || copc_info.halfsize <= 0.0 {
+            return Err(InvalidCopcFile(format!(
+                "halfsize of {} is not positive",
+                copc_info.halfsize
+            )));
+        }
+
+        Ok(copc_info)
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        let mut data: Vec<u8> = [
+            self.center.x,
+            self.center.y,
+            self.center.z,
+            self.halfsize,
+            self.spacing,
+        ]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+        data.extend(self.root_hierarchy_offset.to_le_bytes());
+        data.extend(self.root_hierarchy_size.to_le_bytes());
+        data.extend(self.gps_time_minimum.to_le_bytes());
+        data.extend(self.gps_time_maximum.to_le_bytes());
+        data.resize(COPC_INFO_VLR_SIZE, 0);
+
+        data
+    }
+
+    /// Returns the bounding box of an octant.
+    pub fn get_octant_bounding_box(&self, index: OctantIndex) -> AxisAlignedBoundingBox {
+        let edge_length = 2.0 * self.halfsize / 2.0f64.powi(index.level as i32);
+        let lower_bound = self.center - Vector3::from_element(self.halfsize)
+            + Vector3::new(index.x as f64, index.y as f64, index.z as f64) * edge_length;
+        let upper_bound = lower_bound + Vector3::from_element(edge_length);
+
+        AxisAlignedBoundingBox::new(lower_bound, upper_bound).expect("edge length is positive")
+    }
+}
+
+/// Entry of a hierarchy page, which references the point data of an octant or a child page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HierarchyEntry {
+    index: OctantIndex,
+    /// File offset of the point data chunk or the child page.
+    offset: u64,
+    /// Size of the point data chunk or the child page in bytes.
+    byte_size: i32,
+    /// Number of points, which is -1 for references to child pages.
+    point_count: i32,
+}
+
+impl HierarchyEntry {
+    /// Parses an entry, while entries with negative octant indices are skipped.
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        let i32_at = |i: usize| i32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+        let (level, x, y, z) = (
+            u32::try_from(i32_at(0)).ok()?,
+            u64::try_from(i32_at(4)).ok()?,
+            u64::try_from(i32_at(8)).ok()?,
+            u64::try_from(i32_at(12)).ok()?,
+        );
+
+        Some(Self {
+            index: OctantIndex::new_unchecked(level, x, y, z),
+            offset: u64::from_le_bytes(data[16..24].try_into().unwrap()),
+            byte_size: i32_at(24),
+            point_count: i32_at(28),
+        })
+    }
+
+    /// Returns the byte size, which must not be negative.
+    fn checked_byte_size(&self) -> Result<u64, Error> {
+        u64::try_from(self.byte_size).map_err(|_| {
+            InvalidCopcFile(format!(
+                "hierarchy entry of octant {:?} has the negative byte size {}",
+                self.index, self.byte_size
+            ))
+        })
+    }
+
+    fn to_bytes(self) -> Vec<u8> {
+        let mut data: Vec<u8> = [
+            self.index.level as i32,
+            self.index.x as i32,
+            self.index.y as i32,
+            self.index.z as i32,
+        ]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+        data.extend(self.offset.to_le_bytes());
+        data.extend(self.byte_size.to_le_bytes());
+        data.extend(self.point_count.to_le_bytes());
+
+        data
+    }
+
+    fn is_page_reference(&self) -> bool {
+        self.point_count == -1
+    }
+}
+
+fn intersects(a: &AxisAlignedBoundingBox, b: &AxisAlignedBoundingBox) -> bool {
+    let (a_min, a_max) = (a.lower_bound(), a.upper_bound());
+    let (b_min, b_max) = (b.lower_bound(), b.upper_bound());
+
+    a_min.x <= b_max.x
+        && a_max.x >= b_min.x
+        && a_min.y <= b_max.y
+        && a_max.y >= b_min.y
+        && a_min.z <= b_max.z
+        && a_max.z >= b_min.z
+}
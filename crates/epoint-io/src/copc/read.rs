@@ -0,0 +1,104 @@
+use crate::Error;
+use crate::Error::{InvalidFileExtension, NoFileExtension, NoFileName};
+use crate::copc::read_impl::import_point_cloud_from_copc_reader;
+use crate::copc::{CopcInfo, FILE_EXTENSION_COPC_FORMAT};
+use ecoord::{AxisAlignedBoundingBox, FrameId};
+use epoint_core::PointCloud;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+
+/// `CopcReader` imports a point cloud from a COPC (Cloud Optimized Point Cloud) file.
+///
+/// Only the point data chunks of the octants intersecting the bounds and not exceeding the
+/// maximum level are decompressed. Points of these octants outside the bounds are removed. The
+/// octant of each point can be added as `octant_index_*` columns, which refer to the COPC octree
+/// with the root octant given by [`CopcInfo`].
+///
+/// See also: <https://copc.io>
+#[derive(Debug, Clone)]
+pub struct CopcReader<R: Read + Seek> {
+    reader: R,
+    normalize_colors: bool,
+    reference_frame_id: FrameId,
+    bounds: Option<AxisAlignedBoundingBox>,
+    max_level: Option<u32>,
+    octant_indices: bool,
+}
+
+impl<R: Read + Seek> CopcReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            normalize_colors: false,
+            reference_frame_id: FrameId::global(),
+            bounds: None,
+            max_level: None,
+            octant_indices: false,
+        }
+    }
+
+    pub fn normalize_colors(mut self, normalize_colors: bool) -> Self {
+        self.normalize_colors = normalize_colors;
+        self
+    }
+
+    /// Only reads points within the bounds, including points on the boundary.
+    pub fn with_bounds(mut self, bounds: Option<AxisAlignedBoundingBox>) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Only reads the octants up to the level, where the root octant has level 0.
+    pub fn with_max_level(mut self, max_level: Option<u32>) -> Self {
+        self.max_level = max_level;
+        self
+    }
+
+    /// Adds the octant index of each point as `octant_index_*` columns.
+    pub fn with_octant_indices(mut self, octant_indices: bool) -> Self {
+        self.octant_indices = octant_indices;
+        self
+    }
+
+    pub fn finish(self) -> Result<(PointCloud, CopcReadInfo), Error> {
+        import_point_cloud_from_copc_reader(
+            self.reader,
+            self.bounds,
+            self.max_level,
+            self.octant_indices,
+            self.normalize_colors,
+            self.reference_frame_id,
+        )
+    }
+}
+
+impl CopcReader<BufReader<File>> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file_name = path
+            .as_ref()
+            .file_name()
+            .ok_or(NoFileName())?
+            .to_string_lossy()
+            .to_lowercase();
+        if !file_name.ends_with(&format!(".{FILE_EXTENSION_COPC_FORMAT}")) {
+            let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::open(path)?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopcReadInfo {
+    pub copc_info: CopcInfo,
+    /// EPSG code of the coordinate reference system defined in the (E)VLRs.
+    pub crs_epsg: Option<u32>,
+    /// OGC WKT definition of the coordinate reference system defined in the (E)VLRs.
+    pub crs_wkt: Option<String>,
+}
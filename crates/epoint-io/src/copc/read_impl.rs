@@ -0,0 +1,222 @@
+use crate::Error;
+use crate::Error::InvalidCopcFile;
+use crate::METADATA_KEY_CRS_WKT;
+use crate::copc::read::CopcReadInfo;
+use crate::copc::{
+    COPC_INFO_VLR_RECORD_ID, COPC_VLR_USER_ID, CopcInfo, HIERARCHY_ENTRY_SIZE, HierarchyEntry,
+    intersects,
+};
+use crate::las::crs::read_crs;
+use crate::las::extra_bytes::ExtraBytesDescriptor;
+use crate::las::read_impl::{LasReadFilter, convert_to_data_frame, finish_data_frame};
+use ecoord::{AxisAlignedBoundingBox, FrameId, FrameInfo, TransformTree};
+use epoint_core::Error::NoRemainingPoints;
+use epoint_core::{PointCloud, PointCloudInfo, PointData};
+use laz::LazVlr;
+use laz::record::{LayeredPointRecordDecompressor, RecordDecompressor};
+use polars::prelude::*;
+use std::collections::HashSet;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+pub fn import_point_cloud_from_copc_reader<R: Read + Seek>(
+    mut reader: R,
+    bounds: Option<AxisAlignedBoundingBox>,
+    max_level: Option<u32>,
+    octant_indices: bool,
+    normalize_colors: bool,
+    reference_frame_id: FrameId,
+) -> Result<(PointCloud, CopcReadInfo), Error> {
+    let header = las::Header::new(&mut reader)?;
+    let copc_info_vlr = header
+        .vlrs()
+        .iter()
+        .find(|v| v.user_id == COPC_VLR_USER_ID && v.record_id == COPC_INFO_VLR_RECORD_ID)
+        .ok_or(InvalidCopcFile("info VLR is missing".to_string()))?;
+    let copc_info = CopcInfo::from_bytes(&copc_info_vlr.data)?;
+    let laz_vlr = header.laz_vlr()?;
+    let extra_bytes_descriptors: Vec<ExtraBytesDescriptor> = header
+        .all_vlrs()
+        .filter(|v| ExtraBytesDescriptor::is_extra_bytes_vlr(v))
+        .flat_map(ExtraBytesDescriptor::parse_vlr)
+        .collect();
+    let filter = LasReadFilter {
+        bounds,
+        ..Default::default()
+    };
+
+    let stream_length = reader.seek(SeekFrom::End(0))?;
+    let entries = read_hierarchy(&mut reader, stream_length, &copc_info, bounds, max_level)?;
+    let mut point_data_data_frames: Vec<LazyFrame> = Vec::new();
+    for current_entry in entries {
+        let mut las_points = read_octant_points(
+            &mut reader,
+            stream_length,
+            &header,
+            &laz_vlr,
+            &current_entry,
+        )?;
+        las_points.retain(|p| filter.matches(p, header.gps_time_type()));
+        if las_points.is_empty() {
+            continue;
+        }
+        let number_of_points = las_points.len();
+
+        // the columns are derived from the point format, so that all octants have the same schema
+        let mut point_data = PointData::new_unchecked(convert_to_data_frame(
+            las_points,
+            header.gps_time_type(),
+            header.point_format(),
+            &extra_bytes_descriptors,
+        )?);
+        if octant_indices {
+            point_data.add_octant_indices(vec![current_entry.index; number_of_points])?;
+        }
+        point_data_data_frames.push(point_data.data_frame.lazy());
+    }
+    if point_data_data_frames.is_empty() {
+        return Err(NoRemainingPoints.into());
+    }
+    let point_data = finish_data_frame(
+        concat(point_data_data_frames, Default::default())?.collect()?,
+        normalize_colors,
+    )?;
+
+    let (crs_epsg, crs_wkt) = read_crs(&header)?;
+    let transform_tree = match crs_epsg {
+        Some(crs_epsg) => TransformTree::new(
+            Vec::new(),
+            vec![FrameInfo::new(
+                reference_frame_id.clone(),
+                None,
+                Some(crs_epsg),
+            )],
+        )?,
+        None => TransformTree::default(),
+    };
+    let mut info = PointCloudInfo::new(Some(reference_frame_id));
+    if let Some(crs_wkt) = &crs_wkt {
+        info.metadata
+            .insert(METADATA_KEY_CRS_WKT.to_string(), crs_wkt.clone());
+    }
+    let point_cloud = PointCloud::from_data_frame(point_data, info, transform_tree)?;
+
+    Ok((
+        point_cloud,
+        CopcReadInfo {
+            copc_info,
+            crs_epsg,
+            crs_wkt,
+        },
+    ))
+}
+
+/// Traverses the hierarchy pages and returns the entries of the octants with points, which
+/// intersect the bounds and do not exceed the maximum level.
+///
+/// Child pages of octants outside the bounds or beyond the maximum level are not read. Pages
+/// referenced more than once make the hierarchy invalid, since it would not terminate otherwise.
+fn read_hierarchy<R: Read + Seek>(
+    reader: &mut R,
+    stream_length: u64,
+    copc_info: &CopcInfo,
+    bounds: Option<AxisAlignedBoundingBox>,
+    max_level: Option<u32>,
+) -> Result<Vec<HierarchyEntry>, Error> {
+    let mut entries: Vec<HierarchyEntry> = Vec::new();
+    let mut visited_page_offsets: HashSet<u64> = HashSet::new();
+    let mut pages: Vec<(u64, u64)> = vec![(
+        copc_info.root_hierarchy_offset,
+        copc_info.root_hierarchy_size,
+    )];
+
+    while let Some((offset, size)) = pages.pop() {
+        if !visited_page_offsets.insert(offset) {
+            return Err(InvalidCopcFile(format!(
+                "hierarchy page at offset {offset} is referenced repeatedly"
+            )));
+        }
+        let page = read_byte_range(reader, stream_length, offset, size)?;
+
+        for current_entry in page
+            .chunks_exact(HIERARCHY_ENTRY_SIZE)
+            .filter_map(HierarchyEntry::from_bytes)
+        {
+            if max_level.is_some_and(|l| current_entry.index.level > l) {
+                continue;
+            }
+            if let Some(bounds) = &bounds
+                && !intersects(
+                    &copc_info.get_octant_bounding_box(current_entry.index),
+                    bounds,
+                )
+            {
+                continue;
+            }
+
+            if current_entry.is_page_reference() {
+                pages.push((current_entry.offset, current_entry.checked_byte_size()?));
+            } else if current_entry.point_count > 0 {
+                entries.push(current_entry);
+            }
+        }
+    }
+
+    entries.sort_by_key(|e| (e.index.level, e.index.x, e.index.y, e.index.z));
+    entries.dedup_by_key(|e| e.index);
+    Ok(entries)
+}
+
+/// Decompresses the point data chunk of an octant.
+fn read_octant_points<R: Read + Seek>(
+    reader: &mut R,
+    stream_length: u64,
+    header: &las::Header,
+    laz_vlr: &LazVlr,
+    entry: &HierarchyEntry,
+) -> Result<Vec<las::Point>, Error> {
+    let compressed_data = read_byte_range(
+        reader,
+        stream_length,
+        entry.offset,
+        entry.checked_byte_size()?,
+    )?;
+
+    // each chunk is compressed independently, so that a new decompressor is needed per chunk
+    let mut decompressor = LayeredPointRecordDecompressor::new(Cursor::new(compressed_data));
+    decompressor.set_fields_from(laz_vlr.items())?;
+    let record_length = header.point_format().len() as usize;
+    let mut decompressed_data = vec![0u8; entry.point_count as usize * record_length];
+    decompressor.decompress_many(&mut decompressed_data)?;
+
+    let las_points = decompressed_data
+        .chunks_exact(record_length)
+        .map(|r| {
+            let raw_point = las::raw::Point::read_from(r, header.point_format())?;
+            Ok(las::Point::new(raw_point, header.transforms()))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(las_points)
+}
+
+/// Reads a byte range given by the file, which is checked against the stream length first, so that
+/// corrupt sizes do not cause huge allocations.
+fn read_byte_range<R: Read + Seek>(
+    reader: &mut R,
+    stream_length: u64,
+    offset: u64,
+    size: u64,
+) -> Result<Vec<u8>, Error> {
+    if offset
+        .checked_add(size)
+        .is_none_or(|end| end > stream_length)
+    {
+        return Err(InvalidCopcFile(format!(
+            "byte range of size {size} at offset {offset} exceeds the file length {stream_length}"
+        )));
+    }
+
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut data = vec![0u8; size as usize];
+    reader.read_exact(&mut data)?;
+    Ok(data)
+}
@@ -0,0 +1,97 @@
+use crate::Error;
+use crate::Error::{InvalidFileExtension, NoFileExtension, NoFileName};
+use crate::copc::FILE_EXTENSION_COPC_FORMAT;
+use crate::copc::write_impl::write_copc_format;
+use crate::las::write_impl::LasWriteSettings;
+use crate::las::{LasIntensityScaling, LasVersion};
+use epoint_core::octree::PointCloudOctree;
+use nalgebra::Vector3;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
+use std::path::Path;
+
+/// `CopcWriter` exports a point cloud octree to a COPC (Cloud Optimized Point Cloud) file.
+///
+/// Each octant containing points is written as an independent LAZ chunk, and all octants are
+/// listed in a single hierarchy page. Octants without points are only listed if they are
+/// ancestors of octants with points. The octree should therefore be computed with
+/// [`StorageMode::AllOctants`](ecoord::octree::StorageMode::AllOctants), so that each point is
+/// written once and coarser levels provide an overview of the point cloud.
+///
+/// The points are written in the extended point formats 6-8 of LAS 1.4, as required by COPC.
+/// Columns are otherwise mapped as by [`LasWriter`](crate::LasWriter). Hence, an EPSG code of
+/// the point cloud's frame requires its OGC WKT definition under
+/// [`METADATA_KEY_CRS_WKT`](crate::METADATA_KEY_CRS_WKT).
+///
+/// See also: <https://copc.io>
+#[derive(Debug, Clone)]
+pub struct CopcWriter<W: Write + Seek> {
+    writer: W,
+    scale: Option<Vector3<f64>>,
+    offset: Option<Vector3<f64>>,
+    intensity_scaling: LasIntensityScaling,
+}
+
+impl<W: Write + Seek> CopcWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            scale: None,
+            offset: None,
+            intensity_scaling: LasIntensityScaling::default(),
+        }
+    }
+
+    /// Sets the scale factors of the coordinates, which are 0.001 by default.
+    pub fn with_scale(mut self, scale: Vector3<f64>) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Sets the offset of the coordinates, which is the center of the points by default.
+    pub fn with_offset(mut self, offset: Vector3<f64>) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn with_intensity_scaling(mut self, intensity_scaling: LasIntensityScaling) -> Self {
+        self.intensity_scaling = intensity_scaling;
+        self
+    }
+
+    pub fn finish(self, point_cloud_octree: PointCloudOctree) -> Result<(), Error> {
+        let settings = LasWriteSettings {
+            version: LasVersion::V1_4,
+            scale: self.scale,
+            offset: self.offset,
+            intensity_scaling: self.intensity_scaling,
+            compressed: true,
+            points_per_chunk: None,
+            extended_point_format: true,
+        };
+        write_copc_format(BufWriter::new(self.writer), &point_cloud_octree, &settings)?;
+
+        Ok(())
+    }
+}
+
+impl CopcWriter<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file_name = path
+            .as_ref()
+            .file_name()
+            .ok_or(NoFileName())?
+            .to_string_lossy()
+            .to_lowercase();
+        if !file_name.ends_with(&format!(".{FILE_EXTENSION_COPC_FORMAT}")) {
+            let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::create(path)?;
+        Ok(Self::new(file))
+    }
+}
@@ -0,0 +1,193 @@
+use crate::Error;
+use crate::Error::CopcOctantTooLarge;
+use crate::copc::{
+    COPC_HIERARCHY_EVLR_RECORD_ID, COPC_INFO_VLR_RECORD_ID, COPC_INFO_VLR_SIZE, COPC_VLR_USER_ID,
+    CopcInfo, HierarchyEntry,
+};
+use crate::las::write_impl::{LasWriteSettings, create_header_builder};
+use ecoord::octree::OctantIndex;
+use epoint_core::octree::PointCloudOctree;
+use laz::laszip::{ChunkTable, ChunkTableEntry};
+use laz::record::{LayeredPointRecordCompressor, RecordCompressor};
+use std::collections::BTreeSet;
+use std::io::{Cursor, Seek, SeekFrom, Write};
+
+const LASZIP_VLR_USER_ID: &str = "laszip encoded";
+const LASZIP_VLR_RECORD_ID: u16 = 22204;
+
+pub fn write_copc_format<W: Write + Seek>(
+    mut writer: W,
+    point_cloud_octree: &PointCloudOctree,
+    settings: &LasWriteSettings,
+) -> Result<(), Error> {
+    let point_cloud = &point_cloud_octree.point_cloud;
+    if point_cloud.point_data.is_empty() {
+        return Err(epoint_core::Error::NoRemainingPoints.into());
+    }
+
+    let (mut builder, point_encoder) = create_header_builder(point_cloud, settings)?;
+    let point_format = builder.point_format;
+    let laz_vlr = laz::LazVlrBuilder::default()
+        .with_point_format(point_format.to_u8()?, point_format.extra_bytes)?
+        .with_variable_chunk_size()
+        .build();
+    let mut laz_vlr_data: Vec<u8> = Vec::new();
+    laz_vlr.write_to(&mut laz_vlr_data)?;
+
+    // the COPC info VLR must be the first VLR and is completed after writing the octants
+    builder.vlrs.insert(
+        0,
+        las::Vlr {
+            user_id: COPC_VLR_USER_ID.to_string(),
+            record_id: COPC_INFO_VLR_RECORD_ID,
+            description: "COPC info".to_string(),
+            data: vec![0; COPC_INFO_VLR_SIZE],
+        },
+    );
+    builder.vlrs.push(las::Vlr {
+        user_id: LASZIP_VLR_USER_ID.to_string(),
+        record_id: LASZIP_VLR_RECORD_ID,
+        description: "laz variable chunks".to_string(),
+        data: laz_vlr_data,
+    });
+    let mut header = builder.into_header()?;
+    let transforms = *header.transforms();
+
+    // header, VLRs and chunk table offset are written once the point counts and bounds are known
+    let start_offset = writer.stream_position()?;
+    let chunks_offset = header.clone().into_raw()?.offset_to_point_data as u64 + 8;
+    writer.seek(SeekFrom::Start(start_offset + chunks_offset))?;
+
+    let occupied_indices: BTreeSet<OctantIndex> = point_cloud_octree
+        .octree
+        .cells()
+        .iter()
+        .filter(|(_, points)| !points.is_empty())
+        .map(|(index, _)| *index)
+        .collect();
+    // ancestors without points are listed, so that the hierarchy is traversable from the root
+    let all_indices: BTreeSet<OctantIndex> = occupied_indices
+        .iter()
+        .flat_map(|i| i.get_ancestors().into_iter().chain(std::iter::once(*i)))
+        .collect();
+
+    let mut hierarchy_entries: Vec<HierarchyEntry> = Vec::with_capacity(all_indices.len());
+    let mut chunk_table = ChunkTable::with_capacity(occupied_indices.len());
+    let mut gps_time_range: Option<(f64, f64)> = None;
+    let mut chunk_offset = chunks_offset;
+    for current_index in all_indices {
+        if !occupied_indices.contains(&current_index) {
+            hierarchy_entries.push(HierarchyEntry {
+                index: current_index,
+                offset: 0,
+                byte_size: 0,
+                point_count: 0,
+            });
+            continue;
+        }
+
+        let octant_point_cloud = point_cloud_octree.extract_octant(current_index)?;
+        let points = point_encoder.encode(&octant_point_cloud.point_data)?;
+
+        let mut compressor = LayeredPointRecordCompressor::new(Cursor::new(Vec::new()));
+        compressor.set_fields_from(laz_vlr.items())?;
+        let mut record: Vec<u8> = Vec::with_capacity(point_format.len() as usize);
+        for current_point in &points {
+            header.add_point(current_point);
+            if let Some(gps_time) = current_point.gps_time {
+                gps_time_range = Some(gps_time_range.map_or((gps_time, gps_time), |(min, max)| {
+                    (min.min(gps_time), max.max(gps_time))
+                }));
+            }
+
+            record.clear();
+            current_point
+                .clone()
+                .into_raw(&transforms)?
+                .write_to(&mut record, &point_format)?;
+            compressor.compress_next(&record)?;
+        }
+        compressor.done()?;
+        let chunk = compressor.into_inner().into_inner();
+        let (Ok(byte_size), Ok(point_count)) =
+            (i32::try_from(chunk.len()), i32::try_from(points.len()))
+        else {
+            return Err(CopcOctantTooLarge {
+                byte_size: chunk.len(),
+                point_count: points.len(),
+            });
+        };
+        writer.write_all(&chunk)?;
+
+        hierarchy_entries.push(HierarchyEntry {
+            index: current_index,
+            offset: chunk_offset,
+            byte_size,
+            point_count,
+        });
+        chunk_table.push(ChunkTableEntry {
+            point_count: points.len() as u64,
+            byte_count: chunk.len() as u64,
+        });
+        chunk_offset += chunk.len() as u64;
+    }
+
+    let chunk_table_offset = chunk_offset;
+    chunk_table.write_to(&mut writer, &laz_vlr)?;
+    let evlr_offset = writer.stream_position()? - start_offset;
+
+    let hierarchy_data: Vec<u8> = hierarchy_entries
+        .iter()
+        .flat_map(|e| e.to_bytes())
+        .collect();
+    let root_hierarchy_size = hierarchy_data.len() as u64;
+    let hierarchy_evlr = las::Vlr {
+        user_id: COPC_VLR_USER_ID.to_string(),
+        record_id: COPC_HIERARCHY_EVLR_RECORD_ID,
+        description: "EPT hierarchy".to_string(),
+        data: hierarchy_data,
+    }
+    .into_raw(true)?;
+    // the hierarchy page follows the EVLR header
+    let root_hierarchy_offset = evlr_offset + 60;
+    hierarchy_evlr.write_to(&mut writer)?;
+    let end_offset = writer.stream_position()?;
+
+    let enclosing_cube = point_cloud_octree.octree.bounds().enclosing_cube();
+    let root_point_count = point_cloud_octree
+        .octree
+        .cell(OctantIndex::origin())
+        .map_or(0, |p| p.len());
+    let (gps_time_minimum, gps_time_maximum) = gps_time_range.unwrap_or_default();
+    let copc_info = CopcInfo {
+        center: enclosing_cube.center(),
+        halfsize: enclosing_cube.half_edge_length(),
+        spacing: enclosing_cube.edge_length() / (root_point_count.max(1) as f64).sqrt(),
+        root_hierarchy_offset,
+        root_hierarchy_size,
+        gps_time_minimum,
+        gps_time_maximum,
+    };
+
+    let mut raw_header = header.clone().into_raw()?;
+    raw_header.evlr = Some(las::raw::header::Evlr {
+        start_of_first_evlr: evlr_offset,
+        number_of_evlrs: 1,
+    });
+    writer.seek(SeekFrom::Start(start_offset))?;
+    raw_header.write_to(&mut writer)?;
+    for current_vlr in header.vlrs() {
+        let mut current_vlr = current_vlr.clone();
+        if current_vlr.user_id == COPC_VLR_USER_ID
+            && current_vlr.record_id == COPC_INFO_VLR_RECORD_ID
+        {
+            current_vlr.data = copc_info.to_bytes();
+        }
+        current_vlr.into_raw(false)?.write_to(&mut writer)?;
+    }
+    writer.write_all(&(chunk_table_offset as i64).to_le_bytes())?;
+    writer.seek(SeekFrom::Start(end_offset))?;
+    writer.flush()?;
+
+    Ok(())
+}
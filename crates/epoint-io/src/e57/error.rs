@@ -3,12 +3,15 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum Error {
     #[error(transparent)]
-    EpointError(#[from] epoint_core::Error),
+    Epoint(#[from] epoint_core::Error),
     #[error(transparent)]
-    EpointTransformError(#[from] epoint_transform::Error),
+    EpointTransform(#[from] epoint_transform::Error),
 
     #[error(transparent)]
-    E57Error(#[from] e57::Error),
+    Polars(#[from] polars::error::PolarsError),
+
+    #[error(transparent)]
+    E57(#[from] e57::Error),
 
     #[error("feature not supported")]
     NotSupported(&'static str),
@@ -1,5 +1,7 @@
 pub mod error;
 pub mod read;
 mod read_impl;
+pub mod write;
+mod write_impl;
 
 pub const FILE_EXTENSION_E57_FORMAT: &str = "e57";
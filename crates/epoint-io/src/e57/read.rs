@@ -1,7 +1,7 @@
 use crate::Error;
 use crate::Error::{InvalidFileExtension, NoFileExtension};
 use crate::e57::FILE_EXTENSION_E57_FORMAT;
-use crate::e57::read_impl::import_point_cloud_from_e57_file;
+use crate::e57::read_impl::{import_point_cloud_from_e57_file, import_point_clouds_from_e57_file};
 use ecoord::FrameId;
 use epoint_core::PointCloud;
 use std::fmt::Debug;
@@ -11,11 +11,21 @@ use std::path::Path;
 
 /// `E57Reader` imports a point cloud from a E57 file.
 ///
+/// Each data3D section (scan) is assigned to a frame, whose pose relative to the reference frame
+/// is added to the transform tree. A single scan is assigned to the sensor frame. Files with
+/// multiple scans use the scan names as frame ids, or the scan GUIDs if the names are missing or
+/// not unique. [`finish`](Self::finish) merges all scans into one point cloud with a `frame_id`
+/// column, whereas [`finish_scans`](Self::finish_scans) returns a point cloud per scan. When
+/// merging, attributes such as intensities or colors are null for the scans not providing them.
+///
+/// Spherical coordinates and row/column grid indices are imported if the scan provides them.
+/// Intensities are imported as stored, without normalizing them by the intensity limits.
 #[derive(Debug, Clone)]
 pub struct E57Reader<R: Read + Seek> {
     reader: R,
     reference_frame_id: FrameId,
     sensor_frame_id: FrameId,
+    keep_invalid_points: bool,
 }
 
 impl<R: Read + Seek> E57Reader<R> {
@@ -24,18 +34,40 @@ impl<R: Read + Seek> E57Reader<R> {
             reader,
             reference_frame_id: FrameId::global(),
             sensor_frame_id: FrameId::sensor(),
+            keep_invalid_points: false,
         }
     }
 
+    /// Keeps points without valid Cartesian coordinates, such as invalid returns or pure
+    /// directions, with NaN coordinates. Points with invalid intensities or time stamps are kept
+    /// with null values.
+    pub fn with_keep_invalid_points(mut self, keep_invalid_points: bool) -> Self {
+        self.keep_invalid_points = keep_invalid_points;
+        self
+    }
+
     pub fn finish(self) -> Result<PointCloud, Error> {
         let point_cloud = import_point_cloud_from_e57_file(
             self.reader,
             self.reference_frame_id,
             self.sensor_frame_id,
+            self.keep_invalid_points,
         )?;
 
         Ok(point_cloud)
     }
+
+    /// Reads each scan as separate point cloud.
+    pub fn finish_scans(self) -> Result<Vec<PointCloud>, Error> {
+        let point_clouds = import_point_clouds_from_e57_file(
+            self.reader,
+            self.reference_frame_id,
+            self.sensor_frame_id,
+            self.keep_invalid_points,
+        )?;
+
+        Ok(point_clouds)
+    }
 }
 
 impl E57Reader<File> {
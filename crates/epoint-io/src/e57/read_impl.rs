@@ -1,104 +1,227 @@
 use crate::e57::error::Error;
 use crate::e57::error::Error::{NoPointCloudsInFile, NotSupported};
-use e57::{CartesianCoordinate, PointCloudReaderSimple};
+use crate::las::GPS_EPOCH_REFERENCE_TIMESTAMP;
+use chrono::{DateTime, TimeDelta, Utc};
+use e57::{ColorLimits, PointCloudReaderRaw, Record, RecordDataType, RecordName, RecordValue};
 use ecoord::{FrameId, StaticTransform, Transform, TransformEdge, TransformTree};
 use epoint_core::{PointCloud, PointCloudInfo, PointDataColumnType};
 use epoint_transform::merge;
 use nalgebra::{Quaternion, UnitQuaternion, Vector3};
 use polars::frame::DataFrame;
-use polars::prelude::{Column, NamedFrom};
+use polars::prelude::{Column, DataType, PlSmallStr};
+use std::collections::HashSet;
 use std::io::{BufReader, Read, Seek};
 
+const NANOS_PER_SECOND: f64 = 1.0e9;
+
 pub fn import_point_cloud_from_e57_file<R: Read + Seek>(
     reader: R,
     reference_frame_id: FrameId,
     sensor_frame_id: FrameId,
+    keep_invalid_points: bool,
 ) -> Result<PointCloud, Error> {
-    let mut e57_reader = e57::E57Reader::new(BufReader::new(reader))?;
-    if e57_reader.pointclouds().is_empty() {
-        return Err(NoPointCloudsInFile());
+    let mut point_clouds = import_point_clouds_from_e57_file(
+        reader,
+        reference_frame_id,
+        sensor_frame_id,
+        keep_invalid_points,
+    )?;
+    if point_clouds.len() == 1 {
+        return Ok(point_clouds.pop().expect("must contain one point cloud"));
     }
-    if e57_reader.pointclouds().len() > 1 {
-        return Err(NotSupported(
-            "reading e57 file with multiple point clouds is not supported",
-        ));
+
+    // attributes not provided by every scan are filled with nulls, so that the scans have the
+    // same columns
+    let mut columns: Vec<(PlSmallStr, DataType)> = Vec::new();
+    for current_point_cloud in point_clouds.iter() {
+        for current_column in current_point_cloud.point_data.data_frame.get_columns() {
+            if !columns
+                .iter()
+                .any(|(name, _)| name == current_column.name())
+            {
+                columns.push((
+                    current_column.name().clone(),
+                    current_column.dtype().clone(),
+                ));
+            }
+        }
+    }
+    for current_point_cloud in point_clouds.iter_mut() {
+        let data_frame = &mut current_point_cloud.point_data.data_frame;
+        let height = data_frame.height();
+        for (name, data_type) in columns.iter() {
+            if data_frame.column(name).is_err() {
+                data_frame.with_column(Column::full_null(name.clone(), height, data_type))?;
+            }
+        }
+        *data_frame = data_frame.select(columns.iter().map(|(name, _)| name.clone()))?;
     }
 
-    let mut point_clouds: Vec<PointCloud> = Vec::new();
-    for current_e57_point_cloud in e57_reader.pointclouds().into_iter() {
-        let mut e57_point_cloud_reader = e57_reader.pointcloud_simple(&current_e57_point_cloud)?;
-        e57_point_cloud_reader.apply_pose(false);
+    // the scan frame is moved from the info into the points, so that the scans can be merged
+    for current_point_cloud in point_clouds.iter_mut() {
+        let frame_id = current_point_cloud
+            .info
+            .frame_id
+            .take()
+            .expect("scan frame id must be set");
+        current_point_cloud
+            .point_data
+            .add_unique_frame_id(frame_id)?;
+    }
 
-        if current_e57_point_cloud.acquisition_start.is_some()
-            || current_e57_point_cloud.acquisition_end.is_some()
-        {
-            return Err(NotSupported(
-                "times acquisition_start and acquisition_end are not yet supported",
-            ));
-        }
+    let merged_point_cloud = merge(point_clouds)?;
+    Ok(merged_point_cloud)
+}
+
+pub fn import_point_clouds_from_e57_file<R: Read + Seek>(
+    reader: R,
+    reference_frame_id: FrameId,
+    sensor_frame_id: FrameId,
+    keep_invalid_points: bool,
+) -> Result<Vec<PointCloud>, Error> {
+    let mut e57_reader = e57::E57Reader::new(BufReader::new(reader))?;
+    let e57_point_clouds = e57_reader.pointclouds();
+    if e57_point_clouds.is_empty() {
+        return Err(NoPointCloudsInFile());
+    }
+    let scan_frame_ids = derive_scan_frame_ids(&e57_point_clouds, &sensor_frame_id);
+
+    let mut point_clouds: Vec<PointCloud> = Vec::with_capacity(e57_point_clouds.len());
+    for (current_e57_point_cloud, current_frame_id) in
+        e57_point_clouds.iter().zip(scan_frame_ids.iter())
+    {
+        let e57_point_cloud_reader = e57_reader.pointcloud_raw(current_e57_point_cloud)?;
 
         let point_cloud = import_individual_point_cloud_from_e57_file(
             e57_point_cloud_reader,
-            &current_e57_point_cloud.transform,
+            current_e57_point_cloud,
             &reference_frame_id,
-            &sensor_frame_id,
-            current_e57_point_cloud.has_timestamp(),
-            current_e57_point_cloud.has_intensity(),
-            current_e57_point_cloud.has_color(),
+            current_frame_id,
+            keep_invalid_points,
         )?;
 
         point_clouds.push(point_cloud);
     }
 
-    let merged_point_cloud = merge(point_clouds)?;
-    Ok(merged_point_cloud)
+    Ok(point_clouds)
+}
+
+/// Derives a frame id for each scan.
+///
+/// A single scan is assigned to the sensor frame. Multiple scans are identified by their name, or
+/// by their GUID if the names are missing or ambiguous, and by their index as last resort.
+fn derive_scan_frame_ids(
+    e57_point_clouds: &[e57::PointCloud],
+    sensor_frame_id: &FrameId,
+) -> Vec<FrameId> {
+    if e57_point_clouds.len() == 1 {
+        return vec![sensor_frame_id.clone()];
+    }
+
+    let names: Vec<Option<&String>> = e57_point_clouds.iter().map(|p| p.name.as_ref()).collect();
+    if is_complete_and_unique(&names) {
+        return names
+            .into_iter()
+            .map(|n| n.expect("must be set").as_str().into())
+            .collect();
+    }
+
+    let guids: Vec<Option<&String>> = e57_point_clouds.iter().map(|p| p.guid.as_ref()).collect();
+    if is_complete_and_unique(&guids) {
+        return guids
+            .into_iter()
+            .map(|g| g.expect("must be set").as_str().into())
+            .collect();
+    }
+
+    (0..e57_point_clouds.len())
+        .map(|i| format!("scan_{i}").into())
+        .collect()
 }
 
+fn is_complete_and_unique(values: &[Option<&String>]) -> bool {
+    let distinct_values: HashSet<&String> = values.iter().flatten().copied().collect();
+    distinct_values.len() == values.len() && values.iter().all(|v| v.is_some_and(|v| !v.is_empty()))
+}
+
+/// Imports a single scan.
+///
+/// Points without valid Cartesian coordinates are skipped, unless `keep_invalid_points` is set, in
+/// which case their coordinates are NaN. The same applies to points with invalid intensities or
+/// time stamps, which are kept as null values.
 pub fn import_individual_point_cloud_from_e57_file<T: Read + Seek>(
-    e57_point_cloud_reader: PointCloudReaderSimple<T>,
-    transform: &Option<e57::Transform>,
+    e57_point_cloud_reader: PointCloudReaderRaw<T>,
+    e57_point_cloud: &e57::PointCloud,
     reference_frame_id: &FrameId,
     sensor_frame_id: &FrameId,
-    has_timestamp_column: bool,
-    has_intensity_column: bool,
-    has_color_columns: bool,
+    keep_invalid_points: bool,
 ) -> Result<PointCloud, Error> {
-    if has_timestamp_column {
-        return Err(NotSupported("timestamp column is not supported"));
-    }
+    let has_timestamp_columns = e57_point_cloud.has_timestamp();
+    let has_intensity_column = e57_point_cloud.has_intensity();
+    let has_color_columns = e57_point_cloud.has_color();
+    let has_spherical_columns = e57_point_cloud.has_spherical();
+    let has_grid_index_columns = e57_point_cloud.has_row_column();
+    let point_decoder = E57PointDecoder::new(e57_point_cloud)?;
 
     let mut x_values: Vec<f64> = Vec::new();
     let mut y_values: Vec<f64> = Vec::new();
     let mut z_values: Vec<f64> = Vec::new();
-    let mut intensity_values: Vec<f32> = Vec::new();
+    let mut timestamp_values: Vec<Option<DateTime<Utc>>> = Vec::new();
+    let mut intensity_values: Vec<Option<f32>> = Vec::new();
     let mut color_red_values: Vec<u16> = Vec::new();
     let mut color_green_values: Vec<u16> = Vec::new();
     let mut color_blue_values: Vec<u16> = Vec::new();
+    let mut spherical_azimuth_values: Vec<f64> = Vec::new();
+    let mut spherical_elevation_values: Vec<f64> = Vec::new();
+    let mut spherical_range_values: Vec<f64> = Vec::new();
+    let mut row_index_values: Vec<u32> = Vec::new();
+    let mut column_index_values: Vec<u32> = Vec::new();
+
+    for current_values in e57_point_cloud_reader {
+        let current_values = current_values?;
 
-    for current_e57_point in e57_point_cloud_reader.flatten() {
         // check if point contains complete information
-        match current_e57_point.cartesian {
-            CartesianCoordinate::Valid { .. } => {}
-            CartesianCoordinate::Direction { .. } => continue,
-            CartesianCoordinate::Invalid => continue,
+        let (x, y, z) = match point_decoder.cartesian(&current_values)? {
+            Some(cartesian) => cartesian,
+            None if keep_invalid_points => (f64::NAN, f64::NAN, f64::NAN),
+            None => continue,
+        };
+        let intensity = point_decoder.intensity(&current_values)?;
+        if has_intensity_column && intensity.is_none() && !keep_invalid_points {
+            continue;
         }
-        if has_intensity_column && current_e57_point.intensity.is_none() {
+        let timestamp = point_decoder.timestamp(&current_values)?;
+        if has_timestamp_columns && timestamp.is_none() && !keep_invalid_points {
             continue;
         }
 
         // parse point
-        if let CartesianCoordinate::Valid { x, y, z } = current_e57_point.cartesian {
-            x_values.push(x);
-            y_values.push(y);
-            z_values.push(z);
+        x_values.push(x);
+        y_values.push(y);
+        z_values.push(z);
+        if has_timestamp_columns {
+            timestamp_values.push(timestamp);
         }
-        if let Some(intensity) = current_e57_point.intensity {
+        if has_intensity_column {
             intensity_values.push(intensity);
         }
-        if let Some(color) = current_e57_point.color {
-            color_red_values.push((color.red * u16::MAX as f32) as u16);
-            color_green_values.push((color.green * u16::MAX as f32) as u16);
-            color_blue_values.push((color.blue * u16::MAX as f32) as u16);
+        if has_color_columns {
+            // points with invalid color are kept in black
+            let [red, green, blue] = point_decoder.color(&current_values)?.unwrap_or_default();
+            color_red_values.push(red);
+            color_green_values.push(green);
+            color_blue_values.push(blue);
+        }
+        if has_spherical_columns {
+            let (range, azimuth, elevation) = point_decoder.spherical(&current_values)?;
+            spherical_azimuth_values.push(azimuth);
+            spherical_elevation_values.push(elevation);
+            spherical_range_values.push(range);
+        }
+        if has_grid_index_columns {
+            let (row, column) = point_decoder.grid_index(&current_values)?;
+            row_index_values.push(row);
+            column_index_values.push(column);
         }
     }
 
@@ -108,6 +231,22 @@ pub fn import_individual_point_cloud_from_e57_file<T: Read + Seek>(
         Column::new(PointDataColumnType::Z.into(), z_values),
     ];
 
+    if has_timestamp_columns {
+        point_data_columns.push(Column::new(
+            PointDataColumnType::TimestampSecond.into(),
+            timestamp_values
+                .iter()
+                .map(|t| t.map(|t| t.timestamp()))
+                .collect::<Vec<Option<i64>>>(),
+        ));
+        point_data_columns.push(Column::new(
+            PointDataColumnType::TimestampNanoSecond.into(),
+            timestamp_values
+                .iter()
+                .map(|t| t.map(|t| t.timestamp_subsec_nanos()))
+                .collect::<Vec<Option<u32>>>(),
+        ));
+    }
     if has_intensity_column {
         point_data_columns.push(Column::new(
             PointDataColumnType::Intensity.into(),
@@ -128,9 +267,37 @@ pub fn import_individual_point_cloud_from_e57_file<T: Read + Seek>(
             color_blue_values,
         ));
     }
+    if has_spherical_columns {
+        point_data_columns.push(Column::new(
+            PointDataColumnType::SphericalAzimuth.into(),
+            spherical_azimuth_values,
+        ));
+        point_data_columns.push(Column::new(
+            PointDataColumnType::SphericalElevation.into(),
+            spherical_elevation_values,
+        ));
+        point_data_columns.push(Column::new(
+            PointDataColumnType::SphericalRange.into(),
+            spherical_range_values,
+        ));
+    }
+    if has_grid_index_columns {
+        point_data_columns.push(Column::new(
+            PointDataColumnType::RowIndex.into(),
+            row_index_values,
+        ));
+        point_data_columns.push(Column::new(
+            PointDataColumnType::ColumnIndex.into(),
+            column_index_values,
+        ));
+    }
 
     let point_data = DataFrame::new(point_data_columns).expect("should work");
-    let transform_tree = parse_transform_tree(transform, reference_frame_id, sensor_frame_id);
+    let transform_tree = parse_transform_tree(
+        &e57_point_cloud.transform,
+        reference_frame_id,
+        sensor_frame_id,
+    );
     let point_cloud_info = PointCloudInfo::new(Some(sensor_frame_id.clone()));
 
     let point_cloud = PointCloud::from_data_frame(point_data, point_cloud_info, transform_tree)?;
@@ -138,19 +305,277 @@ pub fn import_individual_point_cloud_from_e57_file<T: Read + Seek>(
     Ok(point_cloud)
 }
 
+/// Decodes the raw values of a scan's points, whose records are located once per scan.
+///
+/// Invalid states are evaluated as in the simple reader of the e57 crate, but intensities are
+/// returned as stored and time stamps are decoded in the same pass.
+struct E57PointDecoder<'a> {
+    prototype: &'a [Record],
+    cartesian: Option<[usize; 3]>,
+    cartesian_invalid: Option<usize>,
+    spherical: Option<[usize; 3]>,
+    spherical_invalid: Option<usize>,
+    color: Option<[(usize, f64, f64); 3]>,
+    color_invalid: Option<usize>,
+    intensity: Option<usize>,
+    intensity_invalid: Option<usize>,
+    grid_index: Option<[usize; 2]>,
+    timestamp: Option<(usize, DateTime<Utc>)>,
+    timestamp_invalid: Option<usize>,
+}
+
+impl<'a> E57PointDecoder<'a> {
+    fn new(e57_point_cloud: &'a e57::PointCloud) -> Result<Self, Error> {
+        let prototype = e57_point_cloud.prototype.as_slice();
+        let find_record = |name: RecordName| prototype.iter().position(|r| r.name == name);
+        let find_records = |names: [RecordName; 3]| {
+            let [a, b, c] = names.map(find_record);
+            Some([a?, b?, c?])
+        };
+
+        let color_limits = e57_point_cloud.color_limits.as_ref();
+        let color_limit = |select: fn(&ColorLimits) -> &Option<RecordValue>| {
+            color_limits.and_then(|l| select(l).as_ref())
+        };
+        let color = find_records([
+            RecordName::ColorRed,
+            RecordName::ColorGreen,
+            RecordName::ColorBlue,
+        ])
+        .map(|[red, green, blue]| {
+            [
+                (
+                    red,
+                    color_limit(|l| &l.red_min),
+                    color_limit(|l| &l.red_max),
+                ),
+                (
+                    green,
+                    color_limit(|l| &l.green_min),
+                    color_limit(|l| &l.green_max),
+                ),
+                (
+                    blue,
+                    color_limit(|l| &l.blue_min),
+                    color_limit(|l| &l.blue_max),
+                ),
+            ]
+            .map(|(index, min, max)| {
+                let (min, max) = derive_color_range(&prototype[index].data_type, min, max);
+                (index, min, max)
+            })
+        });
+
+        let timestamp = match find_record(RecordName::TimeStamp) {
+            Some(index) => Some((index, derive_base_time(e57_point_cloud)?)),
+            None => None,
+        };
+        let grid_index = find_record(RecordName::RowIndex)
+            .zip(find_record(RecordName::ColumnIndex))
+            .map(|(row, column)| [row, column]);
+
+        Ok(Self {
+            prototype,
+            cartesian: find_records([
+                RecordName::CartesianX,
+                RecordName::CartesianY,
+                RecordName::CartesianZ,
+            ]),
+            cartesian_invalid: find_record(RecordName::CartesianInvalidState),
+            spherical: find_records([
+                RecordName::SphericalRange,
+                RecordName::SphericalAzimuth,
+                RecordName::SphericalElevation,
+            ]),
+            spherical_invalid: find_record(RecordName::SphericalInvalidState),
+            color,
+            color_invalid: find_record(RecordName::IsColorInvalid),
+            intensity: find_record(RecordName::Intensity),
+            intensity_invalid: find_record(RecordName::IsIntensityInvalid),
+            grid_index,
+            timestamp,
+            timestamp_invalid: find_record(RecordName::IsTimeStampInvalid),
+        })
+    }
+
+    fn value(&self, values: &[RecordValue], index: usize) -> Result<f64, Error> {
+        Ok(values[index].to_f64(&self.prototype[index].data_type)?)
+    }
+
+    /// Returns the invalid state, which is zero for valid values.
+    fn state(&self, values: &[RecordValue], index: Option<usize>) -> Result<i64, Error> {
+        match index {
+            Some(index) => Ok(values[index].to_i64(&self.prototype[index].data_type)?),
+            None => Ok(0),
+        }
+    }
+
+    /// Returns the valid Cartesian coordinates, which are derived from the spherical coordinates
+    /// if necessary.
+    fn cartesian(&self, values: &[RecordValue]) -> Result<Option<(f64, f64, f64)>, Error> {
+        if let Some([x, y, z]) = self.cartesian
+            && self.state(values, self.cartesian_invalid)? == 0
+        {
+            return Ok(Some((
+                self.value(values, x)?,
+                self.value(values, y)?,
+                self.value(values, z)?,
+            )));
+        }
+
+        let (range, azimuth, elevation) = self.spherical(values)?;
+        if range.is_nan() {
+            return Ok(None);
+        }
+        let cos_elevation = elevation.cos();
+        Ok(Some((
+            range * cos_elevation * azimuth.cos(),
+            range * cos_elevation * azimuth.sin(),
+            range * elevation.sin(),
+        )))
+    }
+
+    /// Returns range, azimuth and elevation, which are NaN if invalid. Directions have no range.
+    fn spherical(&self, values: &[RecordValue]) -> Result<(f64, f64, f64), Error> {
+        let Some([range, azimuth, elevation]) = self.spherical else {
+            return Ok((f64::NAN, f64::NAN, f64::NAN));
+        };
+
+        match self.state(values, self.spherical_invalid)? {
+            0 => Ok((
+                self.value(values, range)?,
+                self.value(values, azimuth)?,
+                self.value(values, elevation)?,
+            )),
+            1 => Ok((
+                f64::NAN,
+                self.value(values, azimuth)?,
+                self.value(values, elevation)?,
+            )),
+            _ => Ok((f64::NAN, f64::NAN, f64::NAN)),
+        }
+    }
+
+    /// Returns the color normalized to the full range of `u16`.
+    fn color(&self, values: &[RecordValue]) -> Result<Option<[u16; 3]>, Error> {
+        let Some(color) = self.color else {
+            return Ok(None);
+        };
+        if self.state(values, self.color_invalid)? != 0 {
+            return Ok(None);
+        }
+
+        let mut channels = [0; 3];
+        for (channel, (index, min, max)) in channels.iter_mut().zip(color) {
+            let value = self.value(values, index)?;
+            let normalized = if max > min {
+                (value.clamp(min, max) - min) / (max - min)
+            } else {
+                0.0
+            };
+            *channel = (normalized * u16::MAX as f64).round() as u16;
+        }
+        Ok(Some(channels))
+    }
+
+    fn intensity(&self, values: &[RecordValue]) -> Result<Option<f32>, Error> {
+        match self.intensity {
+            Some(index) if self.state(values, self.intensity_invalid)? == 0 => {
+                Ok(Some(self.value(values, index)? as f32))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn grid_index(&self, values: &[RecordValue]) -> Result<(u32, u32), Error> {
+        let Some([row, column]) = self.grid_index else {
+            return Ok((0, 0));
+        };
+        let row = values[row].to_i64(&self.prototype[row].data_type)?;
+        let column = values[column].to_i64(&self.prototype[column].data_type)?;
+        Ok((row.max(0) as u32, column.max(0) as u32))
+    }
+
+    /// Returns the time stamp, which is given in seconds relative to the acquisition start.
+    fn timestamp(&self, values: &[RecordValue]) -> Result<Option<DateTime<Utc>>, Error> {
+        match self.timestamp {
+            Some((index, base_time)) if self.state(values, self.timestamp_invalid)? == 0 => {
+                let offset = self.value(values, index)?;
+                Ok(Some(
+                    base_time + TimeDelta::nanoseconds((offset * NANOS_PER_SECOND).round() as i64),
+                ))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Derives the range of a color channel from the color limits, or from the record's data type if
+/// the limits are missing.
+fn derive_color_range(
+    data_type: &RecordDataType,
+    min: Option<&RecordValue>,
+    max: Option<&RecordValue>,
+) -> (f64, f64) {
+    let limit_value = |value: Option<&RecordValue>| match value {
+        Some(RecordValue::Double(value)) => Some(*value),
+        Some(RecordValue::Single(value)) => Some(*value as f64),
+        Some(RecordValue::Integer(value)) => Some(*value as f64),
+        _ => None,
+    };
+    if let (Some(min), Some(max)) = (limit_value(min), limit_value(max)) {
+        return (min, max);
+    }
+
+    match *data_type {
+        RecordDataType::Single { min, max } => (
+            min.unwrap_or(f32::MIN) as f64,
+            max.unwrap_or(f32::MAX) as f64,
+        ),
+        RecordDataType::Double { min, max } => (min.unwrap_or(f64::MIN), max.unwrap_or(f64::MAX)),
+        RecordDataType::ScaledInteger {
+            min,
+            max,
+            scale,
+            offset,
+        } => (min as f64 * scale + offset, max as f64 * scale + offset),
+        RecordDataType::Integer { min, max } => (min as f64, max as f64),
+    }
+}
+
+/// Derives the acquisition start, to which the time stamps of a scan are relative.
+///
+/// The acquisition start is given in GPS time. Without acquisition start, the time stamps are
+/// interpreted as GPS time.
+fn derive_base_time(e57_point_cloud: &e57::PointCloud) -> Result<DateTime<Utc>, Error> {
+    let acquisition_start = e57_point_cloud
+        .acquisition_start
+        .as_ref()
+        .map(|d| d.gps_time)
+        .unwrap_or_default();
+    let base_seconds = acquisition_start.floor();
+    DateTime::from_timestamp(
+        base_seconds as i64 + GPS_EPOCH_REFERENCE_TIMESTAMP,
+        ((acquisition_start - base_seconds) * NANOS_PER_SECOND).round() as u32,
+    )
+    .ok_or(NotSupported("acquisition start is out of range"))
+}
+
 // see also: http://www.libe57.org/bestCoordinates.html
 fn parse_transform_tree(
     transform: &Option<e57::Transform>,
     reference_frame_id: &FrameId,
     sensor_frame_id: &FrameId,
 ) -> TransformTree {
-    let Some(transform) = transform else {
-        return TransformTree::default();
+    // a missing pose is equivalent to the identity
+    let (translation, rotation) = match transform {
+        Some(transform) => (
+            convert_translation(&transform.translation),
+            convert_rotation(&transform.rotation),
+        ),
+        None => (Vector3::zeros(), UnitQuaternion::identity()),
     };
 
-    let translation: Vector3<f64> = convert_translation(&transform.translation);
-    let rotation = convert_rotation(&transform.rotation);
-
     let static_transform = StaticTransform::new(
         reference_frame_id.clone(),
         sensor_frame_id.clone(),
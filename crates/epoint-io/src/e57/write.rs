@@ -0,0 +1,75 @@
+use crate::Error;
+use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::e57::FILE_EXTENSION_E57_FORMAT;
+use crate::e57::write_impl::write_e57_format;
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+/// `E57Writer` exports a point cloud to a E57 file.
+///
+/// A separate data3D section is written for each distinct frame id of the point cloud, using the
+/// frame id as name. The pose of each section is derived from the static transform between the
+/// reference frame (global by default) and the section's frame. Sections with a frame that is only
+/// reachable via time-dependent transforms are resolved to the reference frame instead.
+///
+/// Cartesian coordinates, intensity, colors and timestamps are written, whereby timestamps are
+/// stored relative to the acquisition start of the respective section.
+#[derive(Debug, Clone)]
+pub struct E57Writer<W: Read + Write + Seek> {
+    writer: W,
+    frame_id: Option<FrameId>,
+    reference_frame_id: FrameId,
+}
+
+impl<W: Read + Write + Seek> E57Writer<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            frame_id: None,
+            reference_frame_id: FrameId::global(),
+        }
+    }
+
+    pub fn with_frame_id(mut self, frame_id: FrameId) -> Self {
+        self.frame_id = Some(frame_id);
+        self
+    }
+
+    pub fn with_reference_frame_id(mut self, reference_frame_id: FrameId) -> Self {
+        self.reference_frame_id = reference_frame_id;
+        self
+    }
+
+    pub fn finish(self, mut point_cloud: PointCloud) -> Result<(), Error> {
+        if let Some(frame_id) = self.frame_id {
+            point_cloud.resolve_to_frame(frame_id)?;
+        }
+
+        write_e57_format(self.writer, point_cloud, &self.reference_frame_id)?;
+
+        Ok(())
+    }
+}
+
+impl E57Writer<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+        if extension != FILE_EXTENSION_E57_FORMAT {
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        // the E57 writer needs to read back already written pages for the checksums
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self::new(file))
+    }
+}
@@ -0,0 +1,290 @@
+use crate::e57::error::Error;
+use crate::las::GPS_EPOCH_REFERENCE_TIMESTAMP;
+use chrono::{DateTime, Timelike, Utc};
+use e57::{Record, RecordDataType, RecordName, RecordValue};
+use ecoord::{FrameId, TransformId};
+use epoint_core::{PointCloud, PointDataColumnType};
+use nalgebra::Isometry3;
+use polars::prelude::ChunkAgg;
+use std::io::{Read, Seek, Write};
+
+/// A point cloud to be written as separate data3D section.
+#[derive(Debug, Clone)]
+struct E57Scan {
+    name: Option<String>,
+    pose: Option<Isometry3<f64>>,
+    point_cloud: PointCloud,
+}
+
+pub fn write_e57_format<W: Read + Write + Seek>(
+    writer: W,
+    point_cloud: PointCloud,
+    reference_frame_id: &FrameId,
+) -> Result<(), Error> {
+    let scans = split_into_scans(point_cloud, reference_frame_id)?;
+
+    let mut e57_writer = e57::E57Writer::new(writer, &generate_guid())?;
+    for current_scan in scans {
+        write_scan(&mut e57_writer, current_scan)?;
+    }
+    e57_writer.finalize()?;
+
+    Ok(())
+}
+
+fn split_into_scans(
+    point_cloud: PointCloud,
+    reference_frame_id: &FrameId,
+) -> Result<Vec<E57Scan>, Error> {
+    let point_clouds: Vec<PointCloud> = if point_cloud.point_data.contains_frame_id_column() {
+        let mut frame_ids: Vec<FrameId> = point_cloud
+            .point_data
+            .get_distinct_frame_ids()?
+            .into_iter()
+            .collect();
+        frame_ids.sort_by_key(|f| f.to_string());
+
+        frame_ids
+            .iter()
+            .map(|f| {
+                let mut scan_point_cloud = point_cloud.filter_by_frame_id(f)?;
+                scan_point_cloud
+                    .point_data
+                    .data_frame
+                    .drop_in_place(PointDataColumnType::FrameId.as_str())?;
+                scan_point_cloud.set_info_frame_id(Some(f.clone()));
+                Ok(scan_point_cloud)
+            })
+            .collect::<Result<Vec<_>, Error>>()?
+    } else {
+        vec![point_cloud]
+    };
+
+    let mut scans: Vec<E57Scan> = Vec::with_capacity(point_clouds.len());
+    for mut current_point_cloud in point_clouds {
+        let Some(frame_id) = current_point_cloud.info_frame_id().cloned() else {
+            scans.push(E57Scan {
+                name: None,
+                pose: None,
+                point_cloud: current_point_cloud,
+            });
+            continue;
+        };
+
+        let pose = if &frame_id == reference_frame_id {
+            None
+        } else if let Ok(transform) =
+            current_point_cloud
+                .transform_tree
+                .get_static_transform(&TransformId::new(
+                    reference_frame_id.clone(),
+                    frame_id.clone(),
+                ))
+        {
+            Some(transform.isometry())
+        } else {
+            // time-dependent poses cannot be represented by a data3D section
+            if current_point_cloud
+                .transform_tree
+                .contains_frame(reference_frame_id)
+            {
+                current_point_cloud.resolve_to_frame(reference_frame_id.clone())?;
+            }
+            None
+        };
+
+        scans.push(E57Scan {
+            name: Some(frame_id.to_string()),
+            pose,
+            point_cloud: current_point_cloud,
+        });
+    }
+
+    Ok(scans)
+}
+
+fn write_scan<W: Read + Write + Seek>(
+    e57_writer: &mut e57::E57Writer<W>,
+    scan: E57Scan,
+) -> Result<(), Error> {
+    let point_data = &scan.point_cloud.point_data;
+
+    let mut prototype = vec![
+        Record::CARTESIAN_X_F64,
+        Record::CARTESIAN_Y_F64,
+        Record::CARTESIAN_Z_F64,
+    ];
+
+    let intensity_values = point_data.get_intensity_values().ok();
+    if let Some(intensity_values) = intensity_values {
+        // the limits enclose the stored values, which are read back without normalization
+        prototype.push(Record {
+            name: RecordName::Intensity,
+            data_type: RecordDataType::Single {
+                min: Some(intensity_values.min().unwrap_or(0.0)),
+                max: Some(intensity_values.max().unwrap_or(1.0)),
+            },
+        });
+    }
+
+    let colors = point_data.get_all_colors().ok();
+    if colors.is_some() {
+        for name in [
+            RecordName::ColorRed,
+            RecordName::ColorGreen,
+            RecordName::ColorBlue,
+        ] {
+            prototype.push(Record {
+                name,
+                data_type: RecordDataType::U16,
+            });
+        }
+    }
+
+    let spherical_points = point_data.get_all_spherical_points().ok();
+    if spherical_points.is_some() {
+        for name in [
+            RecordName::SphericalRange,
+            RecordName::SphericalAzimuth,
+            RecordName::SphericalElevation,
+        ] {
+            prototype.push(Record {
+                name,
+                data_type: RecordDataType::F64,
+            });
+        }
+    }
+
+    let grid_indices = point_data
+        .get_row_index_values()
+        .ok()
+        .zip(point_data.get_column_index_values().ok());
+    if let Some((row_index_values, column_index_values)) = grid_indices {
+        // at least a byte per value, since the e57 crate fails to read bit-packed streams shorter
+        // than a byte
+        prototype.push(Record {
+            name: RecordName::RowIndex,
+            data_type: RecordDataType::Integer {
+                min: 0,
+                max: row_index_values
+                    .max()
+                    .unwrap_or_default()
+                    .max(u8::MAX as u32) as i64,
+            },
+        });
+        prototype.push(Record {
+            name: RecordName::ColumnIndex,
+            data_type: RecordDataType::Integer {
+                min: 0,
+                max: column_index_values
+                    .max()
+                    .unwrap_or_default()
+                    .max(u8::MAX as u32) as i64,
+            },
+        });
+    }
+
+    let timestamps = point_data.get_all_timestamps().ok();
+    let acquisition_start = timestamps.as_ref().and_then(|t| t.iter().min().copied());
+    let acquisition_end = timestamps.as_ref().and_then(|t| t.iter().max().copied());
+    if timestamps.is_some() {
+        prototype.push(Record {
+            name: RecordName::TimeStamp,
+            data_type: RecordDataType::F64,
+        });
+    }
+
+    let mut point_cloud_writer = e57_writer.add_pointcloud(&generate_guid(), prototype)?;
+    point_cloud_writer.set_name(scan.name);
+    point_cloud_writer.set_transform(scan.pose.map(convert_pose));
+    point_cloud_writer.set_acquisition_start(acquisition_start.map(convert_date_time));
+    point_cloud_writer.set_acquisition_end(acquisition_end.map(convert_date_time));
+
+    for (i, current_point) in point_data.get_all_points().iter().enumerate() {
+        let mut values: Vec<RecordValue> = vec![
+            RecordValue::Double(current_point.x),
+            RecordValue::Double(current_point.y),
+            RecordValue::Double(current_point.z),
+        ];
+        if let Some(intensity_values) = intensity_values {
+            values.push(RecordValue::Single(
+                intensity_values.get(i).unwrap_or_default(),
+            ));
+        }
+        if let Some(colors) = &colors {
+            values.push(RecordValue::Integer(colors[i].red as i64));
+            values.push(RecordValue::Integer(colors[i].green as i64));
+            values.push(RecordValue::Integer(colors[i].blue as i64));
+        }
+        if let Some(spherical_points) = &spherical_points {
+            values.push(RecordValue::Double(spherical_points[i].r));
+            values.push(RecordValue::Double(spherical_points[i].phi));
+            values.push(RecordValue::Double(spherical_points[i].theta));
+        }
+        if let Some((row_index_values, column_index_values)) = grid_indices {
+            values.push(RecordValue::Integer(
+                row_index_values.get(i).unwrap_or_default() as i64,
+            ));
+            values.push(RecordValue::Integer(
+                column_index_values.get(i).unwrap_or_default() as i64,
+            ));
+        }
+        if let (Some(timestamps), Some(acquisition_start)) = (&timestamps, &acquisition_start) {
+            values.push(RecordValue::Double(seconds_between(
+                acquisition_start,
+                &timestamps[i],
+            )));
+        }
+
+        point_cloud_writer.add_point(values)?;
+    }
+    point_cloud_writer.finalize()?;
+
+    Ok(())
+}
+
+fn convert_pose(pose: Isometry3<f64>) -> e57::Transform {
+    e57::Transform {
+        rotation: e57::Quaternion {
+            w: pose.rotation.w,
+            x: pose.rotation.i,
+            y: pose.rotation.j,
+            z: pose.rotation.k,
+        },
+        translation: e57::Translation {
+            x: pose.translation.x,
+            y: pose.translation.y,
+            z: pose.translation.z,
+        },
+    }
+}
+
+/// Converts a timestamp to GPS time, which E57 uses for date and time values.
+fn convert_date_time(value: DateTime<Utc>) -> e57::DateTime {
+    e57::DateTime {
+        gps_time: (value.timestamp() - GPS_EPOCH_REFERENCE_TIMESTAMP) as f64
+            + value.nanosecond() as f64 * 1.0e-9,
+        atomic_reference: false,
+    }
+}
+
+fn seconds_between(start: &DateTime<Utc>, end: &DateTime<Utc>) -> f64 {
+    (end.timestamp() - start.timestamp()) as f64
+        + (end.nanosecond() as f64 - start.nanosecond() as f64) * 1.0e-9
+}
+
+/// Generates a random GUID (UUID version 4) in the notation commonly used in E57 files.
+fn generate_guid() -> String {
+    let value: u128 = rand::random();
+    let value = (value & !(0xf << 76)) | (0x4 << 76);
+    let value = (value & !(0x3 << 62)) | (0x2 << 62);
+
+    format!(
+        "{{{:08x}-{:04x}-{:04x}-{:04x}-{:012x}}}",
+        value >> 96,
+        (value >> 80) & 0xffff,
+        (value >> 64) & 0xffff,
+        (value >> 48) & 0xffff,
+        value & 0xffff_ffff_ffff
+    )
+}
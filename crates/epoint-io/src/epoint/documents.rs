@@ -1,27 +1,211 @@
-use ecoord::FrameId;
-use epoint_core::PointCloudInfo;
+use crate::epoint::{EPOINT_FORMAT_VERSION, EpointColumnDescription};
+use chrono::{DateTime, Utc};
+use ecoord::{AxisAlignedBoundingBox, FrameId};
+use epoint_core::{PointCloudInfo, PointDataColumnType};
+use nalgebra::Point3;
+use polars::prelude::Schema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EpointInfoDocument {
+    pub format_version: u32,
+    /// Name and version of the software that wrote the archive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub writer: Option<String>,
     pub frame_id: Option<String>,
+    /// Schema of the point data, which is empty for archives migrated from version 1.
+    #[serde(default)]
+    pub columns: Vec<EpointColumnDocument>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    /// Summary of the point data, which is missing in archives migrated from version 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<EpointSummaryDocument>,
+}
+
+impl Default for EpointInfoDocument {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EpointInfoDocument {
     pub fn new() -> Self {
-        Self { frame_id: None }
+        Self {
+            format_version: EPOINT_FORMAT_VERSION,
+            writer: Some(format!(
+                "{} {}",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION")
+            )),
+            frame_id: None,
+            columns: Vec::new(),
+            metadata: BTreeMap::new(),
+            summary: None,
+        }
+    }
+
+    pub fn with_format_version(mut self, format_version: u32) -> Self {
+        self.format_version = format_version;
+        self
     }
 
     pub fn with_frame_id(mut self, frame_id: Option<FrameId>) -> Self {
         self.frame_id = frame_id.map(|f| f.into());
         self
     }
+
+    pub fn with_columns(mut self, columns: Vec<EpointColumnDocument>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: BTreeMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn with_summary(mut self, summary: Option<EpointSummaryDocument>) -> Self {
+        self.summary = summary;
+        self
+    }
 }
 
 impl From<EpointInfoDocument> for PointCloudInfo {
     fn from(item: EpointInfoDocument) -> Self {
-        PointCloudInfo {
-            frame_id: item.frame_id.map(|f| f.into()),
+        PointCloudInfo::new(item.frame_id.map(|f| f.into())).with_metadata(item.metadata)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpointSummaryDocument {
+    pub point_count: usize,
+    pub bounding_box: EpointBoundingBoxDocument,
+    pub time_range: Option<EpointTimeRangeDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpointColumnDocument {
+    pub name: String,
+    pub data_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Scale and offset of a column, which is stored as 32 bit integers instead of its data type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<EpointQuantizationDocument>,
+}
+
+impl EpointColumnDocument {
+    /// Creates the column documents of a schema, where the given descriptions take precedence
+    /// over the ones of known point data columns.
+    pub fn from_schema(
+        schema: &Schema,
+        column_descriptions: &HashMap<String, EpointColumnDescription>,
+    ) -> Vec<Self> {
+        schema
+            .iter()
+            .map(|(name, data_type)| {
+                let column_type = PointDataColumnType::from_str(name.as_str()).ok();
+                let column_description = column_descriptions.get(name.as_str());
+                Self {
+                    name: name.to_string(),
+                    data_type: data_type.to_string(),
+                    unit: column_description
+                        .and_then(|d| d.unit.clone())
+                        .or(column_type.and_then(|t| t.unit()).map(|u| u.to_string())),
+                    description: column_description
+                        .and_then(|d| d.description.clone())
+                        .or(column_type.map(|t| t.description().to_string())),
+                    quantization: None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EpointQuantizationDocument {
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl From<EpointColumnDocument> for EpointColumnDescription {
+    fn from(item: EpointColumnDocument) -> Self {
+        Self::new(item.unit, item.description)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpointBoundingBoxDocument {
+    pub lower_bound: [f64; 3],
+    pub upper_bound: [f64; 3],
+}
+
+impl From<AxisAlignedBoundingBox> for EpointBoundingBoxDocument {
+    fn from(item: AxisAlignedBoundingBox) -> Self {
+        Self {
+            lower_bound: item.lower_bound().into(),
+            upper_bound: item.upper_bound().into(),
         }
     }
 }
+
+impl From<EpointBoundingBoxDocument> for (Point3<f64>, Point3<f64>) {
+    fn from(item: EpointBoundingBoxDocument) -> Self {
+        (item.lower_bound.into(), item.upper_bound.into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpointTimeRangeDocument {
+    pub start: EpointTimestampDocument,
+    pub end: EpointTimestampDocument,
+}
+
+/// Timestamp split into seconds and nanoseconds like the timestamp columns of the point data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpointTimestampDocument {
+    pub sec: i64,
+    pub nanosec: u32,
+}
+
+impl From<DateTime<Utc>> for EpointTimestampDocument {
+    fn from(item: DateTime<Utc>) -> Self {
+        Self {
+            sec: item.timestamp(),
+            nanosec: item.timestamp_subsec_nanos(),
+        }
+    }
+}
+
+impl EpointTimestampDocument {
+    pub fn to_date_time(self) -> Option<DateTime<Utc>> {
+        DateTime::<Utc>::from_timestamp(self.sec, self.nanosec)
+    }
+}
+
+/// Index of the point data parts, which precedes the parts in the archive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpointPartIndexDocument {
+    pub parts: Vec<EpointPartDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpointPartDocument {
+    pub file_name: String,
+    pub point_count: usize,
+    pub bounding_box: EpointBoundingBoxDocument,
+}
+
+impl EpointBoundingBoxDocument {
+    /// Returns true, if the boxes overlap or touch.
+    pub fn intersects(&self, other: &AxisAlignedBoundingBox) -> bool {
+        let (lower_bound, upper_bound) = (other.lower_bound(), other.upper_bound());
+        (0..3)
+            .all(|i| self.lower_bound[i] <= upper_bound[i] && lower_bound[i] <= self.upper_bound[i])
+    }
+}
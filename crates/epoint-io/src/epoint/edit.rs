@@ -0,0 +1,114 @@
+use crate::Error::{FormatNotSupported, InvalidFileExtension, NoFileName};
+use crate::epoint::edit_impl::{edit_epoint_archive, edit_epoint_directory};
+use crate::epoint::{
+    FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT, FILE_EXTENSION_EPOINT_FORMAT,
+    FILE_EXTENSION_EPOINT_TAR_FORMAT,
+};
+use crate::error::Error;
+use ecoord::TransformTree;
+use epoint_core::PointCloudInfo;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// `EpointEditor` replaces the transform tree and the info of an epoint archive without decoding
+/// the point data.
+///
+/// Only the ecoord and info documents are rewritten, while the point data members are copied byte
+/// for byte. Documents without a replacement are copied unchanged as well.
+///
+/// An unarchived epoint directory opened via [`EpointEditor::from_path`] is edited in place with
+/// [`EpointEditor::finish_in_place`], which leaves the point data files untouched.
+#[derive(Debug, Clone)]
+pub struct EpointEditor<R: Read> {
+    source: EpointEditorSource<R>,
+    transform_tree: Option<TransformTree>,
+    info: Option<PointCloudInfo>,
+}
+
+#[derive(Debug, Clone)]
+enum EpointEditorSource<R: Read> {
+    Archive(R),
+    Directory(PathBuf),
+}
+
+impl<R: Read> EpointEditor<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            source: EpointEditorSource::Archive(reader),
+            transform_tree: None,
+            info: None,
+        }
+    }
+
+    pub fn with_transform_tree(mut self, transform_tree: Option<TransformTree>) -> Self {
+        self.transform_tree = transform_tree;
+        self
+    }
+
+    /// Replaces the frame id and the metadata, while the schema and the summary of the point data
+    /// are kept.
+    pub fn with_info(mut self, info: Option<PointCloudInfo>) -> Self {
+        self.info = info;
+        self
+    }
+
+    /// Writes the edited archive, which must not be the archive being read.
+    pub fn finish(self, writer: impl Write) -> Result<(), Error> {
+        let EpointEditorSource::Archive(reader) = self.source else {
+            return Err(FormatNotSupported(
+                "writing an edited epoint directory".to_string(),
+            ));
+        };
+        edit_epoint_archive(
+            reader,
+            writer,
+            self.transform_tree.as_ref(),
+            self.info.as_ref(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Rewrites the ecoord and info documents of the epoint directory being read.
+    pub fn finish_in_place(self) -> Result<(), Error> {
+        let EpointEditorSource::Directory(directory_path) = self.source else {
+            return Err(FormatNotSupported(
+                "editing an epoint archive in place".to_string(),
+            ));
+        };
+        edit_epoint_directory(
+            &directory_path,
+            self.transform_tree.as_ref(),
+            self.info.as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
+
+impl EpointEditor<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file_name_str = path
+            .as_ref()
+            .file_name()
+            .ok_or(NoFileName())?
+            .to_string_lossy()
+            .to_lowercase();
+        if file_name_str.ends_with(FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT) {
+            return Ok(Self {
+                source: EpointEditorSource::Directory(path.as_ref().to_path_buf()),
+                transform_tree: None,
+                info: None,
+            });
+        }
+        if !file_name_str.ends_with(FILE_EXTENSION_EPOINT_TAR_FORMAT)
+            && !file_name_str.ends_with(FILE_EXTENSION_EPOINT_FORMAT)
+        {
+            return Err(InvalidFileExtension(file_name_str.to_string()));
+        }
+
+        let file = File::open(path)?;
+        Ok(Self::new(file))
+    }
+}
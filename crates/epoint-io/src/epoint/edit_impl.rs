@@ -0,0 +1,182 @@
+use crate::Error::FileNotFound;
+use crate::epoint::read_impl::migrate_info_document;
+use crate::epoint::write::DEFAULT_COMPRESSION_LEVEL;
+use crate::epoint::{
+    FILE_NAME_ECOORD_COMPRESSED, FILE_NAME_ECOORD_UNCOMPRESSED, FILE_NAME_INFO_COMPRESSED,
+    FILE_NAME_INFO_UNCOMPRESSED,
+};
+use crate::error::Error;
+use ecoord::TransformTree;
+use epoint_core::{PointCloudInfo, PointDataColumnType};
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Write};
+use std::path::Path;
+use tar::{Archive, Builder};
+
+/// Copies an epoint archive member by member, while the ecoord and info documents are replaced.
+///
+/// The replaced documents keep the compression and the header of the original members. All other
+/// members, including the point data, are copied byte for byte in their original order.
+pub fn edit_epoint_archive<R: Read, W: Write>(
+    reader: R,
+    writer: W,
+    transform_tree: Option<&TransformTree>,
+    info: Option<&PointCloudInfo>,
+) -> Result<(), Error> {
+    let mut archive = Archive::new(reader);
+    let mut archive_builder = Builder::new(writer);
+    let mut contains_info_document = false;
+    let mut contains_ecoord_document = false;
+
+    for file in archive.entries()? {
+        let f = file?;
+        let file_name = f.path()?.to_str().unwrap().to_string();
+        let mut header = f.header().clone();
+
+        let document_buffer = match file_name.as_str() {
+            FILE_NAME_INFO_UNCOMPRESSED | FILE_NAME_INFO_COMPRESSED => {
+                contains_info_document = true;
+                let Some(info) = info else {
+                    archive_builder.append_data(&mut header, &file_name, f)?;
+                    continue;
+                };
+                let compressed = file_name == FILE_NAME_INFO_COMPRESSED;
+                edit_info_document(f, info, compressed)?
+            }
+            FILE_NAME_ECOORD_UNCOMPRESSED | FILE_NAME_ECOORD_COMPRESSED => {
+                contains_ecoord_document = true;
+                let Some(transform_tree) = transform_tree else {
+                    archive_builder.append_data(&mut header, &file_name, f)?;
+                    continue;
+                };
+                create_ecoord_document(transform_tree, file_name == FILE_NAME_ECOORD_COMPRESSED)?
+            }
+            _ => {
+                archive_builder.append_data(&mut header, &file_name, f)?;
+                continue;
+            }
+        };
+
+        header.set_size(document_buffer.len() as u64);
+        header.set_cksum();
+        archive_builder.append_data(&mut header, &file_name, Cursor::new(document_buffer))?;
+    }
+
+    if !contains_info_document {
+        return Err(FileNotFound("info".to_string()));
+    }
+    if !contains_ecoord_document {
+        return Err(FileNotFound("ecoord".to_string()));
+    }
+    archive_builder.into_inner()?.flush()?;
+
+    Ok(())
+}
+
+/// Rewrites the ecoord and info documents of an epoint directory in place.
+///
+/// The replaced documents keep their compression, while the point data files are not touched.
+pub fn edit_epoint_directory(
+    directory_path: &Path,
+    transform_tree: Option<&TransformTree>,
+    info: Option<&PointCloudInfo>,
+) -> Result<(), Error> {
+    if !directory_path.is_dir() {
+        return Err(FileNotFound(directory_path.to_string_lossy().to_string()));
+    }
+    let find_document = |file_names: [&'static str; 2], name: &str| {
+        file_names
+            .into_iter()
+            .find(|f| directory_path.join(f).is_file())
+            .ok_or(FileNotFound(name.to_string()))
+    };
+    let info_file_name = find_document(
+        [FILE_NAME_INFO_UNCOMPRESSED, FILE_NAME_INFO_COMPRESSED],
+        "info",
+    )?;
+    let ecoord_file_name = find_document(
+        [FILE_NAME_ECOORD_UNCOMPRESSED, FILE_NAME_ECOORD_COMPRESSED],
+        "ecoord",
+    )?;
+
+    if let Some(info) = info {
+        let file_path = directory_path.join(info_file_name);
+        let document_buffer = edit_info_document(
+            BufReader::new(File::open(&file_path)?),
+            info,
+            info_file_name == FILE_NAME_INFO_COMPRESSED,
+        )?;
+        fs::write(file_path, document_buffer)?;
+    }
+    if let Some(transform_tree) = transform_tree {
+        let document_buffer = create_ecoord_document(
+            transform_tree,
+            ecoord_file_name == FILE_NAME_ECOORD_COMPRESSED,
+        )?;
+        fs::write(directory_path.join(ecoord_file_name), document_buffer)?;
+    }
+
+    Ok(())
+}
+
+fn create_ecoord_document(
+    transform_tree: &TransformTree,
+    compressed: bool,
+) -> Result<Vec<u8>, Error> {
+    let compression = if compressed {
+        ecoord::io::Compression::Zstd(DEFAULT_COMPRESSION_LEVEL)
+    } else {
+        ecoord::io::Compression::None
+    };
+    let mut document_buffer: Vec<u8> = Vec::new();
+    ecoord::io::EcoordWriter::new(&mut document_buffer)
+        .with_compression(compression)
+        .with_pretty(!compressed)
+        .finish(transform_tree)?;
+    Ok(document_buffer)
+}
+
+/// Replaces the frame id and the metadata of an info document, while the schema and the summary of
+/// the unchanged point data are kept.
+fn edit_info_document<R: Read>(
+    reader: R,
+    info: &PointCloudInfo,
+    compressed: bool,
+) -> Result<Vec<u8>, Error> {
+    let (format_version, info_document) = if compressed {
+        let mut decompressed_buffer: Vec<u8> = Vec::new();
+        zstd::stream::copy_decode(reader, &mut decompressed_buffer)?;
+        migrate_info_document(serde_json::from_slice(&decompressed_buffer)?)?
+    } else {
+        migrate_info_document(serde_json::from_reader(reader)?)?
+    };
+    if info.frame_id.is_some()
+        && info_document
+            .columns
+            .iter()
+            .any(|c| c.name == PointDataColumnType::FrameId.as_str())
+    {
+        return Err(epoint_core::Error::MultipleFrameIdDefinitions.into());
+    }
+    // the migrated document has the layout of version 2, while the point data is unchanged
+    let info_document = info_document
+        .with_format_version(format_version.max(2))
+        .with_frame_id(info.frame_id.clone())
+        .with_metadata(info.metadata.clone());
+
+    let mut document_buffer: Vec<u8> = Vec::new();
+    if compressed {
+        serde_json::to_writer(&mut document_buffer, &info_document)?;
+        let mut document_compressed_buffer: Vec<u8> = Vec::new();
+        zstd::stream::copy_encode(
+            Cursor::new(document_buffer),
+            &mut document_compressed_buffer,
+            DEFAULT_COMPRESSION_LEVEL,
+        )?;
+        Ok(document_compressed_buffer)
+    } else {
+        serde_json::to_writer_pretty(&mut document_buffer, &info_document)?;
+        Ok(document_buffer)
+    }
+}
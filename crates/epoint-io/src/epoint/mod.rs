@@ -1,3 +1,7 @@
+use nalgebra::{Point3, Vector3};
+
+pub mod edit;
+mod edit_impl;
 pub mod read;
 
 // TODO: not make public
@@ -7,14 +11,134 @@ pub mod write;
 mod documents;
 pub mod write_impl;
 
+/// Version of the archive layout and the info document, which is increased on each change of the
+/// format. Archives without a version were written with version 1.
+///
+/// Archives are written with the lowest version supporting their content: version 2 added the
+/// schema and metadata to the info document, version 3 partitioned point data and version 4
+/// quantized columns.
+pub const EPOINT_FORMAT_VERSION: u32 = 4;
+
 pub const FILE_EXTENSION_EPOINT_FORMAT: &str = "epoint";
 pub const FILE_EXTENSION_EPOINT_TAR_FORMAT: &str = "epoint.tar";
+/// Extension of an unarchived directory holding the members of an epoint archive.
+pub const FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT: &str = "epoint.d";
 
 pub const FILE_NAME_POINT_DATA_COMPRESSED: &str = "point_data.parquet";
 pub const FILE_NAME_POINT_DATA_UNCOMPRESSED: &str = "point_data.xyz";
+pub const FILE_NAME_PART_INDEX_COMPRESSED: &str = "point_data_index.json.zst";
+pub const FILE_NAME_PART_INDEX_UNCOMPRESSED: &str = "point_data_index.json";
+pub const DIRECTORY_NAME_POINT_DATA_PARTS: &str = "point_data";
+pub const FILE_EXTENSION_POINT_DATA_COMPRESSED: &str = "parquet";
+pub const FILE_EXTENSION_POINT_DATA_UNCOMPRESSED: &str = "xyz";
 pub const FILE_NAME_INFO_COMPRESSED: &str = "info.json.zst";
 pub const FILE_NAME_INFO_UNCOMPRESSED: &str = "info.json";
 pub const FILE_NAME_ECOORD_COMPRESSED: &str = "ecoord.json.zst";
 pub const FILE_NAME_ECOORD_UNCOMPRESSED: &str = "ecoord.json";
 
 pub const EPOINT_SEPARATOR: u8 = b';';
+
+/// Unit and description of a point data column, which are stored in the schema of the info
+/// document.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EpointColumnDescription {
+    pub unit: Option<String>,
+    pub description: Option<String>,
+}
+
+impl EpointColumnDescription {
+    pub fn new(unit: Option<String>, description: Option<String>) -> Self {
+        Self { unit, description }
+    }
+}
+
+/// Spatial partitioning of the point data into several parts of an archive, so that readers only
+/// decode the parts intersecting their bounds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EpointPartitioning {
+    /// Leaf octants of an octree, each with at most the maximum number of points, which must be
+    /// positive.
+    Octree { max_points_per_part: usize },
+    /// Tiles of a horizontal grid with the edge length of the tiles, which must be finite and
+    /// positive.
+    Grid { tile_size: f64 },
+}
+
+/// Compression codec of the Parquet point data with an optional level, which defaults to the one
+/// of the codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EpointParquetCompression {
+    Uncompressed,
+    Snappy,
+    Lz4,
+    Gzip(Option<u8>),
+    Brotli(Option<u32>),
+    Zstd(Option<i32>),
+}
+
+impl Default for EpointParquetCompression {
+    fn default() -> Self {
+        Self::Zstd(None)
+    }
+}
+
+/// Encoding of the Parquet point data, which is ignored for uncompressed archives.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpointParquetOptions {
+    pub compression: EpointParquetCompression,
+    /// Maximum number of points per row group, which are the units skipped by their statistics.
+    pub row_group_size: Option<usize>,
+    /// Dictionary encoding of integer and string columns, if their values repeat often enough.
+    pub dictionary_encoding: bool,
+    /// Sorts the points by the Morton order of their coordinates, so that row groups cover compact
+    /// regions and compress better.
+    pub spatial_sorting: bool,
+    /// Stores the coordinates as scaled 32 bit integers instead of 64 bit floats.
+    pub quantization: Option<EpointQuantization>,
+}
+
+impl Default for EpointParquetOptions {
+    fn default() -> Self {
+        Self {
+            compression: EpointParquetCompression::default(),
+            row_group_size: None,
+            dictionary_encoding: true,
+            spatial_sorting: false,
+            quantization: None,
+        }
+    }
+}
+
+/// Storage of the coordinates as 32 bit integers with a scale and offset, like in LAS.
+///
+/// The scale and offset are recorded in the info document, so that the coordinates are restored
+/// on reading.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpointQuantization {
+    /// Scale factors of the x, y and z coordinates, which are the precision of the stored values.
+    pub scale: Vector3<f64>,
+    /// Offset of the coordinates, which is the center of the point cloud, if not given.
+    pub offset: Option<Point3<f64>>,
+    /// Quantizes the sensor translation columns with the same scale and offset as well.
+    pub sensor_translation: bool,
+}
+
+impl EpointQuantization {
+    pub fn new(scale: Vector3<f64>) -> Self {
+        Self {
+            scale,
+            offset: None,
+            sensor_translation: false,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: Option<Point3<f64>>) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_sensor_translation(mut self, sensor_translation: bool) -> Self {
+        self.sensor_translation = sensor_translation;
+        self
+    }
+}
@@ -1,101 +1,182 @@
-use crate::Error::{FileNotFound, InvalidFileExtension, NoFileName};
-use crate::epoint::documents::EpointInfoDocument;
-use crate::epoint::read_impl::cast_data_frame;
+use crate::Error::{FileNotFound, FormatNotSupported, InvalidFileExtension, NoFileName};
+use crate::epoint::read_impl::{
+    EpointArchiveEntries, EpointReadFilter, cast_lazy_frame, create_point_data_schema,
+    dequantize_lazy_frame, read_epoint_archive, read_epoint_directory, read_point_data_columns,
+    summarize_point_data,
+};
 use crate::epoint::{
-    EPOINT_SEPARATOR, FILE_EXTENSION_EPOINT_FORMAT, FILE_EXTENSION_EPOINT_TAR_FORMAT,
-    FILE_NAME_ECOORD_COMPRESSED, FILE_NAME_ECOORD_UNCOMPRESSED, FILE_NAME_INFO_COMPRESSED,
-    FILE_NAME_INFO_UNCOMPRESSED, FILE_NAME_POINT_DATA_COMPRESSED,
-    FILE_NAME_POINT_DATA_UNCOMPRESSED,
+    EpointColumnDescription, FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT, FILE_EXTENSION_EPOINT_FORMAT,
+    FILE_EXTENSION_EPOINT_TAR_FORMAT,
 };
 use crate::error::Error;
-use ecoord::TransformTree;
-use epoint_core::PointCloud;
+use chrono::{DateTime, Utc};
+use ecoord::{AxisAlignedBoundingBox, TransformTree};
 use epoint_core::PointCloudInfo;
-use polars::prelude::*;
+use epoint_core::{LazyPointCloud, PointCloud};
+use polars::prelude::Schema;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Cursor, Read};
-use std::path::Path;
-use tar::Archive;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 /// `EpointReader` sets up a reader for the custom reader data structure.
 ///
+/// Bounds, a time range and a column projection are pushed down into the scan of the point data,
+/// so that row groups of compressed point data are skipped by their statistics. Parts of
+/// partitioned archives, which do not intersect the bounds, are not decoded at all.
+///
+/// Besides a tar archive, the reader accepts an unarchived epoint directory holding the same
+/// members via [`EpointReader::from_path`].
 #[derive(Debug, Clone)]
 pub struct EpointReader<R: Read> {
-    reader: R,
+    source: EpointSource<R>,
+    filter: EpointReadFilter,
+}
+
+#[derive(Debug, Clone)]
+enum EpointSource<R: Read> {
+    Archive(R),
+    Directory(PathBuf),
+}
+
+impl<R: Read> EpointSource<R> {
+    fn read_entries(
+        self,
+        skip_summarized_point_data: bool,
+        bounds: Option<&AxisAlignedBoundingBox>,
+    ) -> Result<EpointArchiveEntries, Error> {
+        match self {
+            EpointSource::Archive(reader) => {
+                read_epoint_archive(reader, skip_summarized_point_data, bounds)
+            }
+            EpointSource::Directory(directory_path) => {
+                read_epoint_directory(&directory_path, skip_summarized_point_data, bounds)
+            }
+        }
+    }
 }
 
 impl<R: Read> EpointReader<R> {
     pub fn new(reader: R) -> Self {
-        Self { reader }
+        Self {
+            source: EpointSource::Archive(reader),
+            filter: EpointReadFilter::default(),
+        }
+    }
+
+    /// Only reads points within the bounds, including points on the boundary.
+    pub fn with_bounds(mut self, bounds: Option<AxisAlignedBoundingBox>) -> Self {
+        self.filter.bounds = bounds;
+        self
+    }
+
+    /// Only reads points with timestamps within the closed range of start and end time.
+    pub fn with_time_range(mut self, time_range: Option<(DateTime<Utc>, DateTime<Utc>)>) -> Self {
+        self.filter.time_range = time_range;
+        self
+    }
+
+    /// Only reads the columns with the names, while the `x`, `y`, `z` and `frame_id` columns are
+    /// always read.
+    pub fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.filter.columns = columns;
+        self
     }
 
     pub fn finish(self) -> Result<PointCloud, Error> {
-        let mut archive = Archive::new(self.reader);
-
-        let mut info_document: Option<EpointInfoDocument> = None;
-        let mut point_data_frame: Option<DataFrame> = None;
-        let mut transform_tree: Option<TransformTree> = None;
-
-        for file in archive.entries()? {
-            let mut f = file?;
-
-            match f.path()?.to_str().unwrap() {
-                FILE_NAME_INFO_UNCOMPRESSED => {
-                    info_document = serde_json::from_reader(f)?;
-                }
-                FILE_NAME_INFO_COMPRESSED => {
-                    let mut decompressed_buffer: Vec<u8> = Vec::new();
-                    zstd::stream::copy_decode(f, &mut decompressed_buffer)?;
-                    info_document = serde_json::from_reader(Cursor::new(decompressed_buffer))?;
-                }
-                FILE_NAME_POINT_DATA_UNCOMPRESSED => {
-                    let mut buffer: Vec<u8> = Vec::new();
-                    f.read_to_end(&mut buffer)?;
-                    let reader = Cursor::new(&buffer);
-
-                    let csv_parse_options =
-                        CsvParseOptions::default().with_separator(EPOINT_SEPARATOR);
-                    let data_frame: DataFrame = CsvReadOptions::default()
-                        .with_parse_options(csv_parse_options)
-                        .into_reader_with_file_handle(reader)
-                        .finish()?;
-                    let casted_data_frame = cast_data_frame(data_frame)?;
-
-                    point_data_frame = Some(casted_data_frame);
-                }
-                FILE_NAME_POINT_DATA_COMPRESSED => {
-                    let mut buffer: Vec<u8> = Vec::new();
-                    f.read_to_end(&mut buffer)?;
-                    let reader = Cursor::new(&buffer);
-
-                    let data_frame: DataFrame = ParquetReader::new(reader).finish()?;
-                    let casted_data_frame = cast_data_frame(data_frame)?;
-
-                    point_data_frame = Some(casted_data_frame);
-                }
-                FILE_NAME_ECOORD_UNCOMPRESSED => {
-                    transform_tree = Some(ecoord::io::EcoordReader::new(f).finish()?);
-                }
-                FILE_NAME_ECOORD_COMPRESSED => {
-                    transform_tree = Some(
-                        ecoord::io::EcoordReader::new(f)
-                            .with_compression(ecoord::io::Compression::default_zstd())
-                            .finish()?,
-                    );
-                }
-                _ => {}
-            }
+        let lazy_point_cloud = self.finish_lazy()?;
+        let point_data_frame = lazy_point_cloud.point_data.collect()?;
+        if point_data_frame.height() == 0 {
+            return Err(epoint_core::Error::NoRemainingPoints.into());
         }
 
-        let info: PointCloudInfo = info_document
-            .ok_or(FileNotFound("info".to_string()))?
-            .into();
-        let point_data_frame = point_data_frame.ok_or(FileNotFound("point_data".to_string()))?;
-        let transform_tree = transform_tree.ok_or(FileNotFound("ecoord".to_string()))?;
-
-        let point_cloud = PointCloud::from_data_frame(point_data_frame, info, transform_tree)?;
+        let point_cloud = PointCloud::from_data_frame(
+            point_data_frame,
+            lazy_point_cloud.info,
+            lazy_point_cloud.transform_tree,
+        )?;
         Ok(point_cloud)
     }
+
+    /// Reads the info and the transform tree, while the point data is only scanned lazily with the
+    /// filter applied.
+    pub fn finish_lazy(self) -> Result<LazyPointCloud, Error> {
+        let archive_entries = self
+            .source
+            .read_entries(false, self.filter.bounds.as_ref())?;
+        let point_data = archive_entries
+            .point_data
+            .ok_or(FileNotFound("point_data".to_string()))?;
+
+        let point_data = cast_lazy_frame(
+            self.filter
+                .apply(point_data, &archive_entries.info_document.columns)?,
+        )?;
+        Ok(LazyPointCloud::new(
+            point_data,
+            archive_entries.info_document.into(),
+            archive_entries.transform_tree,
+        ))
+    }
+
+    /// Reads the info, the transform tree, the schema and the summary of the point data without
+    /// decoding the point data.
+    ///
+    /// Archives written without a schema or summary in the info document are summarized by
+    /// scanning the coordinate and timestamp columns. The filter is not applied.
+    pub fn read_info(self) -> Result<EpointReadInfo, Error> {
+        let archive_entries = self.source.read_entries(true, None)?;
+        let info_document = archive_entries.info_document;
+        let point_data = || {
+            archive_entries
+                .point_data
+                .clone()
+                .map(|p| dequantize_lazy_frame(p, &info_document.columns))
+                .ok_or(FileNotFound("point_data".to_string()))
+        };
+        let summary_document = match info_document.summary.clone() {
+            Some(summary_document) => summary_document,
+            None => summarize_point_data(point_data()?)?,
+        };
+        let columns = if info_document.columns.is_empty() {
+            read_point_data_columns(point_data()?)?
+        } else {
+            info_document.columns.clone()
+        };
+
+        let schema = create_point_data_schema(&columns, point_data().ok())?;
+        let column_descriptions: HashMap<String, EpointColumnDescription> = columns
+            .into_iter()
+            .map(|c| (c.name.clone(), c.into()))
+            .collect();
+        let (lower_bound, upper_bound) = summary_document.bounding_box.into();
+        let time_range = summary_document
+            .time_range
+            .map(|t| {
+                Ok::<_, Error>((
+                    t.start
+                        .to_date_time()
+                        .ok_or(FormatNotSupported("time range".to_string()))?,
+                    t.end
+                        .to_date_time()
+                        .ok_or(FormatNotSupported("time range".to_string()))?,
+                ))
+            })
+            .transpose()?;
+
+        Ok(EpointReadInfo {
+            format_version: archive_entries.format_version,
+            writer: info_document.writer.clone(),
+            info: info_document.into(),
+            transform_tree: archive_entries.transform_tree,
+            point_count: summary_document.point_count,
+            schema,
+            column_descriptions,
+            bounding_box: AxisAlignedBoundingBox::new(lower_bound, upper_bound)
+                .map_err(ecoord::Error::from)?,
+            time_range,
+        })
+    }
 }
 
 impl EpointReader<File> {
@@ -106,6 +187,12 @@ impl EpointReader<File> {
             .ok_or(NoFileName())?
             .to_string_lossy()
             .to_lowercase();
+        if file_name_str.ends_with(FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT) {
+            return Ok(Self {
+                source: EpointSource::Directory(path.as_ref().to_path_buf()),
+                filter: EpointReadFilter::default(),
+            });
+        }
         if !file_name_str.ends_with(FILE_EXTENSION_EPOINT_TAR_FORMAT)
             && !file_name_str.ends_with(FILE_EXTENSION_EPOINT_FORMAT)
         {
@@ -116,3 +203,20 @@ impl EpointReader<File> {
         Ok(Self::new(file))
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct EpointReadInfo {
+    /// Format version the archive was written with.
+    pub format_version: u32,
+    /// Name and version of the software that wrote the archive, if recorded.
+    pub writer: Option<String>,
+    pub info: PointCloudInfo,
+    pub transform_tree: TransformTree,
+    pub point_count: usize,
+    /// Names and types of the point data columns, as they are returned by [`EpointReader::finish`].
+    pub schema: Schema,
+    pub column_descriptions: HashMap<String, EpointColumnDescription>,
+    pub bounding_box: AxisAlignedBoundingBox,
+    /// Closed range of the earliest and latest timestamp, if the points have timestamps.
+    pub time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
@@ -1,28 +1,635 @@
+use crate::Error::{FileNotFound, FormatNotSupported, UnsupportedEpointFormatVersion};
+use crate::epoint::documents::{
+    EpointBoundingBoxDocument, EpointColumnDocument, EpointInfoDocument, EpointPartIndexDocument,
+    EpointSummaryDocument, EpointTimeRangeDocument, EpointTimestampDocument,
+};
+use crate::epoint::{
+    DIRECTORY_NAME_POINT_DATA_PARTS, EPOINT_FORMAT_VERSION, EPOINT_SEPARATOR,
+    FILE_EXTENSION_POINT_DATA_COMPRESSED, FILE_NAME_ECOORD_COMPRESSED,
+    FILE_NAME_ECOORD_UNCOMPRESSED, FILE_NAME_INFO_COMPRESSED, FILE_NAME_INFO_UNCOMPRESSED,
+    FILE_NAME_PART_INDEX_COMPRESSED, FILE_NAME_PART_INDEX_UNCOMPRESSED,
+    FILE_NAME_POINT_DATA_COMPRESSED, FILE_NAME_POINT_DATA_UNCOMPRESSED,
+};
 use crate::error::Error;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read};
+use std::path::Path;
 use std::str::FromStr;
 
+use chrono::{DateTime, Utc};
+use ecoord::{AxisAlignedBoundingBox, TransformTree};
 use epoint_core::PointDataColumnType;
+use polars::io::HiveOptions;
+use polars::polars_utils::mmap::MemSlice;
 use polars::prelude::DataFrame;
 use polars::prelude::*;
+use tar::Archive;
+
+pub struct EpointArchiveEntries {
+    /// Format version the archive was written with.
+    pub format_version: u32,
+    /// Info document migrated to the current format version.
+    pub info_document: EpointInfoDocument,
+    /// Point data as stored, i.e. with quantized columns not yet restored.
+    pub point_data: Option<LazyFrame>,
+    pub transform_tree: TransformTree,
+}
+
+/// Collects the documents and point data of an epoint archive or directory, where the members
+/// are added in the order of the archive.
+struct EpointEntryCollector<'a> {
+    skip_summarized_point_data: bool,
+    bounds: Option<&'a AxisAlignedBoundingBox>,
+    info_document: Option<(u32, EpointInfoDocument)>,
+    part_index_document: Option<EpointPartIndexDocument>,
+    point_data: Option<LazyFrame>,
+    point_data_parts: Vec<LazyFrame>,
+    /// Scan of a part outside the bounds, which names the data types missing in the info document.
+    schema_point_data: Option<LazyFrame>,
+    transform_tree: Option<TransformTree>,
+}
+
+impl<'a> EpointEntryCollector<'a> {
+    fn new(skip_summarized_point_data: bool, bounds: Option<&'a AxisAlignedBoundingBox>) -> Self {
+        Self {
+            skip_summarized_point_data,
+            bounds,
+            info_document: None,
+            part_index_document: None,
+            point_data: None,
+            point_data_parts: Vec::new(),
+            schema_point_data: None,
+            transform_tree: None,
+        }
+    }
+
+    fn is_point_data(file_name: &str) -> bool {
+        file_name == FILE_NAME_POINT_DATA_COMPRESSED
+            || file_name == FILE_NAME_POINT_DATA_UNCOMPRESSED
+            || file_name.starts_with(&format!("{DIRECTORY_NAME_POINT_DATA_PARTS}/"))
+    }
+
+    /// Returns true, if the data types of some columns are only known from the stored point data.
+    fn requires_stored_schema(&self) -> bool {
+        self.info_document.as_ref().is_some_and(|(_, d)| {
+            d.columns
+                .iter()
+                .any(|c| parse_data_type(&c.name, &c.data_type).is_err())
+        })
+    }
+
+    /// Returns true, if the point data member must be scanned.
+    fn is_point_data_required(&self, file_name: &str) -> Result<bool, Error> {
+        if self.skip_summarized_point_data
+            && self
+                .info_document
+                .as_ref()
+                .is_some_and(|(_, d)| d.summary.is_some() && !d.columns.is_empty())
+            && !self.requires_stored_schema()
+        {
+            return Ok(false);
+        }
+        if !file_name.starts_with(&format!("{DIRECTORY_NAME_POINT_DATA_PARTS}/")) {
+            return Ok(true);
+        }
+
+        let part_document = self
+            .part_index_document
+            .as_ref()
+            .and_then(|d| d.parts.iter().find(|p| p.file_name == file_name))
+            .ok_or(FileNotFound(FILE_NAME_PART_INDEX_UNCOMPRESSED.to_string()))?;
+        Ok(self
+            .bounds
+            .is_none_or(|b| part_document.bounding_box.intersects(b)))
+    }
+
+    /// Returns true, if a point data part outside the bounds must be scanned for its schema.
+    fn is_schema_scan_required(&self) -> bool {
+        self.point_data_parts.is_empty()
+            && self.schema_point_data.is_none()
+            && self.requires_stored_schema()
+    }
+
+    fn add_point_data(&mut self, file_name: &str, point_data: LazyFrame, required: bool) {
+        if !required {
+            self.schema_point_data = Some(point_data);
+        } else if file_name.starts_with(&format!("{DIRECTORY_NAME_POINT_DATA_PARTS}/")) {
+            self.point_data_parts.push(point_data);
+        } else {
+            self.point_data = Some(point_data);
+        }
+    }
+
+    fn add_document<R: Read>(&mut self, file_name: &str, reader: R) -> Result<(), Error> {
+        match file_name {
+            FILE_NAME_INFO_UNCOMPRESSED => {
+                self.info_document = Some(migrate_info_document(serde_json::from_reader(reader)?)?);
+            }
+            FILE_NAME_INFO_COMPRESSED => {
+                self.info_document = Some(migrate_info_document(read_compressed_json(reader)?)?);
+            }
+            FILE_NAME_PART_INDEX_UNCOMPRESSED => {
+                self.part_index_document = Some(serde_json::from_reader(reader)?);
+            }
+            FILE_NAME_PART_INDEX_COMPRESSED => {
+                self.part_index_document =
+                    Some(serde_json::from_value(read_compressed_json(reader)?)?);
+            }
+            FILE_NAME_ECOORD_UNCOMPRESSED => {
+                self.transform_tree = Some(ecoord::io::EcoordReader::new(reader).finish()?);
+            }
+            FILE_NAME_ECOORD_COMPRESSED => {
+                self.transform_tree = Some(
+                    ecoord::io::EcoordReader::new(reader)
+                        .with_compression(ecoord::io::Compression::default_zstd())
+                        .finish()?,
+                );
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<EpointArchiveEntries, Error> {
+        let (format_version, info_document) =
+            self.info_document.ok_or(FileNotFound("info".to_string()))?;
+        let mut point_data = self.point_data;
+        if self.part_index_document.is_some() && point_data.is_none() {
+            point_data = Some(if self.point_data_parts.is_empty() {
+                // schema of the point data without decoding any part
+                let schema =
+                    create_point_data_schema(&info_document.columns, self.schema_point_data)?;
+                DataFrame::empty_with_schema(&schema).lazy()
+            } else {
+                concat(self.point_data_parts, UnionArgs::default())?
+            });
+        }
+        Ok(EpointArchiveEntries {
+            format_version,
+            info_document,
+            point_data,
+            transform_tree: self
+                .transform_tree
+                .ok_or(FileNotFound("ecoord".to_string()))?,
+        })
+    }
+}
+
+/// Reads the entries of an epoint archive, while the point data is only set up for a lazy scan.
+///
+/// If `skip_summarized_point_data` is set, the point data is skipped when the preceding info
+/// document contains a summary and names the data types of all columns. Point data parts of
+/// partitioned archives are skipped, if they do not intersect the bounds.
+pub fn read_epoint_archive<R: Read>(
+    reader: R,
+    skip_summarized_point_data: bool,
+    bounds: Option<&AxisAlignedBoundingBox>,
+) -> Result<EpointArchiveEntries, Error> {
+    let mut archive = Archive::new(reader);
+    let mut collector = EpointEntryCollector::new(skip_summarized_point_data, bounds);
+
+    for file in archive.entries()? {
+        let mut f = file?;
+        let file_name = f.path()?.to_str().unwrap().to_string();
+
+        if !EpointEntryCollector::is_point_data(&file_name) {
+            collector.add_document(&file_name, f)?;
+            continue;
+        }
+        let required = collector.is_point_data_required(&file_name)?;
+        if required || collector.is_schema_scan_required() {
+            let mut buffer: Vec<u8> = Vec::new();
+            f.read_to_end(&mut buffer)?;
+            let sources = ScanSources::Buffers([MemSlice::from_vec(buffer)].into());
+            let point_data = scan_point_data(sources, &file_name)?;
+            collector.add_point_data(&file_name, point_data, required);
+        }
+    }
+
+    collector.finish()
+}
+
+/// Reads the members of an epoint directory like [`read_epoint_archive`], while the point data
+/// files are scanned directly.
+pub fn read_epoint_directory(
+    directory_path: &Path,
+    skip_summarized_point_data: bool,
+    bounds: Option<&AxisAlignedBoundingBox>,
+) -> Result<EpointArchiveEntries, Error> {
+    if !directory_path.is_dir() {
+        return Err(FileNotFound(directory_path.to_string_lossy().to_string()));
+    }
+    let mut collector = EpointEntryCollector::new(skip_summarized_point_data, bounds);
+
+    for file_name in [
+        FILE_NAME_INFO_UNCOMPRESSED,
+        FILE_NAME_INFO_COMPRESSED,
+        FILE_NAME_ECOORD_UNCOMPRESSED,
+        FILE_NAME_ECOORD_COMPRESSED,
+        FILE_NAME_PART_INDEX_UNCOMPRESSED,
+        FILE_NAME_PART_INDEX_COMPRESSED,
+    ] {
+        let file_path = directory_path.join(file_name);
+        if file_path.is_file() {
+            collector.add_document(file_name, BufReader::new(File::open(file_path)?))?;
+        }
+    }
+
+    // only the parts listed in the index belong to the point data
+    let point_data_file_names: Vec<String> = match &collector.part_index_document {
+        Some(part_index_document) => part_index_document
+            .parts
+            .iter()
+            .map(|p| p.file_name.clone())
+            .collect(),
+        None => [
+            FILE_NAME_POINT_DATA_COMPRESSED,
+            FILE_NAME_POINT_DATA_UNCOMPRESSED,
+        ]
+        .into_iter()
+        .filter(|f| directory_path.join(f).is_file())
+        .map(|f| f.to_string())
+        .collect(),
+    };
+    for file_name in point_data_file_names {
+        let required = collector.is_point_data_required(&file_name)?;
+        if required || collector.is_schema_scan_required() {
+            let file_path = PlPath::Local(directory_path.join(&file_name).into());
+            let sources = ScanSources::Paths(vec![file_path].into());
+            let point_data = scan_point_data(sources, &file_name)?;
+            collector.add_point_data(&file_name, point_data, required);
+        }
+    }
+
+    collector.finish()
+}
+
+/// Restores the values of quantized columns from their scaled 32 bit integers.
+pub fn dequantize_lazy_frame(lazy_frame: LazyFrame, columns: &[EpointColumnDocument]) -> LazyFrame {
+    let dequantization_expr: Vec<Expr> = columns
+        .iter()
+        .filter_map(|c| {
+            let quantization = c.quantization?;
+            Some(
+                (col(c.name.as_str()).cast(DataType::Float64) * lit(quantization.scale)
+                    + lit(quantization.offset))
+                .alias(c.name.as_str()),
+            )
+        })
+        .collect();
+    if dequantization_expr.is_empty() {
+        return lazy_frame;
+    }
+
+    lazy_frame.with_columns(dequantization_expr)
+}
+
+fn read_compressed_json<R: Read>(reader: R) -> Result<serde_json::Value, Error> {
+    let mut decompressed_buffer: Vec<u8> = Vec::new();
+    zstd::stream::copy_decode(reader, &mut decompressed_buffer)?;
+    Ok(serde_json::from_reader(Cursor::new(decompressed_buffer))?)
+}
+
+/// Sets up a lazy scan of point data, which is stored as Parquet or, uncompressed, as CSV.
+fn scan_point_data(sources: ScanSources, file_name: &str) -> Result<LazyFrame, Error> {
+    if file_name.ends_with(FILE_EXTENSION_POINT_DATA_COMPRESSED) {
+        let scan_arguments = ScanArgsParquet {
+            hive_options: HiveOptions::new_disabled(),
+            ..Default::default()
+        };
+        Ok(LazyFrame::scan_parquet_sources(sources, scan_arguments)?)
+    } else {
+        let lazy_frame = LazyCsvReader::new_with_sources(sources)
+            .with_separator(EPOINT_SEPARATOR)
+            .finish()?;
+        Ok(lazy_frame)
+    }
+}
+
+/// Migrates an info document of any earlier format version to the current one and returns it
+/// together with the format version it was written with.
+///
+/// Version 1 archives have no format version and only a frame id in the info document.
+pub fn migrate_info_document(
+    mut value: serde_json::Value,
+) -> Result<(u32, EpointInfoDocument), Error> {
+    let format_version = match value.get("format_version") {
+        Some(format_version) => format_version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or(FormatNotSupported(format!(
+                "epoint format version {format_version}"
+            )))?,
+        None => 1,
+    };
+    if format_version > EPOINT_FORMAT_VERSION {
+        return Err(UnsupportedEpointFormatVersion(format_version));
+    }
+
+    // version 2 added the columns and the summary, which are derived from the point data if
+    // missing, version 3 partitioned point data and version 4 quantized columns
+    if let Some(info) = value.as_object_mut() {
+        info.insert("format_version".to_string(), EPOINT_FORMAT_VERSION.into());
+    }
+
+    let info_document: EpointInfoDocument = serde_json::from_value(value)?;
+    Ok((format_version, info_document))
+}
+
+/// Determines the columns from the schema of the point data.
+pub fn read_point_data_columns(point_data: LazyFrame) -> Result<Vec<EpointColumnDocument>, Error> {
+    let schema = cast_lazy_frame(point_data)?.collect_schema()?;
+    Ok(EpointColumnDocument::from_schema(&schema, &HashMap::new()))
+}
+
+/// Computes the summary of point data, for which only the coordinate and timestamp columns are
+/// decoded.
+pub fn summarize_point_data(point_data: LazyFrame) -> Result<EpointSummaryDocument, Error> {
+    let mut point_data = cast_lazy_frame(point_data)?;
+    let schema = point_data.collect_schema()?;
+
+    let (x, y, z) = (
+        PointDataColumnType::X.as_str(),
+        PointDataColumnType::Y.as_str(),
+        PointDataColumnType::Z.as_str(),
+    );
+    let mut aggregation_expr: Vec<Expr> = vec![
+        len(),
+        col(x).min().alias("x_min"),
+        col(y).min().alias("y_min"),
+        col(z).min().alias("z_min"),
+        col(x).max().alias("x_max"),
+        col(y).max().alias("y_max"),
+        col(z).max().alias("z_max"),
+    ];
+    let second = PointDataColumnType::TimestampSecond.as_str();
+    let nanosecond = PointDataColumnType::TimestampNanoSecond.as_str();
+    let contains_timestamps = schema.contains(second) && schema.contains(nanosecond);
+    if contains_timestamps {
+        aggregation_expr.extend([
+            col(second).min().alias("sec_min"),
+            col(nanosecond)
+                .filter(col(second).eq(col(second).min()))
+                .min()
+                .alias("nanosec_min"),
+            col(second).max().alias("sec_max"),
+            col(nanosecond)
+                .filter(col(second).eq(col(second).max()))
+                .max()
+                .alias("nanosec_max"),
+        ]);
+    }
+    let aggregation = point_data.select(aggregation_expr).collect()?;
+
+    let point_count = aggregation.column("len")?.u32()?.get(0).unwrap_or_default() as usize;
+    if point_count == 0 {
+        return Err(epoint_core::Error::NoRemainingPoints.into());
+    }
+    let value = |name: &str| -> Result<f64, Error> {
+        Ok(aggregation.column(name)?.f64()?.get(0).unwrap_or_default())
+    };
+    let bounding_box = EpointBoundingBoxDocument {
+        lower_bound: [value("x_min")?, value("y_min")?, value("z_min")?],
+        upper_bound: [value("x_max")?, value("y_max")?, value("z_max")?],
+    };
+    let time_range = if contains_timestamps {
+        let timestamp = |second_name: &str, nanosecond_name: &str| -> Result<_, Error> {
+            Ok(EpointTimestampDocument {
+                sec: aggregation
+                    .column(second_name)?
+                    .i64()?
+                    .get(0)
+                    .unwrap_or_default(),
+                nanosec: aggregation
+                    .column(nanosecond_name)?
+                    .u32()?
+                    .get(0)
+                    .unwrap_or_default(),
+            })
+        };
+        Some(EpointTimeRangeDocument {
+            start: timestamp("sec_min", "nanosec_min")?,
+            end: timestamp("sec_max", "nanosec_max")?,
+        })
+    } else {
+        None
+    };
+
+    Ok(EpointSummaryDocument {
+        point_count,
+        bounding_box,
+        time_range,
+    })
+}
+
+/// Parses the name of a column's data type as written into the summary.
+///
+/// Known point data columns get their defined data type.
+pub fn parse_data_type(column_name: &str, data_type: &str) -> Result<DataType, Error> {
+    if let Ok(column_type) = PointDataColumnType::from_str(column_name) {
+        return Ok(column_type.data_frame_data_type());
+    }
+
+    let data_type = match data_type {
+        "bool" => DataType::Boolean,
+        "u8" => DataType::UInt8,
+        "u16" => DataType::UInt16,
+        "u32" => DataType::UInt32,
+        "u64" => DataType::UInt64,
+        "i8" => DataType::Int8,
+        "i16" => DataType::Int16,
+        "i32" => DataType::Int32,
+        "i64" => DataType::Int64,
+        "f32" => DataType::Float32,
+        "f64" => DataType::Float64,
+        "str" => DataType::String,
+        _ => return Err(FormatNotSupported(data_type.to_string())),
+    };
+    Ok(data_type)
+}
+
+/// Creates the schema of the point data columns, where data types without a parsable name are
+/// taken from the schema of the stored point data.
+pub fn create_point_data_schema(
+    columns: &[EpointColumnDocument],
+    point_data: Option<LazyFrame>,
+) -> Result<Schema, Error> {
+    let mut stored_schema: Option<SchemaRef> = None;
+    let mut schema = Schema::with_capacity(columns.len());
+    for column in columns {
+        let data_type = match parse_data_type(&column.name, &column.data_type) {
+            Ok(data_type) => data_type,
+            Err(error) => {
+                if stored_schema.is_none()
+                    && let Some(mut point_data) = point_data.clone()
+                {
+                    stored_schema = Some(point_data.collect_schema()?);
+                }
+                stored_schema
+                    .as_ref()
+                    .and_then(|s| s.get(&column.name))
+                    .cloned()
+                    .ok_or(error)?
+            }
+        };
+        schema.insert(column.name.as_str().into(), data_type);
+    }
+
+    Ok(schema)
+}
 
 pub fn cast_data_frame(data_frame: DataFrame) -> Result<DataFrame, Error> {
+    let casted_data_frame = cast_lazy_frame(data_frame.lazy())?.collect()?;
+
+    Ok(casted_data_frame)
+}
+
+/// Casts the point data columns to their defined data types.
+///
+/// The cast is strict, so that values not representable by the defined data type cause an error
+/// instead of becoming null.
+pub fn cast_lazy_frame(mut lazy_frame: LazyFrame) -> Result<LazyFrame, Error> {
     let mut column_casting_expr: Vec<Expr> = Vec::new();
-    for current_column_name in data_frame.get_column_names() {
+    for current_column_name in lazy_frame.collect_schema()?.iter_names() {
         let column_type = PointDataColumnType::from_str(current_column_name.as_str()).ok();
 
         if let Some(column_type) = column_type {
             column_casting_expr
-                .push(col(column_type.as_str()).cast(column_type.data_frame_data_type()));
+                .push(col(column_type.as_str()).strict_cast(column_type.data_frame_data_type()));
         } else {
             column_casting_expr.push(col(current_column_name.as_str()));
         }
     }
 
-    let casted_data_frame = data_frame
-        .clone()
-        .lazy()
-        .select(column_casting_expr)
-        .collect()?;
+    Ok(lazy_frame.select(column_casting_expr))
+}
+
+/// Conditions on the points and columns, which are pushed down into the scan of the point data.
+#[derive(Debug, Clone, Default)]
+pub struct EpointReadFilter {
+    pub bounds: Option<AxisAlignedBoundingBox>,
+    pub time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    pub columns: Option<Vec<String>>,
+}
 
-    Ok(casted_data_frame)
+impl EpointReadFilter {
+    /// Applies the predicates and the projection to the uncasted point data as stored, whose
+    /// quantized columns are restored.
+    pub fn apply(
+        &self,
+        mut lazy_frame: LazyFrame,
+        columns: &[EpointColumnDocument],
+    ) -> Result<LazyFrame, Error> {
+        let schema = lazy_frame.collect_schema()?;
+
+        let coordinate_bounds = self.bounds.as_ref().map(|bounds| {
+            let (lower_bound, upper_bound) = (bounds.lower_bound(), bounds.upper_bound());
+            [
+                (PointDataColumnType::X, lower_bound.x, upper_bound.x),
+                (PointDataColumnType::Y, lower_bound.y, upper_bound.y),
+                (PointDataColumnType::Z, lower_bound.z, upper_bound.z),
+            ]
+        });
+
+        if let Some(coordinate_bounds) = &coordinate_bounds {
+            // the bounds are converted to the stored integers, so that row groups can still be
+            // skipped by their statistics, and widened by one step against rounding errors
+            let predicates: Vec<Expr> = coordinate_bounds
+                .iter()
+                .filter_map(|(column_type, lower, upper)| {
+                    let quantization = columns
+                        .iter()
+                        .find(|c| c.name == column_type.as_str())
+                        .and_then(|c| c.quantization)?;
+                    let quantize = |value: f64| {
+                        ((value - quantization.offset) / quantization.scale)
+                            .clamp(i32::MIN as f64, i32::MAX as f64)
+                    };
+                    let lower = (quantize(*lower).ceil() as i32).saturating_sub(1);
+                    let upper = (quantize(*upper).floor() as i32).saturating_add(1);
+                    let column = col(column_type.as_str());
+                    Some(
+                        column
+                            .clone()
+                            .gt_eq(lit(lower))
+                            .and(column.lt_eq(lit(upper))),
+                    )
+                })
+                .collect();
+            if let Some(predicate) = predicates.into_iter().reduce(|a, b| a.and(b)) {
+                lazy_frame = lazy_frame.filter(predicate);
+            }
+        }
+        lazy_frame = dequantize_lazy_frame(lazy_frame, columns);
+
+        if let Some(coordinate_bounds) = coordinate_bounds {
+            let predicate = coordinate_bounds
+                .into_iter()
+                .map(|(column_type, lower, upper)| {
+                    let column = col(column_type.as_str());
+                    column
+                        .clone()
+                        .gt_eq(lit(lower))
+                        .and(column.lt_eq(lit(upper)))
+                })
+                .reduce(|a, b| a.and(b))
+                .expect("three coordinates");
+            lazy_frame = lazy_frame.filter(predicate);
+        }
+
+        if let Some((start, end)) = &self.time_range {
+            let second_column = PointDataColumnType::TimestampSecond.as_str();
+            let nanosecond_column = PointDataColumnType::TimestampNanoSecond.as_str();
+            let predicate = if schema.contains(second_column) && schema.contains(nanosecond_column)
+            {
+                // the second bounds allow skipping row groups by their statistics
+                let (second, nanosecond) = (col(second_column), col(nanosecond_column));
+                let after_start = second.clone().gt(lit(start.timestamp())).or(second
+                    .clone()
+                    .eq(lit(start.timestamp()))
+                    .and(
+                        nanosecond
+                            .clone()
+                            .gt_eq(lit(start.timestamp_subsec_nanos())),
+                    ));
+                let before_end = second.clone().lt(lit(end.timestamp())).or(second
+                    .clone()
+                    .eq(lit(end.timestamp()))
+                    .and(nanosecond.lt_eq(lit(end.timestamp_subsec_nanos()))));
+
+                second
+                    .clone()
+                    .gt_eq(lit(start.timestamp()))
+                    .and(second.lt_eq(lit(end.timestamp())))
+                    .and(after_start)
+                    .and(before_end)
+            } else {
+                lit(false)
+            };
+            lazy_frame = lazy_frame.filter(predicate);
+        }
+
+        if let Some(columns) = &self.columns {
+            let selected_columns: Vec<Expr> = schema
+                .iter_names()
+                .filter(|c| {
+                    [
+                        PointDataColumnType::X,
+                        PointDataColumnType::Y,
+                        PointDataColumnType::Z,
+                        PointDataColumnType::FrameId,
+                    ]
+                    .iter()
+                    .any(|t| t.as_str() == c.as_str())
+                        || columns.iter().any(|s| s == c.as_str())
+                })
+                .map(|c| col(c.clone()))
+                .collect();
+            lazy_frame = lazy_frame.select(selected_columns);
+        }
+
+        Ok(lazy_frame)
+    }
 }
@@ -1,9 +1,14 @@
 use crate::Error::{InvalidFileExtension, NoFileName};
-use crate::epoint::write_impl::write_epoint_format;
-use crate::epoint::{FILE_EXTENSION_EPOINT_FORMAT, FILE_EXTENSION_EPOINT_TAR_FORMAT};
+use crate::epoint::write_impl::{EpointDestination, write_epoint_format};
+use crate::epoint::{
+    EpointColumnDescription, EpointParquetOptions, EpointPartitioning,
+    FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT, FILE_EXTENSION_EPOINT_FORMAT,
+    FILE_EXTENSION_EPOINT_TAR_FORMAT,
+};
 use crate::error::Error;
 use chrono::{DateTime, Utc};
 use epoint_core::PointCloud;
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::Path;
@@ -12,19 +17,27 @@ pub const DEFAULT_COMPRESSION_LEVEL: i32 = 10;
 
 /// `EpointWriter` sets up a writer for the custom reader data structure.
 ///
+/// Besides a tar archive, the writer creates an unarchived epoint directory holding the same
+/// members via [`EpointWriter::from_path`], which replaces the members of a previous write.
 #[derive(Debug, Clone)]
 pub struct EpointWriter<W: Write> {
-    writer: W,
+    destination: EpointDestination<W>,
     compression_level: Option<i32>,
     time: Option<DateTime<Utc>>,
+    column_descriptions: HashMap<String, EpointColumnDescription>,
+    partitioning: Option<EpointPartitioning>,
+    parquet_options: EpointParquetOptions,
 }
 
 impl<W: Write> EpointWriter<W> {
     pub fn new(writer: W) -> Self {
         Self {
-            writer,
+            destination: EpointDestination::Archive(writer),
             compression_level: Some(DEFAULT_COMPRESSION_LEVEL),
             time: None,
+            column_descriptions: HashMap::new(),
+            partitioning: None,
+            parquet_options: EpointParquetOptions::default(),
         }
     }
 
@@ -42,8 +55,40 @@ impl<W: Write> EpointWriter<W> {
         self
     }
 
+    /// Sets the unit and description of columns by their names, which take precedence over the
+    /// ones of known point data columns.
+    pub fn with_column_descriptions(
+        mut self,
+        column_descriptions: HashMap<String, EpointColumnDescription>,
+    ) -> Self {
+        self.column_descriptions = column_descriptions;
+        self
+    }
+
+    /// Writes the point data as several spatially partitioned parts, which are listed with their
+    /// bounds and point counts in an index.
+    pub fn with_partitioning(mut self, partitioning: Option<EpointPartitioning>) -> Self {
+        self.partitioning = partitioning;
+        self
+    }
+
+    /// Sets the codec, row group size, dictionary encoding and sorting of the Parquet point data,
+    /// while the compression level only applies to the JSON documents.
+    pub fn with_parquet_options(mut self, parquet_options: EpointParquetOptions) -> Self {
+        self.parquet_options = parquet_options;
+        self
+    }
+
     pub fn finish(self, point_cloud: PointCloud) -> Result<(), Error> {
-        write_epoint_format(self.writer, point_cloud, self.compression_level, self.time)?;
+        write_epoint_format(
+            self.destination,
+            point_cloud,
+            self.compression_level,
+            self.time,
+            &self.column_descriptions,
+            self.partitioning,
+            &self.parquet_options,
+        )?;
 
         Ok(())
     }
@@ -57,6 +102,16 @@ impl EpointWriter<File> {
             .ok_or(NoFileName())?
             .to_string_lossy()
             .to_lowercase();
+        if file_name_str.ends_with(FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT) {
+            return Ok(Self {
+                destination: EpointDestination::Directory(path.as_ref().to_path_buf()),
+                compression_level: Some(DEFAULT_COMPRESSION_LEVEL),
+                time: None,
+                column_descriptions: HashMap::new(),
+                partitioning: None,
+                parquet_options: EpointParquetOptions::default(),
+            });
+        }
         if !file_name_str.ends_with(FILE_EXTENSION_EPOINT_TAR_FORMAT)
             && !file_name_str.ends_with(FILE_EXTENSION_EPOINT_FORMAT)
         {
@@ -1,49 +1,159 @@
-use crate::epoint::documents::EpointInfoDocument;
+use crate::Error::{CoordinateScaleTooSmall, InvalidPartitioning, NonFiniteQuantizedValue};
+use crate::epoint::documents::{
+    EpointColumnDocument, EpointInfoDocument, EpointPartDocument, EpointPartIndexDocument,
+    EpointQuantizationDocument, EpointSummaryDocument, EpointTimeRangeDocument,
+};
 use crate::epoint::{
-    EPOINT_SEPARATOR, FILE_NAME_ECOORD_COMPRESSED, FILE_NAME_ECOORD_UNCOMPRESSED,
-    FILE_NAME_INFO_COMPRESSED, FILE_NAME_INFO_UNCOMPRESSED, FILE_NAME_POINT_DATA_COMPRESSED,
+    DIRECTORY_NAME_POINT_DATA_PARTS, EPOINT_SEPARATOR, EpointColumnDescription,
+    EpointParquetCompression, EpointParquetOptions, EpointPartitioning, EpointQuantization,
+    FILE_EXTENSION_POINT_DATA_COMPRESSED, FILE_EXTENSION_POINT_DATA_UNCOMPRESSED,
+    FILE_NAME_ECOORD_COMPRESSED, FILE_NAME_ECOORD_UNCOMPRESSED, FILE_NAME_INFO_COMPRESSED,
+    FILE_NAME_INFO_UNCOMPRESSED, FILE_NAME_PART_INDEX_COMPRESSED,
+    FILE_NAME_PART_INDEX_UNCOMPRESSED, FILE_NAME_POINT_DATA_COMPRESSED,
     FILE_NAME_POINT_DATA_UNCOMPRESSED,
 };
 use crate::error::Error;
 use chrono::{DateTime, Utc};
-use epoint_core::PointCloud;
-use polars::prelude::{CsvWriter, ParquetWriter, SerWriter, StatisticsOptions};
+use ecoord::octree::{OctantIndex, StorageMode};
+use epoint_core::octree::PointCloudOctree;
+use epoint_core::{PointCloud, PointData, PointDataColumnType};
+use polars::frame::chunk_df_for_writing;
+use polars::prelude::{
+    BrotliLevel, CompatLevel, CsvWriter, DataFrame, GzipLevel, NamedFrom, ParquetCompression,
+    ParquetWriter, SchemaExt, SerWriter, Series, StatisticsOptions, UInt32Chunked, ZstdLevel,
+};
+use polars_parquet::write::{
+    ColumnWriteOptions, Encoding, FieldWriteOptions, FileWriter, RowGroupIterator, Version,
+    WriteOptions,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::fs::File;
 use std::io::{Cursor, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 use tar::Builder;
 
+/// Number of points per row group written by polars, if no row group size is given.
+const DEFAULT_PARQUET_ROW_GROUP_SIZE: usize = 512 * 512;
+/// Bits of the quantized coordinates of each axis, which are interleaved to a 64 bit Morton code.
+const MORTON_BITS_PER_AXIS: u32 = 21;
+
+/// Destination of an epoint archive's members, which is either a tar stream or a directory.
+#[derive(Debug, Clone)]
+pub enum EpointDestination<W: Write> {
+    Archive(W),
+    Directory(PathBuf),
+}
+
+enum MemberWriter<W: Write> {
+    Archive(Builder<W>),
+    Directory(PathBuf),
+}
+
+impl<W: Write> MemberWriter<W> {
+    fn new(destination: EpointDestination<W>) -> Result<Self, Error> {
+        match destination {
+            EpointDestination::Archive(writer) => Ok(Self::Archive(Builder::new(writer))),
+            EpointDestination::Directory(directory_path) => {
+                // members of a previous write would be mistaken for the point data
+                for file_name in [
+                    FILE_NAME_POINT_DATA_COMPRESSED,
+                    FILE_NAME_POINT_DATA_UNCOMPRESSED,
+                    FILE_NAME_INFO_COMPRESSED,
+                    FILE_NAME_INFO_UNCOMPRESSED,
+                    FILE_NAME_ECOORD_COMPRESSED,
+                    FILE_NAME_ECOORD_UNCOMPRESSED,
+                    FILE_NAME_PART_INDEX_COMPRESSED,
+                    FILE_NAME_PART_INDEX_UNCOMPRESSED,
+                ] {
+                    let file_path = directory_path.join(file_name);
+                    if file_path.is_file() {
+                        fs::remove_file(file_path)?;
+                    }
+                }
+                let parts_directory_path = directory_path.join(DIRECTORY_NAME_POINT_DATA_PARTS);
+                if parts_directory_path.is_dir() {
+                    fs::remove_dir_all(parts_directory_path)?;
+                }
+                fs::create_dir_all(&directory_path)?;
+
+                Ok(Self::Directory(directory_path))
+            }
+        }
+    }
+
+    fn append(
+        &mut self,
+        file_name: &str,
+        data: Vec<u8>,
+        time: Option<DateTime<Utc>>,
+    ) -> Result<(), Error> {
+        match self {
+            MemberWriter::Archive(archive_builder) => {
+                archive_builder.append_data(
+                    &mut create_archive_header(data.len(), time),
+                    file_name,
+                    Cursor::new(data),
+                )?;
+            }
+            MemberWriter::Directory(directory_path) => {
+                let file_path = directory_path.join(file_name);
+                if let Some(parent_path) = file_path.parent() {
+                    fs::create_dir_all(parent_path)?;
+                }
+                let mut file = File::create(file_path)?;
+                file.write_all(&data)?;
+                if let Some(time) = time {
+                    file.set_modified(time.into())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub fn write_epoint_format<W: Write>(
-    writer: W,
+    destination: EpointDestination<W>,
     mut point_cloud: PointCloud,
     compression_level: Option<i32>,
     time: Option<DateTime<Utc>>,
+    column_descriptions: &HashMap<String, EpointColumnDescription>,
+    partitioning: Option<EpointPartitioning>,
+    parquet_options: &EpointParquetOptions,
 ) -> Result<(), Error> {
-    let mut archive_builder = Builder::new(writer);
+    if let Some(partitioning) = &partitioning {
+        validate_partitioning(partitioning)?;
+    }
+    let mut member_writer = MemberWriter::new(destination)?;
 
     // info document
-    let info_document =
-        EpointInfoDocument::new().with_frame_id(point_cloud.info().frame_id.clone());
-    let mut info_document_buffer: Vec<u8> = Vec::new();
-    if let Some(compression_level) = compression_level {
-        serde_json::to_writer(&mut info_document_buffer, &info_document)?;
-        let mut info_document_compressed_buffer: Vec<u8> = Vec::new();
-        zstd::stream::copy_encode(
-            Cursor::new(info_document_buffer),
-            &mut info_document_compressed_buffer,
-            compression_level,
-        )?;
-        archive_builder.append_data(
-            &mut create_archive_header(info_document_compressed_buffer.len(), time),
-            FILE_NAME_INFO_COMPRESSED,
-            Cursor::new(info_document_compressed_buffer),
-        )?;
-    } else {
-        serde_json::to_writer_pretty(&mut info_document_buffer, &info_document)?;
-        archive_builder.append_data(
-            &mut create_archive_header(info_document_buffer.len(), time),
-            FILE_NAME_INFO_UNCOMPRESSED,
-            Cursor::new(info_document_buffer),
-        )?;
+    let mut column_documents = EpointColumnDocument::from_schema(
+        point_cloud.point_data.data_frame.schema(),
+        column_descriptions,
+    );
+    if compression_level.is_some()
+        && let Some(quantization) = &parquet_options.quantization
+    {
+        set_column_quantization(&mut column_documents, &point_cloud.point_data, quantization);
     }
+    let format_version = required_format_version(&column_documents, partitioning.is_some());
+    let info_document = EpointInfoDocument::new()
+        .with_format_version(format_version)
+        .with_frame_id(point_cloud.info().frame_id.clone())
+        .with_columns(column_documents)
+        .with_metadata(point_cloud.info().metadata.clone())
+        .with_summary(Some(create_summary_document(&point_cloud.point_data)?));
+    append_json_document(
+        &mut member_writer,
+        &info_document,
+        compression_level,
+        time,
+        FILE_NAME_INFO_UNCOMPRESSED,
+        FILE_NAME_INFO_COMPRESSED,
+    )?;
 
     // ecoord document
     let mut ecoord_document_buffer: Vec<u8> = Vec::new();
@@ -61,37 +171,406 @@ pub fn write_epoint_format<W: Write>(
     } else {
         FILE_NAME_ECOORD_UNCOMPRESSED
     };
-    archive_builder.append_data(
-        &mut create_archive_header(ecoord_document_buffer.len(), time),
-        ecoord_document_file_name,
-        Cursor::new(ecoord_document_buffer),
-    )?;
+    member_writer.append(ecoord_document_file_name, ecoord_document_buffer, time)?;
 
     // point data
+    let Some(partitioning) = partitioning else {
+        let file_name = if compression_level.is_some() {
+            FILE_NAME_POINT_DATA_COMPRESSED
+        } else {
+            FILE_NAME_POINT_DATA_UNCOMPRESSED
+        };
+        if compression_level.is_some() && parquet_options.spatial_sorting {
+            sort_by_morton_order(&mut point_cloud.point_data)?;
+        }
+        append_point_data(
+            &mut member_writer,
+            &mut point_cloud.point_data.data_frame,
+            compression_level,
+            parquet_options,
+            &info_document.columns,
+            time,
+            file_name,
+        )?;
+        return Ok(());
+    };
+
+    let mut parts = partition_point_cloud(point_cloud, partitioning)?;
+    let file_extension = if compression_level.is_some() {
+        FILE_EXTENSION_POINT_DATA_COMPRESSED
+    } else {
+        FILE_EXTENSION_POINT_DATA_UNCOMPRESSED
+    };
+    let part_index_document = EpointPartIndexDocument {
+        parts: parts
+            .iter()
+            .map(|(key, part)| EpointPartDocument {
+                file_name: format!("{DIRECTORY_NAME_POINT_DATA_PARTS}/{key}.{file_extension}"),
+                point_count: part.size(),
+                bounding_box: part.point_data.get_axis_aligned_bounding_box().into(),
+            })
+            .collect(),
+    };
+    append_json_document(
+        &mut member_writer,
+        &part_index_document,
+        compression_level,
+        time,
+        FILE_NAME_PART_INDEX_UNCOMPRESSED,
+        FILE_NAME_PART_INDEX_COMPRESSED,
+    )?;
+    for ((_, part), part_document) in parts.iter_mut().zip(&part_index_document.parts) {
+        if compression_level.is_some() && parquet_options.spatial_sorting {
+            sort_by_morton_order(&mut part.point_data)?;
+        }
+        append_point_data(
+            &mut member_writer,
+            &mut part.point_data.data_frame,
+            compression_level,
+            parquet_options,
+            &info_document.columns,
+            time,
+            &part_document.file_name,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn validate_partitioning(partitioning: &EpointPartitioning) -> Result<(), Error> {
+    match *partitioning {
+        EpointPartitioning::Octree {
+            max_points_per_part: 0,
+        } => Err(InvalidPartitioning(
+            "maximum number of points per part must be positive".to_string(),
+        )),
+        EpointPartitioning::Grid { tile_size } if !tile_size.is_finite() || tile_size <= 0.0 => {
+            Err(InvalidPartitioning(format!(
+                "tile size of {tile_size} must be finite and positive"
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Splits the point cloud into parts, which are keyed by a name unique within the archive.
+fn partition_point_cloud(
+    point_cloud: PointCloud,
+    partitioning: EpointPartitioning,
+) -> Result<Vec<(String, PointCloud)>, Error> {
+    let parts = match partitioning {
+        EpointPartitioning::Octree {
+            max_points_per_part,
+        } => {
+            let point_cloud_octree = PointCloudOctree::new(
+                point_cloud,
+                max_points_per_part,
+                StorageMode::LeafOctantsOnly,
+                None,
+            )?;
+            let cell_indices: BTreeSet<OctantIndex> =
+                point_cloud_octree.cell_indices().into_iter().collect();
+
+            cell_indices
+                .into_iter()
+                .map(|i| {
+                    let key = format!("octant_{}_{}_{}_{}", i.level, i.x, i.y, i.z);
+                    Ok((key, point_cloud_octree.extract_octant(i)?))
+                })
+                .collect::<Result<Vec<_>, Error>>()?
+        }
+        EpointPartitioning::Grid { tile_size } => {
+            let mut tiles: BTreeMap<(i64, i64), Vec<u32>> = BTreeMap::new();
+            for (index, point) in point_cloud.point_data.get_all_points().iter().enumerate() {
+                let tile_index = (
+                    (point.x / tile_size).floor() as i64,
+                    (point.y / tile_size).floor() as i64,
+                );
+                tiles.entry(tile_index).or_default().push(index as u32);
+            }
+
+            tiles
+                .into_iter()
+                .map(|((x, y), indices)| {
+                    let data_frame = point_cloud
+                        .point_data
+                        .data_frame
+                        .take(&UInt32Chunked::from_vec("idx".into(), indices))?;
+                    let part = PointCloud::from_data_frame(
+                        data_frame,
+                        point_cloud.info.clone(),
+                        point_cloud.transform_tree.clone(),
+                    )?;
+                    Ok((format!("tile_{x}_{y}"), part))
+                })
+                .collect::<Result<Vec<_>, Error>>()?
+        }
+    };
+
+    Ok(parts)
+}
+
+fn append_point_data<W: Write>(
+    member_writer: &mut MemberWriter<W>,
+    data_frame: &mut DataFrame,
+    compression_level: Option<i32>,
+    parquet_options: &EpointParquetOptions,
+    column_documents: &[EpointColumnDocument],
+    time: Option<DateTime<Utc>>,
+    file_name: &str,
+) -> Result<(), Error> {
     let mut point_data_buffer: Vec<u8> = Vec::new();
     if compression_level.is_some() {
-        ParquetWriter::new(&mut point_data_buffer)
-            .with_statistics(StatisticsOptions::default())
-            .finish(&mut point_cloud.point_data.data_frame)?;
-        archive_builder.append_data(
-            &mut create_archive_header(point_data_buffer.len(), time),
-            FILE_NAME_POINT_DATA_COMPRESSED,
-            Cursor::new(point_data_buffer),
-        )?;
+        quantize_data_frame(data_frame, column_documents)?;
+        write_parquet(&mut point_data_buffer, data_frame, parquet_options)?;
     } else {
         CsvWriter::new(&mut point_data_buffer)
             .with_separator(EPOINT_SEPARATOR)
-            .finish(&mut point_cloud.point_data.data_frame)?;
-        archive_builder.append_data(
-            &mut create_archive_header(point_data_buffer.len(), time),
-            FILE_NAME_POINT_DATA_UNCOMPRESSED,
-            Cursor::new(point_data_buffer),
+            .finish(data_frame)?;
+    }
+    member_writer.append(file_name, point_data_buffer, time)?;
+
+    Ok(())
+}
+
+/// Writes the point data as Parquet.
+///
+/// Without dictionary encoding, the steps of [`ParquetWriter::finish`] are repeated with plain
+/// encoding for all columns, so that the row groups, the format version, the statistics and the
+/// Arrow schema in the key-value metadata equal the ones written by polars.
+fn write_parquet<W: Write>(
+    writer: W,
+    data_frame: &mut DataFrame,
+    parquet_options: &EpointParquetOptions,
+) -> Result<(), Error> {
+    let compression = match parquet_options.compression {
+        EpointParquetCompression::Uncompressed => ParquetCompression::Uncompressed,
+        EpointParquetCompression::Snappy => ParquetCompression::Snappy,
+        EpointParquetCompression::Lz4 => ParquetCompression::Lz4Raw,
+        EpointParquetCompression::Gzip(level) => {
+            ParquetCompression::Gzip(level.map(GzipLevel::try_new).transpose()?)
+        }
+        EpointParquetCompression::Brotli(level) => {
+            ParquetCompression::Brotli(level.map(BrotliLevel::try_new).transpose()?)
+        }
+        EpointParquetCompression::Zstd(level) => {
+            ParquetCompression::Zstd(level.map(ZstdLevel::try_new).transpose()?)
+        }
+    };
+    if parquet_options.dictionary_encoding {
+        ParquetWriter::new(writer)
+            .with_compression(compression)
+            .with_row_group_size(parquet_options.row_group_size)
+            .with_statistics(StatisticsOptions::default())
+            .finish(data_frame)?;
+        return Ok(());
+    }
+
+    // polars' writer dictionary encodes integer and string columns without an option to opt out
+    let data_frame = chunk_df_for_writing(
+        data_frame,
+        parquet_options
+            .row_group_size
+            .unwrap_or(DEFAULT_PARQUET_ROW_GROUP_SIZE),
+    )?;
+    let schema = data_frame.schema().to_arrow(CompatLevel::newest());
+    let column_options: Vec<ColumnWriteOptions> = schema
+        .iter_values()
+        .map(|_| {
+            FieldWriteOptions::default_with_encoding(Encoding::Plain)
+                .into_default_column_write_options()
+        })
+        .collect();
+    let write_options = WriteOptions {
+        statistics: StatisticsOptions::default(),
+        version: Version::V1,
+        compression: compression.into(),
+        data_page_size: None,
+    };
+    let row_groups = RowGroupIterator::try_new(
+        data_frame.iter_chunks(CompatLevel::newest(), false).map(Ok),
+        &schema,
+        write_options,
+        column_options.clone(),
+    )?;
+    let mut file_writer = FileWriter::try_new(writer, schema, write_options, &column_options)?;
+    for row_group in row_groups {
+        file_writer.write(row_group?)?;
+    }
+    file_writer.end(None, &column_options)?;
+
+    Ok(())
+}
+
+/// Returns the lowest format version, which supports the partitioning and the quantized columns.
+fn required_format_version(column_documents: &[EpointColumnDocument], partitioned: bool) -> u32 {
+    if column_documents.iter().any(|c| c.quantization.is_some()) {
+        4
+    } else if partitioned {
+        3
+    } else {
+        2
+    }
+}
+
+/// Sets the scale and offset of the coordinate columns, which are stored as 32 bit integers.
+fn set_column_quantization(
+    column_documents: &mut [EpointColumnDocument],
+    point_data: &PointData,
+    quantization: &EpointQuantization,
+) {
+    let offset = quantization
+        .offset
+        .unwrap_or_else(|| point_data.get_local_center());
+    for column_document in column_documents {
+        let axis = match PointDataColumnType::from_str(&column_document.name) {
+            Ok(PointDataColumnType::X) => 0,
+            Ok(PointDataColumnType::Y) => 1,
+            Ok(PointDataColumnType::Z) => 2,
+            Ok(PointDataColumnType::SensorTranslationX) if quantization.sensor_translation => 0,
+            Ok(PointDataColumnType::SensorTranslationY) if quantization.sensor_translation => 1,
+            Ok(PointDataColumnType::SensorTranslationZ) if quantization.sensor_translation => 2,
+            _ => continue,
+        };
+        column_document.quantization = Some(EpointQuantizationDocument {
+            scale: quantization.scale[axis],
+            offset: offset[axis],
+        });
+    }
+}
+
+/// Replaces the values of quantized columns by their scaled 32 bit integers.
+fn quantize_data_frame(
+    data_frame: &mut DataFrame,
+    column_documents: &[EpointColumnDocument],
+) -> Result<(), Error> {
+    for column_document in column_documents {
+        let Some(quantization) = column_document.quantization else {
+            continue;
+        };
+        let quantized_values: Vec<Option<i32>> = data_frame
+            .column(&column_document.name)?
+            .f64()?
+            .iter()
+            .map(|v| {
+                v.map(|v| {
+                    let quantized_value = ((v - quantization.offset) / quantization.scale).round();
+                    if !quantized_value.is_finite() {
+                        return Err(NonFiniteQuantizedValue(column_document.name.clone()));
+                    }
+                    if quantized_value.abs() > i32::MAX as f64 {
+                        return Err(CoordinateScaleTooSmall(quantization.scale));
+                    }
+                    Ok(quantized_value as i32)
+                })
+                .transpose()
+            })
+            .collect::<Result<_, Error>>()?;
+        data_frame.with_column(Series::new(
+            column_document.name.as_str().into(),
+            quantized_values,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Sorts the points along the Morton curve through their bounding box, so that points following
+/// each other are mostly close to each other.
+fn sort_by_morton_order(point_data: &mut PointData) -> Result<(), Error> {
+    if point_data.is_empty() {
+        return Ok(());
+    }
+    let bounding_box = point_data.get_axis_aligned_bounding_box();
+    let lower_bound = bounding_box.lower_bound();
+    let extent = bounding_box.upper_bound() - lower_bound;
+    let cell_count = ((1u64 << MORTON_BITS_PER_AXIS) - 1) as f64;
+
+    let mut morton_codes: Vec<(u64, u32)> = point_data
+        .get_all_points()
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            let relative_position = point - lower_bound;
+            let code = (0..3).fold(0u64, |code, axis| {
+                let cell_index = if extent[axis] > 0.0 {
+                    (relative_position[axis] / extent[axis] * cell_count).round() as u64
+                } else {
+                    0
+                };
+                code | (spread_bits(cell_index) << axis)
+            });
+            (code, index as u32)
+        })
+        .collect();
+    morton_codes.sort_by_key(|(code, _)| *code);
+
+    let indices = UInt32Chunked::from_vec(
+        "idx".into(),
+        morton_codes.into_iter().map(|(_, index)| index).collect(),
+    );
+    point_data.data_frame = point_data.data_frame.take(&indices)?;
+    Ok(())
+}
+
+/// Inserts two zero bits after each of the lower 21 bits of the value.
+fn spread_bits(value: u64) -> u64 {
+    let mut value = value & 0x1f_ffff;
+    value = (value | value << 32) & 0x001f_0000_0000_ffff;
+    value = (value | value << 16) & 0x001f_0000_ff00_00ff;
+    value = (value | value << 8) & 0x100f_00f0_0f00_f00f;
+    value = (value | value << 4) & 0x10c3_0c30_c30c_30c3;
+    value = (value | value << 2) & 0x1249_2492_4924_9249;
+    value
+}
+
+/// Appends a JSON document, which is compressed if a compression level is given.
+fn append_json_document<W: Write, T: Serialize>(
+    member_writer: &mut MemberWriter<W>,
+    document: &T,
+    compression_level: Option<i32>,
+    time: Option<DateTime<Utc>>,
+    file_name_uncompressed: &str,
+    file_name_compressed: &str,
+) -> Result<(), Error> {
+    let mut document_buffer: Vec<u8> = Vec::new();
+    if let Some(compression_level) = compression_level {
+        serde_json::to_writer(&mut document_buffer, document)?;
+        let mut document_compressed_buffer: Vec<u8> = Vec::new();
+        zstd::stream::copy_encode(
+            Cursor::new(document_buffer),
+            &mut document_compressed_buffer,
+            compression_level,
         )?;
+        member_writer.append(file_name_compressed, document_compressed_buffer, time)?;
+    } else {
+        serde_json::to_writer_pretty(&mut document_buffer, document)?;
+        member_writer.append(file_name_uncompressed, document_buffer, time)?;
     }
 
     Ok(())
 }
 
+/// Precomputes the statistics, which are read without decoding the point data.
+fn create_summary_document(point_data: &PointData) -> Result<EpointSummaryDocument, Error> {
+    let time_range = match (
+        point_data.get_timestamp_min()?,
+        point_data.get_timestamp_max()?,
+    ) {
+        (Some(start), Some(end)) => Some(EpointTimeRangeDocument {
+            start: start.into(),
+            end: end.into(),
+        }),
+        _ => None,
+    };
+
+    Ok(EpointSummaryDocument {
+        point_count: point_data.height(),
+        bounding_box: point_data.get_axis_aligned_bounding_box().into(),
+        time_range,
+    })
+}
+
 fn create_archive_header(size: usize, time: Option<chrono::DateTime<Utc>>) -> tar::Header {
     let mut header = tar::Header::new_gnu();
     header.set_size(size as u64);
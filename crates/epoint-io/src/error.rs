@@ -4,6 +4,10 @@ use thiserror::Error;
 pub enum Error {
     #[error(transparent)]
     EpointIoE57Error(#[from] crate::e57::error::Error),
+    #[error(transparent)]
+    EpointIoPcdError(#[from] crate::pcd::error::Error),
+    #[error(transparent)]
+    EpointIoPlyError(#[from] crate::ply::error::Error),
 
     #[error(transparent)]
     EpointError(#[from] epoint_core::Error),
@@ -20,6 +24,8 @@ pub enum Error {
     #[error(transparent)]
     Las(#[from] las::Error),
     #[error(transparent)]
+    Laz(#[from] laz::LasZipError),
+    #[error(transparent)]
     StdSystemTimeError(#[from] std::time::SystemTimeError),
 
     #[error("file extension is invalid")]
@@ -39,6 +45,29 @@ pub enum Error {
     UnknownFileExtension(String),
     #[error("invalid version of major={major} and minor={minor}")]
     InvalidVersion { major: u8, minor: u8 },
+    #[error("COPC file is invalid: {0}")]
+    InvalidCopcFile(String),
+    #[error(
+        "COPC octant with {byte_size} bytes and {point_count} points exceeds the 32 bit limits of the hierarchy"
+    )]
+    CopcOctantTooLarge {
+        byte_size: usize,
+        point_count: usize,
+    },
+    #[error("scale of {0} is too small for representing the coordinate extent as 32 bit integers")]
+    CoordinateScaleTooSmall(f64),
+    #[error("column `{0}` contains non-finite values, which cannot be quantized")]
+    NonFiniteQuantizedValue(String),
+    #[error("coordinate reference system EPSG:{0} is not supported")]
+    UnsupportedCrs(u32),
+    #[error(
+        "coordinate reference system EPSG:{0} requires an OGC WKT definition in the metadata for extended point formats"
+    )]
+    MissingCrsWkt(u32),
+    #[error("epoint format version {0} is newer than the supported version")]
+    UnsupportedEpointFormatVersion(u32),
+    #[error("partitioning is invalid: {0}")]
+    InvalidPartitioning(String),
 
     #[error("file extension is invalid")]
     PointDataFileNotFound(),
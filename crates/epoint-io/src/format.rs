@@ -1,7 +1,8 @@
 use crate::{
-    FILE_EXTENSION_E57_FORMAT, FILE_EXTENSION_EPOINT_FORMAT, FILE_EXTENSION_EPOINT_TAR_FORMAT,
-    FILE_EXTENSION_LAS_FORMAT, FILE_EXTENSION_LAZ_FORMAT, FILE_EXTENSION_XYZ_FORMAT,
-    FILE_EXTENSION_XYZ_ZST_FORMAT,
+    FILE_EXTENSION_E57_FORMAT, FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT,
+    FILE_EXTENSION_EPOINT_FORMAT, FILE_EXTENSION_EPOINT_TAR_FORMAT, FILE_EXTENSION_LAS_FORMAT,
+    FILE_EXTENSION_LAZ_FORMAT, FILE_EXTENSION_PCD_FORMAT, FILE_EXTENSION_PLY_FORMAT,
+    FILE_EXTENSION_XYZ_FORMAT, FILE_EXTENSION_XYZ_ZST_FORMAT,
 };
 use std::path::Path;
 
@@ -9,9 +10,12 @@ use std::path::Path;
 pub enum PointCloudFormat {
     Epoint,
     EpointTar,
+    EpointDirectory,
     E57,
     Las,
     Laz,
+    Pcd,
+    Ply,
     Xyz,
     XyzZst,
 }
@@ -21,11 +25,16 @@ impl PointCloudFormat {
         let path_str = path.as_ref().file_name()?.to_string_lossy().to_lowercase();
 
         match path_str {
+            s if s.ends_with(FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT) => {
+                Some(PointCloudFormat::EpointDirectory)
+            }
             s if s.ends_with(FILE_EXTENSION_EPOINT_FORMAT) => Some(PointCloudFormat::Epoint),
             s if s.ends_with(FILE_EXTENSION_EPOINT_TAR_FORMAT) => Some(PointCloudFormat::EpointTar),
             s if s.ends_with(FILE_EXTENSION_E57_FORMAT) => Some(PointCloudFormat::E57),
             s if s.ends_with(FILE_EXTENSION_LAS_FORMAT) => Some(PointCloudFormat::Las),
             s if s.ends_with(FILE_EXTENSION_LAZ_FORMAT) => Some(PointCloudFormat::Laz),
+            s if s.ends_with(FILE_EXTENSION_PCD_FORMAT) => Some(PointCloudFormat::Pcd),
+            s if s.ends_with(FILE_EXTENSION_PLY_FORMAT) => Some(PointCloudFormat::Ply),
             s if s.ends_with(FILE_EXTENSION_XYZ_FORMAT) => Some(PointCloudFormat::Xyz),
             s if s.ends_with(FILE_EXTENSION_XYZ_ZST_FORMAT) => Some(PointCloudFormat::XyzZst),
             _ => None,
@@ -36,19 +45,22 @@ impl PointCloudFormat {
         match self {
             PointCloudFormat::Epoint => FILE_EXTENSION_EPOINT_FORMAT,
             PointCloudFormat::EpointTar => FILE_EXTENSION_EPOINT_TAR_FORMAT,
+            PointCloudFormat::EpointDirectory => FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT,
             PointCloudFormat::E57 => FILE_EXTENSION_E57_FORMAT,
             PointCloudFormat::Las => FILE_EXTENSION_LAS_FORMAT,
             PointCloudFormat::Laz => FILE_EXTENSION_LAZ_FORMAT,
+            PointCloudFormat::Pcd => FILE_EXTENSION_PCD_FORMAT,
+            PointCloudFormat::Ply => FILE_EXTENSION_PLY_FORMAT,
             PointCloudFormat::Xyz => FILE_EXTENSION_XYZ_FORMAT,
             PointCloudFormat::XyzZst => FILE_EXTENSION_XYZ_ZST_FORMAT,
         }
     }
 
     pub fn is_supported_point_cloud_format(path: impl AsRef<Path>) -> bool {
-        if !path.as_ref().is_file() {
-            return false;
+        match PointCloudFormat::from_path(&path) {
+            Some(PointCloudFormat::EpointDirectory) => path.as_ref().is_dir(),
+            Some(_) => path.as_ref().is_file(),
+            None => false,
         }
-
-        PointCloudFormat::from_path(&path).is_some()
     }
 }
@@ -0,0 +1,186 @@
+use crate::Error;
+use crate::Error::UnsupportedCrs;
+
+const PROJECTION_VLR_USER_ID: &str = "LASF_Projection";
+const GEO_KEY_DIRECTORY_VLR_RECORD_ID: u16 = 34735;
+const WKT_VLR_RECORD_ID: u16 = 2112;
+
+const GT_MODEL_TYPE_GEO_KEY: u16 = 1024;
+const GEOGRAPHIC_TYPE_GEO_KEY: u16 = 2048;
+const PROJECTED_CS_TYPE_GEO_KEY: u16 = 3072;
+const MODEL_TYPE_PROJECTED: u16 = 1;
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+const MODEL_TYPE_GEOCENTRIC: u16 = 3;
+const USER_DEFINED_GEO_KEY_VALUE: u16 = 32767;
+
+/// Keywords of the WKT elements that define a compound coordinate reference system.
+const WKT_COMPOUND_CRS_KEYWORDS: [&str; 2] = ["COMPD_CS", "COMPOUNDCRS"];
+/// EPSG codes of common geocentric systems (WGS 84, ETRS89, ITRF88 to ITRF2005), which are within
+/// the 4000-4999 range of geographic systems.
+const GEOCENTRIC_CRS_EPSG_CODES: [u32; 13] = [
+    4896, 4910, 4911, 4912, 4913, 4914, 4915, 4916, 4917, 4918, 4919, 4936, 4978,
+];
+/// Keywords of the WKT elements that define a horizontal coordinate reference system.
+const WKT_HORIZONTAL_CRS_KEYWORDS: [&str; 5] =
+    ["PROJCS", "GEOGCS", "PROJCRS", "GEOGCRS", "GEODCRS"];
+
+/// Derives the EPSG code and the OGC WKT definition of the coordinate reference system defined in
+/// the (E)VLRs.
+///
+/// The OGC WKT definition takes precedence over the GeoTIFF keys. For compound systems without an
+/// own code, the code of the horizontal system is returned.
+pub fn read_crs(header: &las::Header) -> Result<(Option<u32>, Option<String>), Error> {
+    if let Some(wkt_bytes) = header.get_wkt_crs_bytes() {
+        let wkt = String::from_utf8_lossy(wkt_bytes)
+            .trim_end_matches('\0')
+            .to_string();
+        if let Some(epsg) = parse_wkt_epsg(&wkt) {
+            return Ok((Some(epsg), Some(wkt)));
+        }
+    }
+
+    let Some(geotiff_crs) = header.get_geotiff_crs()? else {
+        return Ok((None, None));
+    };
+    let epsg = geotiff_crs
+        .get_projected_crs_geo_key_value()
+        .filter(|v| *v != USER_DEFINED_GEO_KEY_VALUE)
+        .or(geotiff_crs
+            .get_geodetic_crs_geo_key_value()
+            .filter(|v| *v != USER_DEFINED_GEO_KEY_VALUE))
+        .map(|v| v as u32);
+
+    Ok((epsg, None))
+}
+
+/// Returns the GeoTIFF model type of the system with the EPSG code.
+///
+/// Codes in the range 4000-4999 are considered as geographic systems, except for the known
+/// geocentric systems, and all others as projected systems.
+fn model_type(epsg: u32) -> u16 {
+    if GEOCENTRIC_CRS_EPSG_CODES.contains(&epsg) {
+        MODEL_TYPE_GEOCENTRIC
+    } else if (4000..5000).contains(&epsg) {
+        MODEL_TYPE_GEOGRAPHIC
+    } else {
+        MODEL_TYPE_PROJECTED
+    }
+}
+
+/// Creates a GeoKeyDirectory VLR referencing the EPSG code, which is used by the point formats
+/// 0-5.
+///
+/// Codes above 65535 cannot be represented by GeoTIFF keys.
+pub fn create_geo_key_directory_vlr(epsg: u32) -> Result<las::Vlr, Error> {
+    let model_type = model_type(epsg);
+    let crs_geo_key = if model_type == MODEL_TYPE_PROJECTED {
+        PROJECTED_CS_TYPE_GEO_KEY
+    } else {
+        GEOGRAPHIC_TYPE_GEO_KEY
+    };
+    let epsg = u16::try_from(epsg)
+        .ok()
+        .filter(|v| *v != USER_DEFINED_GEO_KEY_VALUE)
+        .ok_or(UnsupportedCrs(epsg))?;
+
+    // header: key directory version, key revision, minor revision, number of keys
+    // entries: key id, tag location (0 for values stored in the entry), count, value
+    let values: [u16; 12] = [
+        1,
+        1,
+        0,
+        2,
+        GT_MODEL_TYPE_GEO_KEY,
+        0,
+        1,
+        model_type,
+        crs_geo_key,
+        0,
+        1,
+        epsg,
+    ];
+
+    Ok(las::Vlr {
+        user_id: PROJECTION_VLR_USER_ID.to_string(),
+        record_id: GEO_KEY_DIRECTORY_VLR_RECORD_ID,
+        description: "GeoTIFF GeoKeyDirectoryTag".to_string(),
+        data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+    })
+}
+
+/// Creates an OGC WKT VLR, which is required by the point formats 6-10.
+pub fn create_wkt_vlr(wkt: &str) -> las::Vlr {
+    let mut data = wkt.as_bytes().to_vec();
+    data.push(0);
+
+    las::Vlr {
+        user_id: PROJECTION_VLR_USER_ID.to_string(),
+        record_id: WKT_VLR_RECORD_ID,
+        description: "OGC Coordinate System WKT".to_string(),
+        data,
+    }
+}
+
+/// Parses the EPSG code of the outermost coordinate reference system of a WKT1 or WKT2 string.
+pub fn parse_wkt_epsg(wkt: &str) -> Option<u32> {
+    let (keyword, content) = split_wkt_element(wkt.trim())?;
+    let children = split_wkt_children(content);
+
+    let authority_epsg = children.iter().find_map(|c| {
+        let (keyword, content) = split_wkt_element(c)?;
+        if keyword != "AUTHORITY" && keyword != "ID" {
+            return None;
+        }
+        let mut values = content.split(',').map(|v| v.trim().trim_matches('"'));
+        if !values.next()?.eq_ignore_ascii_case("EPSG") {
+            return None;
+        }
+        values.next()?.parse::<u32>().ok()
+    });
+
+    if authority_epsg.is_some() || !WKT_COMPOUND_CRS_KEYWORDS.contains(&keyword) {
+        return authority_epsg;
+    }
+    children.iter().find_map(|c| {
+        let (child_keyword, _) = split_wkt_element(c)?;
+        WKT_HORIZONTAL_CRS_KEYWORDS
+            .contains(&child_keyword)
+            .then(|| parse_wkt_epsg(c))
+            .flatten()
+    })
+}
+
+/// Splits a WKT element into its keyword and the content within the outer brackets.
+fn split_wkt_element(element: &str) -> Option<(&str, &str)> {
+    let start = element.find(['[', '('])?;
+    let end = element.rfind([']', ')'])?;
+    if end <= start {
+        return None;
+    }
+
+    Some((element[..start].trim(), &element[start + 1..end]))
+}
+
+/// Splits the content of a WKT element at the commas on the top level.
+fn split_wkt_children(content: &str) -> Vec<&str> {
+    let mut children: Vec<&str> = Vec::new();
+    let mut depth = 0;
+    let mut is_quoted = false;
+    let mut start = 0;
+
+    for (i, c) in content.char_indices() {
+        match c {
+            '"' => is_quoted = !is_quoted,
+            '[' | '(' if !is_quoted => depth += 1,
+            ']' | ')' if !is_quoted => depth -= 1,
+            ',' if !is_quoted && depth == 0 => {
+                children.push(content[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    children.push(content[start..].trim());
+
+    children
+}
@@ -0,0 +1,301 @@
+use crate::Error;
+use polars::prelude::{Column, DataType};
+use tracing::warn;
+
+pub const EXTRA_BYTES_VLR_USER_ID: &str = "LASF_Spec";
+pub const EXTRA_BYTES_VLR_RECORD_ID: u16 = 4;
+const EXTRA_BYTES_VLR_DESCRIPTION: &str = "Extra Bytes Record";
+
+const DESCRIPTOR_SIZE: usize = 192;
+const DESCRIPTOR_NAME_SIZE: usize = 32;
+const DESCRIPTOR_OPTION_SCALE: u8 = 1 << 3;
+const DESCRIPTOR_OPTION_OFFSET: u8 = 1 << 4;
+
+/// Data type of an extra bytes attribute as defined by the LAS 1.4 specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraBytesDataType {
+    /// Bytes without a documented meaning, with the number of bytes.
+    Undocumented(u8),
+    UInt8,
+    Int8,
+    UInt16,
+    Int16,
+    UInt32,
+    Int32,
+    UInt64,
+    Int64,
+    Float32,
+    Float64,
+}
+
+impl ExtraBytesDataType {
+    fn from_code(code: u8, options: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Undocumented(options)),
+            1 => Some(Self::UInt8),
+            2 => Some(Self::Int8),
+            3 => Some(Self::UInt16),
+            4 => Some(Self::Int16),
+            5 => Some(Self::UInt32),
+            6 => Some(Self::Int32),
+            7 => Some(Self::UInt64),
+            8 => Some(Self::Int64),
+            9 => Some(Self::Float32),
+            10 => Some(Self::Float64),
+            // deprecated array types
+            _ => None,
+        }
+    }
+
+    fn code(&self) -> u8 {
+        match self {
+            Self::Undocumented(_) => 0,
+            Self::UInt8 => 1,
+            Self::Int8 => 2,
+            Self::UInt16 => 3,
+            Self::Int16 => 4,
+            Self::UInt32 => 5,
+            Self::Int32 => 6,
+            Self::UInt64 => 7,
+            Self::Int64 => 8,
+            Self::Float32 => 9,
+            Self::Float64 => 10,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Undocumented(size) => *size as usize,
+            Self::UInt8 | Self::Int8 => 1,
+            Self::UInt16 | Self::Int16 => 2,
+            Self::UInt32 | Self::Int32 | Self::Float32 => 4,
+            Self::UInt64 | Self::Int64 | Self::Float64 => 8,
+        }
+    }
+
+    fn from_data_type(data_type: &DataType) -> Option<Self> {
+        match data_type {
+            DataType::Boolean | DataType::UInt8 => Some(Self::UInt8),
+            DataType::Int8 => Some(Self::Int8),
+            DataType::UInt16 => Some(Self::UInt16),
+            DataType::Int16 => Some(Self::Int16),
+            DataType::UInt32 => Some(Self::UInt32),
+            DataType::Int32 => Some(Self::Int32),
+            DataType::UInt64 => Some(Self::UInt64),
+            DataType::Int64 => Some(Self::Int64),
+            DataType::Float32 => Some(Self::Float32),
+            DataType::Float64 => Some(Self::Float64),
+            _ => None,
+        }
+    }
+
+    fn data_type(&self) -> Option<DataType> {
+        match self {
+            Self::Undocumented(_) => None,
+            Self::UInt8 => Some(DataType::UInt8),
+            Self::Int8 => Some(DataType::Int8),
+            Self::UInt16 => Some(DataType::UInt16),
+            Self::Int16 => Some(DataType::Int16),
+            Self::UInt32 => Some(DataType::UInt32),
+            Self::Int32 => Some(DataType::Int32),
+            Self::UInt64 => Some(DataType::UInt64),
+            Self::Int64 => Some(DataType::Int64),
+            Self::Float32 => Some(DataType::Float32),
+            Self::Float64 => Some(DataType::Float64),
+        }
+    }
+}
+
+/// Description of a single extra bytes attribute stored after the standard point record fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtraBytesDescriptor {
+    pub name: String,
+    pub data_type: ExtraBytesDataType,
+    pub scale: Option<f64>,
+    pub offset: Option<f64>,
+}
+
+impl ExtraBytesDescriptor {
+    /// Parses the descriptors of an extra bytes VLR.
+    ///
+    /// Descriptors of deprecated array types are returned as undocumented bytes of the same size.
+    pub fn parse_vlr(vlr: &las::Vlr) -> Vec<Self> {
+        vlr.data
+            .chunks_exact(DESCRIPTOR_SIZE)
+            .map(|d| {
+                let options = d[3];
+                let data_type = ExtraBytesDataType::from_code(d[2], options).unwrap_or(
+                    ExtraBytesDataType::Undocumented(deprecated_type_size(d[2]) as u8),
+                );
+                let name = String::from_utf8_lossy(&d[4..4 + DESCRIPTOR_NAME_SIZE])
+                    .trim_end_matches('\0')
+                    .to_string();
+                let scale = (options & DESCRIPTOR_OPTION_SCALE != 0)
+                    .then(|| f64::from_le_bytes(d[112..120].try_into().unwrap()));
+                let offset = (options & DESCRIPTOR_OPTION_OFFSET != 0)
+                    .then(|| f64::from_le_bytes(d[136..144].try_into().unwrap()));
+
+                Self {
+                    name,
+                    data_type,
+                    scale,
+                    offset,
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_extra_bytes_vlr(vlr: &las::Vlr) -> bool {
+        vlr.user_id == EXTRA_BYTES_VLR_USER_ID && vlr.record_id == EXTRA_BYTES_VLR_RECORD_ID
+    }
+
+    /// Creates the extra bytes VLR describing the descriptors.
+    pub fn create_vlr(descriptors: &[Self]) -> las::Vlr {
+        let mut data: Vec<u8> = Vec::with_capacity(descriptors.len() * DESCRIPTOR_SIZE);
+        for current_descriptor in descriptors {
+            let mut descriptor_data = [0u8; DESCRIPTOR_SIZE];
+            descriptor_data[2] = current_descriptor.data_type.code();
+            if let ExtraBytesDataType::Undocumented(size) = current_descriptor.data_type {
+                descriptor_data[3] = size;
+            }
+            let name = current_descriptor.name.as_bytes();
+            descriptor_data[4..4 + name.len()].copy_from_slice(name);
+            data.extend_from_slice(&descriptor_data);
+        }
+
+        las::Vlr {
+            user_id: EXTRA_BYTES_VLR_USER_ID.to_string(),
+            record_id: EXTRA_BYTES_VLR_RECORD_ID,
+            description: EXTRA_BYTES_VLR_DESCRIPTION.to_string(),
+            data,
+        }
+    }
+
+    /// Creates a descriptor for a data frame column, if the column type can be stored as extra
+    /// bytes.
+    ///
+    /// Boolean columns are stored as `u8` values. Columns with names exceeding the 32 bytes of a
+    /// descriptor are skipped.
+    pub fn from_column(column: &Column) -> Option<Self> {
+        let data_type = ExtraBytesDataType::from_data_type(column.dtype())?;
+        if column.name().len() > DESCRIPTOR_NAME_SIZE {
+            warn!(
+                "column `{}` is not written as extra bytes, since its name exceeds {} bytes",
+                column.name(),
+                DESCRIPTOR_NAME_SIZE
+            );
+            return None;
+        }
+
+        Some(Self {
+            name: column.name().to_string(),
+            data_type,
+            scale: None,
+            offset: None,
+        })
+    }
+
+    /// Creates the data frame column from the extra bytes of all points.
+    ///
+    /// Returns `None` for undocumented bytes.
+    pub fn read_column(&self, las_points: &[las::Point], byte_offset: usize) -> Option<Column> {
+        let size = self.data_type.size();
+        let bytes: Vec<&[u8]> = las_points
+            .iter()
+            .map(|p| &p.extra_bytes[byte_offset..byte_offset + size])
+            .collect();
+        let name = self.name.as_str().into();
+
+        if self.scale.is_some() || self.offset.is_some() {
+            let scale = self.scale.unwrap_or(1.0);
+            let offset = self.offset.unwrap_or(0.0);
+            let values: Vec<f64> = bytes
+                .iter()
+                .map(|b| self.decode_f64(b) * scale + offset)
+                .collect();
+            return Some(Column::new(name, values));
+        }
+
+        let column = match self.data_type {
+            ExtraBytesDataType::Undocumented(_) => return None,
+            ExtraBytesDataType::UInt8 => Column::new(name, decode(&bytes, u8::from_le_bytes)),
+            ExtraBytesDataType::Int8 => Column::new(name, decode(&bytes, i8::from_le_bytes)),
+            ExtraBytesDataType::UInt16 => Column::new(name, decode(&bytes, u16::from_le_bytes)),
+            ExtraBytesDataType::Int16 => Column::new(name, decode(&bytes, i16::from_le_bytes)),
+            ExtraBytesDataType::UInt32 => Column::new(name, decode(&bytes, u32::from_le_bytes)),
+            ExtraBytesDataType::Int32 => Column::new(name, decode(&bytes, i32::from_le_bytes)),
+            ExtraBytesDataType::UInt64 => Column::new(name, decode(&bytes, u64::from_le_bytes)),
+            ExtraBytesDataType::Int64 => Column::new(name, decode(&bytes, i64::from_le_bytes)),
+            ExtraBytesDataType::Float32 => Column::new(name, decode(&bytes, f32::from_le_bytes)),
+            ExtraBytesDataType::Float64 => Column::new(name, decode(&bytes, f64::from_le_bytes)),
+        };
+        Some(column)
+    }
+
+    fn decode_f64(&self, bytes: &[u8]) -> f64 {
+        match self.data_type {
+            ExtraBytesDataType::Undocumented(_) => f64::NAN,
+            ExtraBytesDataType::UInt8 => bytes[0] as f64,
+            ExtraBytesDataType::Int8 => bytes[0] as i8 as f64,
+            ExtraBytesDataType::UInt16 => u16::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::Int16 => i16::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::UInt32 => u32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::Int32 => i32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::UInt64 => u64::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::Int64 => i64::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::Float32 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            ExtraBytesDataType::Float64 => f64::from_le_bytes(bytes.try_into().unwrap()),
+        }
+    }
+}
+
+/// Encodes the values of a column as little endian bytes of the descriptor's data type.
+pub fn encode_column(
+    column: &Column,
+    data_type: ExtraBytesDataType,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let target_data_type = data_type
+        .data_type()
+        .expect("descriptors of columns must be documented");
+    let column = column.cast(&target_data_type)?;
+
+    let values: Vec<Vec<u8>> = match data_type {
+        ExtraBytesDataType::Undocumented(_) => unreachable!(),
+        ExtraBytesDataType::UInt8 => encode(column.u8()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::Int8 => encode(column.i8()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::UInt16 => encode(column.u16()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::Int16 => encode(column.i16()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::UInt32 => encode(column.u32()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::Int32 => encode(column.i32()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::UInt64 => encode(column.u64()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::Int64 => encode(column.i64()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::Float32 => encode(column.f32()?.iter(), |v| v.to_le_bytes().to_vec()),
+        ExtraBytesDataType::Float64 => encode(column.f64()?.iter(), |v| v.to_le_bytes().to_vec()),
+    };
+    Ok(values)
+}
+
+fn encode<T: Default>(
+    values: impl Iterator<Item = Option<T>>,
+    to_bytes: impl Fn(T) -> Vec<u8>,
+) -> Vec<Vec<u8>> {
+    values.map(|v| to_bytes(v.unwrap_or_default())).collect()
+}
+
+fn decode<const N: usize, T>(bytes: &[&[u8]], from_le_bytes: fn([u8; N]) -> T) -> Vec<T> {
+    bytes
+        .iter()
+        .map(|b| from_le_bytes((*b).try_into().expect("size must match")))
+        .collect()
+}
+
+/// Size in bytes of the deprecated array data types 11-30.
+fn deprecated_type_size(code: u8) -> usize {
+    if !(11..=30).contains(&code) {
+        return 0;
+    }
+    let element_count = if code <= 20 { 2 } else { 3 };
+    let element_code = (code - 11) % 10 + 1;
+    let element_size = ExtraBytesDataType::from_code(element_code, 0).map_or(0, |t| t.size());
+    element_count * element_size
+}
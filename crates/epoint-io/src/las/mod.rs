@@ -1,14 +1,23 @@
 use crate::Error;
 use crate::Error::InvalidVersion;
 
+pub(crate) mod crs;
+pub(crate) mod extra_bytes;
 pub mod read;
-mod read_impl;
+pub(crate) mod read_impl;
 pub mod write;
-mod write_impl;
+pub(crate) mod write_impl;
 
 pub const FILE_EXTENSION_LAS_FORMAT: &str = "las";
 pub const FILE_EXTENSION_LAZ_FORMAT: &str = "laz";
 
+/// Key of the point cloud info metadata holding the OGC WKT definition of the coordinate
+/// reference system.
+///
+/// It is set by `LasReader` and `CopcReader` and written by `LasWriter` and `CopcWriter`, if the
+/// EPSG code of the WKT matches the one of the frame info.
+pub const METADATA_KEY_CRS_WKT: &str = "crs_wkt";
+
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
 pub enum LasVersion {
     /// LAS version 1.0 released 2003 by ASPRS.
@@ -34,6 +43,29 @@ impl LasVersion {
             _ => Err(InvalidVersion { major, minor }),
         }
     }
+
+    pub fn major_minor(&self) -> (u8, u8) {
+        match self {
+            Self::V1_0 => (1, 0),
+            Self::V1_1 => (1, 1),
+            Self::V1_2 => (1, 2),
+            Self::V1_3 => (1, 3),
+            Self::V1_4 => (1, 4),
+        }
+    }
+}
+
+/// Conversion of the `f32` intensity values to the `u16` intensity values of LAS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LasIntensityScaling {
+    /// Values are rounded and clamped to the `u16` range, which keeps intensities imported by
+    /// `LasReader` unchanged.
+    #[default]
+    Raw,
+    /// Values normalized to `[0, 1]` are scaled to the full `u16` range.
+    Normalized,
+    /// The range between the minimum and maximum value is stretched to the full `u16` range.
+    MinMax,
 }
 
 /// GPS epoch reference timestamp (Unix time).
@@ -59,7 +91,7 @@ impl LasVersion {
 ///
 /// # Reference
 /// [GPS Time System](https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping)
-const GPS_EPOCH_REFERENCE_TIMESTAMP: i64 = 315964800;
+pub(crate) const GPS_EPOCH_REFERENCE_TIMESTAMP: i64 = 315964800;
 
 // Adjusted GPS time offset in seconds (see: https://groups.google.com/g/lastools/c/_9TxnjoghGM)
 const ADJUSTED_GPS_TIME_OFFSET: i64 = 1_000_000_000;
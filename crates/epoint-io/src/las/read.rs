@@ -1,12 +1,14 @@
-use crate::las::read_impl::import_point_cloud_from_las_reader;
-use crate::{Error, FILE_EXTENSION_LAS_FORMAT, FILE_EXTENSION_LAZ_FORMAT};
+use crate::las::read_impl::{LasReadFilter, import_point_cloud_from_las_reader};
+use crate::{Error, FILE_EXTENSION_LAS_FORMAT, FILE_EXTENSION_LAZ_FORMAT, METADATA_KEY_CRS_WKT};
 
 use crate::las::LasVersion;
 use epoint_core::PointCloud;
 
 use crate::Error::{InvalidFileExtension, NoFileExtension};
-use ecoord::FrameId;
+use chrono::{DateTime, Utc};
 use ecoord::io::EcoordReader;
+use ecoord::{AxisAlignedBoundingBox, FrameId, FrameInfo};
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fs::File;
 use std::io::{Read, Seek};
@@ -14,6 +16,15 @@ use std::path::Path;
 
 /// `LasReader` imports a point cloud from a LAS or LAZ file.
 ///
+/// Extra bytes described by an extra bytes VLR are imported as columns named after the
+/// descriptors. Scaled or offset values are imported as `f64` columns.
+///
+/// The EPSG code of the coordinate reference system, given by the OGC WKT or the GeoTIFF
+/// GeoKeyDirectory VLR, is stored in the frame info of the reference frame. The OGC WKT definition
+/// is stored in the point cloud info metadata under [`METADATA_KEY_CRS_WKT`].
+///
+/// Bounds, a time range, classifications and a column projection are applied to each chunk while
+/// reading, so that only the selected points and columns are held in memory.
 #[derive(Debug, Clone)]
 pub struct LasReader<R: Read + Seek + Send + Sync + Debug> {
     reader: R,
@@ -21,6 +32,7 @@ pub struct LasReader<R: Read + Seek + Send + Sync + Debug> {
     normalize_colors: bool,
     reference_frame_id: FrameId,
     points_per_chunk: Option<u64>,
+    filter: LasReadFilter,
 }
 
 impl<R: Read + Seek + Send + Sync + 'static + Debug> LasReader<R> {
@@ -31,7 +43,8 @@ impl<R: Read + Seek + Send + Sync + 'static + Debug> LasReader<R> {
             sidecar_ecoord_reader: None,
             normalize_colors: false,
             reference_frame_id: FrameId::global(),
-            points_per_chunk: Some(100_000_000),
+            points_per_chunk: Some(1_000_000),
+            filter: LasReadFilter::default(),
         }
     }
 
@@ -50,12 +63,37 @@ impl<R: Read + Seek + Send + Sync + 'static + Debug> LasReader<R> {
         self
     }
 
+    /// Only reads points within the bounds, including points on the boundary.
+    pub fn with_bounds(mut self, bounds: Option<AxisAlignedBoundingBox>) -> Self {
+        self.filter.bounds = bounds;
+        self
+    }
+
+    /// Only reads points with timestamps within the closed range of start and end time.
+    pub fn with_time_range(mut self, time_range: Option<(DateTime<Utc>, DateTime<Utc>)>) -> Self {
+        self.filter.time_range = time_range;
+        self
+    }
+
+    /// Only reads points with one of the classifications.
+    pub fn with_classifications(mut self, classifications: Option<HashSet<u8>>) -> Self {
+        self.filter.classifications = classifications;
+        self
+    }
+
+    /// Only reads the columns with the names, while the `x`, `y` and `z` columns are always read.
+    pub fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.filter.columns = columns;
+        self
+    }
+
     pub fn finish(self) -> Result<(PointCloud, LasReadInfo), Error> {
         let (mut point_cloud, read_info) = import_point_cloud_from_las_reader(
             self.reader,
             self.normalize_colors,
-            self.reference_frame_id,
+            self.reference_frame_id.clone(),
             self.points_per_chunk,
+            &self.filter,
         )?;
 
         if let Some(reader) = self.sidecar_ecoord_reader {
@@ -63,6 +101,25 @@ impl<R: Read + Seek + Send + Sync + 'static + Debug> LasReader<R> {
             point_cloud.append_transform_tree(transform_tree)?;
         }
 
+        // a code defined in the sidecar ecoord file takes precedence
+        if let Some(crs_epsg) = read_info.crs_epsg {
+            let frame_info = point_cloud
+                .transform_tree
+                .frames
+                .entry(self.reference_frame_id.clone())
+                .or_insert(FrameInfo::new(self.reference_frame_id, None, None));
+            frame_info.crs_epsg.get_or_insert(crs_epsg);
+
+            if let Some(crs_wkt) = &read_info.crs_wkt
+                && frame_info.crs_epsg == Some(crs_epsg)
+            {
+                point_cloud
+                    .info
+                    .metadata
+                    .insert(METADATA_KEY_CRS_WKT.to_string(), crs_wkt.clone());
+            }
+        }
+
         Ok((point_cloud, read_info))
     }
 }
@@ -92,4 +149,8 @@ impl LasReader<File> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LasReadInfo {
     pub version: LasVersion,
+    /// EPSG code of the coordinate reference system defined in the (E)VLRs.
+    pub crs_epsg: Option<u32>,
+    /// OGC WKT definition of the coordinate reference system defined in the (E)VLRs.
+    pub crs_wkt: Option<String>,
 }
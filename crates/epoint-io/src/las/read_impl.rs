@@ -1,19 +1,111 @@
 use crate::Error;
 use crate::Error::InvalidVersion;
+use crate::epoint::read_impl::cast_data_frame;
 use crate::las::GPS_EPOCH_REFERENCE_TIMESTAMP;
+use crate::las::crs::read_crs;
+use crate::las::extra_bytes::ExtraBytesDescriptor;
 use crate::las::read::LasReadInfo;
 use crate::las::{ADJUSTED_GPS_TIME_OFFSET, LasVersion};
 use epoint_core::{PointCloud, PointCloudInfo, PointDataColumnType};
+use las::point::ScanDirection;
 use las::{GpsTimeType, Version};
 
-use ecoord::FrameId;
+use chrono::{DateTime, Utc};
+use ecoord::{AxisAlignedBoundingBox, FrameId};
 use epoint_core::Error::NoRemainingPoints;
 use polars::prelude::DataFrame;
 use polars::prelude::*;
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::io::{BufReader, Seek};
 
+/// Conditions on the points and columns, which are applied to each chunk while reading.
+#[derive(Debug, Clone, Default)]
+pub struct LasReadFilter {
+    pub bounds: Option<AxisAlignedBoundingBox>,
+    pub time_range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    pub classifications: Option<HashSet<u8>>,
+    pub columns: Option<Vec<String>>,
+}
+
+impl LasReadFilter {
+    /// Returns false, if no point within the header's bounds can match.
+    fn may_match(&self, header: &las::Header) -> bool {
+        let Some(bounds) = &self.bounds else {
+            return true;
+        };
+        let header_bounds = header.bounds();
+        let (lower_bound, upper_bound) = (bounds.lower_bound(), bounds.upper_bound());
+
+        header_bounds.min.x <= upper_bound.x
+            && header_bounds.max.x >= lower_bound.x
+            && header_bounds.min.y <= upper_bound.y
+            && header_bounds.max.y >= lower_bound.y
+            && header_bounds.min.z <= upper_bound.z
+            && header_bounds.max.z >= lower_bound.z
+    }
+
+    pub fn matches(&self, point: &las::Point, gps_time_type: GpsTimeType) -> bool {
+        if let Some(bounds) = &self.bounds {
+            let (lower_bound, upper_bound) = (bounds.lower_bound(), bounds.upper_bound());
+            if point.x < lower_bound.x
+                || point.x > upper_bound.x
+                || point.y < lower_bound.y
+                || point.y > upper_bound.y
+                || point.z < lower_bound.z
+                || point.z > upper_bound.z
+            {
+                return false;
+            }
+        }
+
+        if let Some(classifications) = &self.classifications
+            && !classifications.contains(&u8::from(point.classification))
+        {
+            return false;
+        }
+
+        if let Some((start, end)) = &self.time_range {
+            let Some(gps_time) = point.gps_time else {
+                return false;
+            };
+            let timestamp = normalize_timestamp(gps_time, gps_time_type);
+            if timestamp < (start.timestamp(), start.timestamp_subsec_nanos())
+                || timestamp > (end.timestamp(), end.timestamp_subsec_nanos())
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Selects the projected columns, while the coordinates are always kept.
+    fn select_columns(&self, data_frame: DataFrame) -> Result<DataFrame, Error> {
+        let Some(columns) = &self.columns else {
+            return Ok(data_frame);
+        };
+
+        let selected_columns: Vec<PlSmallStr> = data_frame
+            .get_column_names()
+            .into_iter()
+            .filter(|c| {
+                [
+                    PointDataColumnType::X,
+                    PointDataColumnType::Y,
+                    PointDataColumnType::Z,
+                ]
+                .iter()
+                .any(|t| t.as_str() == c.as_str())
+                    || columns.iter().any(|s| s == c.as_str())
+            })
+            .cloned()
+            .collect();
+        Ok(data_frame.select(selected_columns)?)
+    }
+}
+
 pub fn import_point_cloud_from_las_reader<
     R: std::io::Read + Seek + Send + Sync + 'static + Debug,
 >(
@@ -21,36 +113,73 @@ pub fn import_point_cloud_from_las_reader<
     normalize_colors: bool,
     reference_frame_id: FrameId,
     points_per_chunk: Option<u64>,
+    filter: &LasReadFilter,
 ) -> Result<(PointCloud, LasReadInfo), Error> {
     let mut las_reader = las::Reader::new(BufReader::new(reader))?;
+    if !filter.may_match(las_reader.header()) {
+        return Err(NoRemainingPoints.into());
+    }
     let gps_time_type = las_reader.header().gps_time_type();
+    let point_format = *las_reader.header().point_format();
+    let extra_bytes_descriptors: Vec<ExtraBytesDescriptor> = las_reader
+        .header()
+        .all_vlrs()
+        .filter(|v| ExtraBytesDescriptor::is_extra_bytes_vlr(v))
+        .flat_map(ExtraBytesDescriptor::parse_vlr)
+        .collect();
 
     let mut point_data_data_frames: Vec<LazyFrame> = Vec::new();
 
     let point_data = if let Some(points_per_chunk) = points_per_chunk {
+        // the chunk buffer is preallocated, so it must not exceed the number of points
+        let points_per_chunk = points_per_chunk.min(las_reader.header().number_of_points().max(1));
         loop {
-            let las_points = las_reader.read_points(points_per_chunk)?;
+            let mut las_points = las_reader.read_points(points_per_chunk)?;
             if las_points.is_empty() {
                 break;
             }
+            las_points.retain(|p| filter.matches(p, gps_time_type));
+            if las_points.is_empty() {
+                continue;
+            }
 
-            let current_data_frame =
-                convert_to_data_frame(las_points, normalize_colors, gps_time_type)?.lazy();
-            point_data_data_frames.push(current_data_frame);
+            let current_data_frame = convert_to_data_frame(
+                las_points,
+                gps_time_type,
+                &point_format,
+                &extra_bytes_descriptors,
+            )?;
+            point_data_data_frames.push(filter.select_columns(current_data_frame)?.lazy());
         }
-        concat(point_data_data_frames, Default::default())?.collect()?
+        if point_data_data_frames.is_empty() {
+            return Err(NoRemainingPoints.into());
+        }
+        let data_frame = concat(point_data_data_frames, Default::default())?.collect()?;
+        finish_data_frame(data_frame, normalize_colors)?
     } else {
         let mut las_points: Vec<las::Point> = Vec::new();
         las_reader.read_all_points_into(&mut las_points)?;
+        las_points.retain(|p| filter.matches(p, gps_time_type));
 
-        convert_to_data_frame(las_points, normalize_colors, gps_time_type)?
+        let data_frame = convert_to_data_frame(
+            las_points,
+            gps_time_type,
+            &point_format,
+            &extra_bytes_descriptors,
+        )?;
+        finish_data_frame(filter.select_columns(data_frame)?, normalize_colors)?
     };
 
     let info = PointCloudInfo::new(Some(reference_frame_id));
     let point_cloud = PointCloud::from_data_frame(point_data, info, Default::default())?;
 
     let version = get_version(&las_reader)?;
-    let las_read_info = LasReadInfo { version };
+    let (crs_epsg, crs_wkt) = read_crs(las_reader.header())?;
+    let las_read_info = LasReadInfo {
+        version,
+        crs_epsg,
+        crs_wkt,
+    };
 
     Ok((point_cloud, las_read_info))
 }
@@ -72,10 +201,16 @@ fn get_version(las_reader: &las::Reader) -> Result<LasVersion, Error> {
     }
 }
 
-fn convert_to_data_frame(
+/// Converts the points of a chunk to a data frame.
+///
+/// The columns are derived from the point format only, so that the data frames of all chunks
+/// have the same schema. The point source IDs are always included and the colors are not
+/// normalized, which is done once for all chunks by [`finish_data_frame`].
+pub fn convert_to_data_frame(
     las_points: Vec<las::Point>,
-    normalize_colors: bool,
     gps_time_type: GpsTimeType,
+    point_format: &las::point::Format,
+    extra_bytes_descriptors: &[ExtraBytesDescriptor],
 ) -> Result<DataFrame, Error> {
     if las_points.is_empty() {
         return Err(NoRemainingPoints.into());
@@ -103,10 +238,10 @@ fn convert_to_data_frame(
         ),
     ];
 
-    if las_points.iter().all(|p| p.gps_time.is_some()) {
+    if point_format.has_gps_time {
         let (seconds, nanos): (Vec<i64>, Vec<u32>) = las_points
             .par_iter()
-            .map(|p| normalize_timestamp(p.gps_time.unwrap(), gps_time_type))
+            .map(|p| normalize_timestamp(p.gps_time.unwrap_or_default(), gps_time_type))
             .unzip();
 
         point_data_columns.push(Column::new(
@@ -119,32 +254,20 @@ fn convert_to_data_frame(
         ));
     }
 
-    if las_points.iter().any(|p| p.point_source_id != 0) {
-        point_data_columns.push(Column::new(
-            PointDataColumnType::PointSourceId.into(),
-            las_points
-                .par_iter()
-                .map(|p| p.point_source_id)
-                .collect::<Vec<u16>>(),
-        ));
-    }
-
-    if las_points.iter().all(|p| p.color.is_some()) {
-        // check if normalization needed
-        let normalization_factor = if normalize_colors
-            && las_points.iter().map(|p| p.color.unwrap()).all(|c| {
-                c.red <= u8::MAX as u16 && c.green <= u8::MAX as u16 && c.blue <= u8::MAX as u16
-            }) {
-            256
-        } else {
-            1
-        };
+    point_data_columns.push(Column::new(
+        PointDataColumnType::PointSourceId.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.point_source_id)
+            .collect::<Vec<u16>>(),
+    ));
 
+    if point_format.has_color {
         let color_red_column = Column::new(
             PointDataColumnType::ColorRed.into(),
             las_points
                 .par_iter()
-                .map(|p| p.color.unwrap_or_default().red * normalization_factor)
+                .map(|p| p.color.unwrap_or_default().red)
                 .collect::<Vec<u16>>(),
         );
         point_data_columns.push(color_red_column);
@@ -153,7 +276,7 @@ fn convert_to_data_frame(
             PointDataColumnType::ColorGreen.into(),
             las_points
                 .par_iter()
-                .map(|p| p.color.unwrap_or_default().green * normalization_factor)
+                .map(|p| p.color.unwrap_or_default().green)
                 .collect::<Vec<u16>>(),
         );
         point_data_columns.push(color_green_column);
@@ -162,18 +285,175 @@ fn convert_to_data_frame(
             PointDataColumnType::ColorBlue.into(),
             las_points
                 .par_iter()
-                .map(|p| p.color.unwrap_or_default().blue * normalization_factor)
+                .map(|p| p.color.unwrap_or_default().blue)
                 .collect::<Vec<u16>>(),
         );
         point_data_columns.push(color_blue_column);
     }
 
-    let data_frame = DataFrame::new(point_data_columns)?;
+    point_data_columns.push(Column::new(
+        PointDataColumnType::Classification.into(),
+        las_points
+            .par_iter()
+            .map(|p| u8::from(p.classification))
+            .collect::<Vec<u8>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::ReturnNumber.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.return_number)
+            .collect::<Vec<u8>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::NumberOfReturns.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.number_of_returns)
+            .collect::<Vec<u8>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::ScanAngle.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.scan_angle)
+            .collect::<Vec<f32>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::ScanDirectionFlag.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.scan_direction == ScanDirection::LeftToRight)
+            .collect::<Vec<bool>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::EdgeOfFlightLine.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.is_edge_of_flight_line)
+            .collect::<Vec<bool>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::SyntheticFlag.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.is_synthetic)
+            .collect::<Vec<bool>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::KeyPointFlag.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.is_key_point)
+            .collect::<Vec<bool>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::WithheldFlag.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.is_withheld)
+            .collect::<Vec<bool>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::OverlapFlag.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.is_overlap)
+            .collect::<Vec<bool>>(),
+    ));
+    point_data_columns.push(Column::new(
+        PointDataColumnType::UserData.into(),
+        las_points
+            .par_iter()
+            .map(|p| p.user_data)
+            .collect::<Vec<u8>>(),
+    ));
+
+    // scanner channels are only available in the extended point formats 6-10
+    if point_format.is_extended {
+        point_data_columns.push(Column::new(
+            PointDataColumnType::ScannerChannel.into(),
+            las_points
+                .par_iter()
+                .map(|p| p.scanner_channel)
+                .collect::<Vec<u8>>(),
+        ));
+    }
+
+    if point_format.has_nir {
+        point_data_columns.push(Column::new(
+            PointDataColumnType::Nir.into(),
+            las_points
+                .par_iter()
+                .map(|p| p.nir.unwrap_or_default())
+                .collect::<Vec<u16>>(),
+        ));
+    }
+
+    // extra bytes without a complete description are ignored
+    let described_size: usize = extra_bytes_descriptors
+        .iter()
+        .map(|d| d.data_type.size())
+        .sum();
+    if described_size <= point_format.extra_bytes as usize {
+        let mut byte_offset = 0;
+        for current_descriptor in extra_bytes_descriptors {
+            let is_duplicate = point_data_columns
+                .iter()
+                .any(|c| c.name().as_str() == current_descriptor.name);
+            if let Some(column) = current_descriptor.read_column(&las_points, byte_offset)
+                && !is_duplicate
+            {
+                point_data_columns.push(column);
+            }
+            byte_offset += current_descriptor.data_type.size();
+        }
+    }
+
+    let data_frame = cast_data_frame(DataFrame::new(point_data_columns)?)?;
     // println!("Created data frame with {} rows", data_frame.height());
     // println!("Data frame schema: {:?}", data_frame.schema());
     Ok(data_frame)
 }
 
+/// Finishes the data frame of all chunks.
+///
+/// The point source IDs are dropped if all are zero. If requested, colors are normalized to the
+/// 16-bit range, if all values of all points are within the 8-bit range.
+pub fn finish_data_frame(
+    mut data_frame: DataFrame,
+    normalize_colors: bool,
+) -> Result<DataFrame, Error> {
+    let point_source_id_column = PointDataColumnType::PointSourceId.as_str();
+    if let Ok(point_source_ids) = data_frame.column(point_source_id_column)
+        && point_source_ids.u16()?.max().unwrap_or_default() == 0
+    {
+        data_frame.drop_in_place(point_source_id_column)?;
+    }
+
+    let color_columns = [
+        PointDataColumnType::ColorRed.as_str(),
+        PointDataColumnType::ColorGreen.as_str(),
+        PointDataColumnType::ColorBlue.as_str(),
+    ];
+    if !normalize_colors || color_columns.iter().any(|c| data_frame.column(c).is_err()) {
+        return Ok(data_frame);
+    }
+    let mut color_maximum = 0;
+    for current_column in color_columns {
+        let current_maximum = data_frame.column(current_column)?.u16()?.max();
+        color_maximum = color_maximum.max(current_maximum.unwrap_or_default());
+    }
+    if color_maximum <= u8::MAX as u16 {
+        for current_column in color_columns {
+            let normalized_values = data_frame.column(current_column)?.u16()? * 256;
+            data_frame.with_column(normalized_values.into_series())?;
+        }
+    }
+
+    Ok(data_frame)
+}
+
 const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
 const MAX_NANOS: u32 = 999_999_999;
 
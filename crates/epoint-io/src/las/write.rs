@@ -1,20 +1,48 @@
-use crate::las::write_impl::write_las_format;
+use crate::las::write_impl::{LasChunkWriter, LasWriteSettings, write_las_format};
+use crate::las::{LasIntensityScaling, LasVersion};
 use crate::{Error, FILE_EXTENSION_LAS_FORMAT, FILE_EXTENSION_LAZ_FORMAT};
 use epoint_core::PointCloud;
 
 use crate::Error::{InvalidFileExtension, NoFileExtension};
 use ecoord::FrameId;
+use nalgebra::Vector3;
 use std::fmt::Debug;
 use std::fs::File;
 use std::io::{BufWriter, Seek, Write};
 use std::path::Path;
 
-/// `EpointWriter` sets up a writer for the custom reader data structure.
+/// `LasWriter` exports a point cloud to a LAS file.
 ///
+/// The smallest point format holding the available columns is selected. The extended point
+/// formats 6-8 are only used if the attributes cannot be represented by the formats 0-3, e.g. for
+/// near-infrared values, classes above 31, more than 7 returns, scanner channels or overlap flags,
+/// and require LAS version 1.4. Numeric columns without a standard LAS field, such as `id` or
+/// custom attributes, are written as extra bytes described by an extra bytes VLR. Boolean columns
+/// are stored as `u8` values and therefore read back as `u8` columns. Columns with names longer
+/// than the 32 bytes of an extra bytes descriptor are skipped with a warning.
+///
+/// If the frame info of the point cloud's frame holds an EPSG code, it is written as GeoTIFF
+/// GeoKeyDirectory VLR for the point formats 0-5 and as OGC WKT VLR for the extended point
+/// formats. The WKT definition stored under [`METADATA_KEY_CRS_WKT`](crate::METADATA_KEY_CRS_WKT)
+/// is written if it references the same code. Otherwise, writing an extended point format fails,
+/// since the definition cannot be derived from the code alone.
+///
+/// The points are LAZ-compressed if the file extension is `laz` or if requested via
+/// [`with_compression`](Self::with_compression).
+///
+/// The points are converted and written in chunks of rows to limit the peak memory. Several point
+/// clouds can be written to one file with an [`IncrementalLasWriter`] created by
+/// [`into_incremental`](Self::into_incremental).
 #[derive(Debug, Clone)]
 pub struct LasWriter<W: 'static + Write + Seek + Sync + Debug + Send> {
     writer: W,
     frame_id: Option<FrameId>,
+    version: LasVersion,
+    scale: Option<Vector3<f64>>,
+    offset: Option<Vector3<f64>>,
+    intensity_scaling: LasIntensityScaling,
+    compressed: bool,
+    points_per_chunk: Option<u64>,
 }
 
 impl<W: Write + Seek + Sync + Debug + Send> LasWriter<W> {
@@ -22,6 +50,12 @@ impl<W: Write + Seek + Sync + Debug + Send> LasWriter<W> {
         Self {
             writer,
             frame_id: None,
+            version: LasVersion::V1_4,
+            scale: None,
+            offset: None,
+            intensity_scaling: LasIntensityScaling::default(),
+            compressed: false,
+            points_per_chunk: Some(1_000_000),
         }
     }
 
@@ -30,15 +64,120 @@ impl<W: Write + Seek + Sync + Debug + Send> LasWriter<W> {
         self
     }
 
+    /// Sets the LAS version of the written file, which is 1.4 by default.
+    pub fn with_version(mut self, version: LasVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the scale factors of the coordinates, which are 0.001 by default.
+    pub fn with_scale(mut self, scale: Vector3<f64>) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Sets the offset of the coordinates, which is the center of the points by default.
+    pub fn with_offset(mut self, offset: Vector3<f64>) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn with_intensity_scaling(mut self, intensity_scaling: LasIntensityScaling) -> Self {
+        self.intensity_scaling = intensity_scaling;
+        self
+    }
+
+    /// Enables the LAZ compression of the points.
+    pub fn with_compression(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
+    }
+
+    /// Sets the number of points converted and written at once, which is 1,000,000 by default.
+    ///
+    /// If `None`, all points of a point cloud are converted at once.
+    pub fn with_points_per_chunk(mut self, points_per_chunk: Option<u64>) -> Self {
+        self.points_per_chunk = points_per_chunk;
+        self
+    }
+
+    /// Converts into an [`IncrementalLasWriter`] appending several point clouds to one file.
+    pub fn into_incremental(self) -> IncrementalLasWriter<W> {
+        let settings = self.settings();
+        IncrementalLasWriter {
+            writer: Some(BufWriter::new(self.writer)),
+            frame_id: self.frame_id,
+            settings,
+            chunk_writer: None,
+        }
+    }
+
     pub fn finish(self, mut point_cloud: PointCloud) -> Result<(), Error> {
-        if let Some(frame_id) = self.frame_id {
+        if let Some(frame_id) = self.frame_id.clone() {
             point_cloud.resolve_to_frame(frame_id)?;
         };
 
-        write_las_format(BufWriter::new(self.writer), point_cloud)?;
+        let settings = self.settings();
+        write_las_format(BufWriter::new(self.writer), point_cloud, &settings)?;
 
         Ok(())
     }
+
+    fn settings(&self) -> LasWriteSettings {
+        LasWriteSettings {
+            version: self.version.clone(),
+            scale: self.scale,
+            offset: self.offset,
+            intensity_scaling: self.intensity_scaling,
+            compressed: self.compressed,
+            points_per_chunk: self.points_per_chunk,
+            extended_point_format: false,
+        }
+    }
+}
+
+/// `IncrementalLasWriter` appends several point clouds to one LAS file.
+///
+/// The point format, extra bytes, coordinate reference system and intensity scaling are derived
+/// from the first appended point cloud. Columns of later point clouds that are not covered by the
+/// selected point format are skipped, and missing columns are filled with default values. The
+/// file is completed by [`finish`](Self::finish).
+pub struct IncrementalLasWriter<W: 'static + Write + Seek + Sync + Debug + Send> {
+    writer: Option<BufWriter<W>>,
+    frame_id: Option<FrameId>,
+    settings: LasWriteSettings,
+    chunk_writer: Option<LasChunkWriter<BufWriter<W>>>,
+}
+
+impl<W: Write + Seek + Sync + Debug + Send> IncrementalLasWriter<W> {
+    pub fn append(&mut self, mut point_cloud: PointCloud) -> Result<(), Error> {
+        if let Some(frame_id) = self.frame_id.clone() {
+            point_cloud.resolve_to_frame(frame_id)?;
+        };
+
+        let chunk_writer = match (&mut self.chunk_writer, self.writer.take()) {
+            (Some(chunk_writer), _) => chunk_writer,
+            (None, Some(writer)) => {
+                self.chunk_writer
+                    .insert(LasChunkWriter::new(writer, &point_cloud, &self.settings)?)
+            }
+            // the writer is consumed, if the header could not be written
+            (None, None) => {
+                return Err(std::io::Error::other("writer is not available anymore").into());
+            }
+        };
+        chunk_writer.write_point_cloud(&point_cloud)?;
+
+        Ok(())
+    }
+
+    /// Completes the file, which requires at least one appended point cloud.
+    pub fn finish(self) -> Result<(), Error> {
+        let chunk_writer = self
+            .chunk_writer
+            .ok_or(epoint_core::Error::NoRemainingPoints)?;
+        chunk_writer.close()
+    }
 }
 
 impl LasWriter<File> {
@@ -50,7 +189,8 @@ impl LasWriter<File> {
             ));
         }
 
+        let compressed = extension == FILE_EXTENSION_LAZ_FORMAT;
         let file = File::create(path)?;
-        Ok(Self::new(file))
+        Ok(Self::new(file).with_compression(compressed))
     }
 }
@@ -1,95 +1,442 @@
 use crate::Error;
+use crate::Error::MissingCrsWkt;
+use crate::METADATA_KEY_CRS_WKT;
 use crate::las::ADJUSTED_GPS_TIME_OFFSET;
 use crate::las::GPS_EPOCH_REFERENCE_TIMESTAMP;
-use chrono::{TimeZone, Timelike};
-use epoint_core::PointCloud;
+use crate::las::crs::{create_geo_key_directory_vlr, create_wkt_vlr, parse_wkt_epsg};
+use crate::las::extra_bytes::{ExtraBytesDescriptor, encode_column};
+use crate::las::{LasIntensityScaling, LasVersion};
+use chrono::Timelike;
+use epoint_core::{PointCloud, PointData, PointDataColumnType};
 use las::GpsTimeType;
+use las::point::{Classification, ScanDirection};
+use nalgebra::Vector3;
+use polars::prelude::{BooleanChunked, ChunkAgg};
 use rayon::prelude::*;
 use std::fmt::Debug;
 use std::io::Seek;
 
+/// Settings of the written LAS file.
+#[derive(Debug, Clone)]
+pub struct LasWriteSettings {
+    pub version: LasVersion,
+    pub scale: Option<Vector3<f64>>,
+    pub offset: Option<Vector3<f64>>,
+    pub intensity_scaling: LasIntensityScaling,
+    pub compressed: bool,
+    pub points_per_chunk: Option<u64>,
+    /// Selects the extended point formats 6-8 regardless of the available columns.
+    pub extended_point_format: bool,
+}
+
 pub fn write_las_format<W: 'static + std::io::Write + Seek + Sync + Debug + Send>(
     writer: W,
     point_cloud: PointCloud,
+    settings: &LasWriteSettings,
 ) -> Result<(), Error> {
-    let center = point_cloud.point_data.get_local_center();
-
-    let mut builder = las::Builder::from((1, 4));
-    builder.point_format = las::point::Format::new(0)?;
-    builder.point_format.has_gps_time = point_cloud.contains_timestamps();
-    builder.point_format.has_color = point_cloud.contains_colors();
-    //builder.point_format.is_extended = false;
-
-    builder.transforms.x.offset = center.x;
-    // builder.transforms.x.scale = 1.0;
-    builder.transforms.y.offset = center.y;
-    builder.transforms.z.offset = center.z;
+    let mut chunk_writer = LasChunkWriter::new(writer, &point_cloud, settings)?;
+    chunk_writer.write_point_cloud(&point_cloud)?;
+    chunk_writer.close()
+}
+
+/// Writes point clouds in chunks of rows, so that only the LAS points of a single chunk are
+/// held in memory.
+///
+/// The header is derived from the point cloud passed on creation. Columns of subsequently written
+/// point clouds that are not covered by the header are skipped, and missing columns are filled
+/// with default values.
+pub struct LasChunkWriter<W: 'static + std::io::Write + Seek + Sync + Debug + Send> {
+    las_writer: las::Writer<W>,
+    point_encoder: LasPointEncoder,
+    points_per_chunk: Option<u64>,
+}
+
+impl<W: 'static + std::io::Write + Seek + Sync + Debug + Send> LasChunkWriter<W> {
+    pub fn new(
+        writer: W,
+        point_cloud: &PointCloud,
+        settings: &LasWriteSettings,
+    ) -> Result<Self, Error> {
+        let (builder, point_encoder) = create_header_builder(point_cloud, settings)?;
+        let las_writer = las::Writer::new(writer, builder.into_header()?)?;
+
+        Ok(Self {
+            las_writer,
+            point_encoder,
+            points_per_chunk: settings.points_per_chunk,
+        })
+    }
+
+    pub fn write_point_cloud(&mut self, point_cloud: &PointCloud) -> Result<(), Error> {
+        let data_frame = &point_cloud.point_data.data_frame;
+        let Some(points_per_chunk) = self.points_per_chunk else {
+            return self.write_point_data(&point_cloud.point_data);
+        };
+
+        let points_per_chunk = points_per_chunk.max(1) as usize;
+        for chunk_offset in (0..data_frame.height()).step_by(points_per_chunk) {
+            let chunk =
+                PointData::new_unchecked(data_frame.slice(chunk_offset as i64, points_per_chunk));
+            self.write_point_data(&chunk)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn close(mut self) -> Result<(), Error> {
+        self.las_writer.close()?;
+        Ok(())
+    }
+
+    fn write_point_data(&mut self, point_data: &PointData) -> Result<(), Error> {
+        for current_point in self.point_encoder.encode(point_data)? {
+            self.las_writer.write_point(current_point)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates the header builder and the encoder of the points for the columns of a point cloud.
+///
+/// The point format, extra bytes, coordinate transforms, coordinate reference system and
+/// intensity scaling are derived from the point cloud.
+pub fn create_header_builder(
+    point_cloud: &PointCloud,
+    settings: &LasWriteSettings,
+) -> Result<(las::Builder, LasPointEncoder), Error> {
+    let offset = settings
+        .offset
+        .unwrap_or(point_cloud.point_data.get_local_center().coords);
+
+    let mut builder = las::Builder::from(settings.version.major_minor());
+    builder.point_format =
+        select_point_format(&point_cloud.point_data, settings.extended_point_format)?;
+    builder.point_format.is_compressed = settings.compressed;
+
+    builder.transforms.x.offset = offset.x;
+    builder.transforms.y.offset = offset.y;
+    builder.transforms.z.offset = offset.z;
+    if let Some(scale) = settings.scale {
+        builder.transforms.x.scale = scale.x;
+        builder.transforms.y.scale = scale.y;
+        builder.transforms.z.scale = scale.z;
+    }
     builder.gps_time_type = GpsTimeType::Standard;
 
-    let header = builder.into_header()?;
+    let crs_epsg = point_cloud
+        .info_frame_id()
+        .and_then(|f| point_cloud.transform_tree.frames().get(f))
+        .and_then(|f| f.crs_epsg);
+    if let Some(crs_epsg) = crs_epsg {
+        // the extended point formats require the coordinate reference system as OGC WKT, which
+        // cannot be derived from the code without an EPSG database
+        if builder.point_format.is_extended {
+            let crs_wkt = point_cloud
+                .info()
+                .metadata
+                .get(METADATA_KEY_CRS_WKT)
+                .filter(|w| parse_wkt_epsg(w) == Some(crs_epsg))
+                .ok_or(MissingCrsWkt(crs_epsg))?;
+            builder.vlrs.push(create_wkt_vlr(crs_wkt));
+            builder.has_wkt_crs = true;
+        } else {
+            builder.vlrs.push(create_geo_key_directory_vlr(crs_epsg)?);
+        }
+    }
 
-    //header.transforms = las::Transform::default();
+    let extra_bytes_descriptors = create_extra_bytes_descriptors(&point_cloud.point_data);
+    if !extra_bytes_descriptors.is_empty() {
+        builder.point_format.extra_bytes = extra_bytes_descriptors
+            .iter()
+            .map(|d| d.data_type.size() as u16)
+            .sum();
+        builder
+            .vlrs
+            .push(ExtraBytesDescriptor::create_vlr(&extra_bytes_descriptors));
+    }
 
-    // header.point_format().is_compressed = true;
-    let mut las_writer = las::Writer::new(writer, header)?;
+    let (intensity_offset, intensity_factor) =
+        intensity_transform(&point_cloud.point_data, settings.intensity_scaling);
+    let point_encoder = LasPointEncoder {
+        point_format: builder.point_format,
+        extra_bytes_descriptors,
+        intensity_offset,
+        intensity_factor,
+    };
 
-    let converted_timestamps = if point_cloud.contains_timestamps() {
-        // GPS time: https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping
-        let values: Vec<f64> = point_cloud
-            .point_data
-            .get_all_timestamps()?
-            .par_iter()
-            .map(|t| {
-                (t.timestamp() - ADJUSTED_GPS_TIME_OFFSET - GPS_EPOCH_REFERENCE_TIMESTAMP) as f64
-                    + (t.nanosecond() as f64 * 1.0e-9)
+    Ok((builder, point_encoder))
+}
+
+/// Converts point data to LAS points of a fixed point format.
+#[derive(Debug, Clone)]
+pub struct LasPointEncoder {
+    point_format: las::point::Format,
+    extra_bytes_descriptors: Vec<ExtraBytesDescriptor>,
+    intensity_offset: f32,
+    intensity_factor: f32,
+}
+
+impl LasPointEncoder {
+    pub fn encode(&self, point_data: &PointData) -> Result<Vec<las::Point>, Error> {
+        let point_format = self.point_format;
+
+        let converted_timestamps = if point_data.contains_timestamps() {
+            // GPS time: https://en.wikipedia.org/wiki/Global_Positioning_System#Timekeeping
+            let values: Vec<f64> = point_data
+                .get_all_timestamps()?
+                .par_iter()
+                .map(|t| {
+                    (t.timestamp() - ADJUSTED_GPS_TIME_OFFSET - GPS_EPOCH_REFERENCE_TIMESTAMP)
+                        as f64
+                        + (t.nanosecond() as f64 * 1.0e-9)
+                })
+                .collect();
+            Some(values)
+        } else {
+            None
+        };
+
+        let converted_colors = if point_data.contains_colors() {
+            let values: Vec<las::Color> = point_data
+                .get_all_colors()?
+                .par_iter()
+                .map(|c| las::Color::new(c.red, c.green, c.blue))
+                .collect();
+            Some(values)
+        } else {
+            None
+        };
+
+        let converted_intensity_values = point_data.get_intensity_values().ok().map(|v| {
+            v.into_iter()
+                .map(|v| {
+                    ((v.unwrap_or_default() - self.intensity_offset) * self.intensity_factor)
+                        .round()
+                        .clamp(0.0, u16::MAX as f32) as u16
+                })
+                .collect::<Vec<_>>()
+        });
+        let converted_classification_values = point_data
+            .get_classification_values()
+            .ok()
+            .map(|v| {
+                v.into_iter()
+                    .map(|c| Classification::new(c.unwrap_or_default()))
+                    .collect::<Result<Vec<_>, _>>()
             })
-            .collect();
-        Some(values)
-    } else {
-        None
-    };
+            .transpose()?;
+        let extra_bytes = encode_extra_bytes(point_data, &self.extra_bytes_descriptors)?;
 
-    let converted_colors = if point_cloud.contains_colors() {
-        let values: Vec<las::Color> = point_cloud
-            .point_data
-            .get_all_colors()?
+        let point_source_id_values = point_data.get_point_source_id_values().ok();
+        let return_number_values = point_data.get_return_number_values().ok();
+        let number_of_returns_values = point_data.get_number_of_returns_values().ok();
+        let scan_angle_values = point_data.get_scan_angle_values().ok();
+        let scan_direction_flag_values = point_data.get_scan_direction_flag_values().ok();
+        let edge_of_flight_line_values = point_data.get_edge_of_flight_line_values().ok();
+        let synthetic_flag_values = point_data.get_synthetic_flag_values().ok();
+        let key_point_flag_values = point_data.get_key_point_flag_values().ok();
+        let withheld_flag_values = point_data.get_withheld_flag_values().ok();
+        let overlap_flag_values = point_data.get_overlap_flag_values().ok();
+        let user_data_values = point_data.get_user_data_values().ok();
+        let scanner_channel_values = point_data.get_scanner_channel_values().ok();
+        let nir_values = point_data.get_nir_values().ok();
+
+        let converted_points: Vec<las::Point> = point_data
+            .get_all_points()
             .par_iter()
-            .map(|c| las::Color::new(c.red, c.green, c.blue))
+            .enumerate()
+            .map(|(i, p)| las::Point {
+                x: p.x,
+                y: p.y,
+                z: p.z,
+                gps_time: point_format.has_gps_time.then(|| {
+                    converted_timestamps
+                        .as_ref()
+                        .map_or(0.0, |v| v.get(i).copied().unwrap_or_default())
+                }),
+                intensity: converted_intensity_values
+                    .as_ref()
+                    .map_or(0, |v| v.get(i).copied().unwrap_or_default()),
+                color: point_format.has_color.then(|| {
+                    converted_colors
+                        .as_ref()
+                        .and_then(|v| v.get(i).copied())
+                        .unwrap_or_default()
+                }),
+                nir: point_format
+                    .has_nir
+                    .then(|| nir_values.and_then(|v| v.get(i)).unwrap_or_default()),
+                classification: converted_classification_values
+                    .as_ref()
+                    .map_or(Classification::CreatedNeverClassified, |v| v[i]),
+                return_number: return_number_values
+                    .and_then(|v| v.get(i))
+                    .unwrap_or_default(),
+                number_of_returns: number_of_returns_values
+                    .and_then(|v| v.get(i))
+                    .unwrap_or_default(),
+                scan_angle: scan_angle_values.and_then(|v| v.get(i)).unwrap_or_default(),
+                scan_direction: if flag_value(scan_direction_flag_values, i) {
+                    ScanDirection::LeftToRight
+                } else {
+                    ScanDirection::RightToLeft
+                },
+                is_edge_of_flight_line: flag_value(edge_of_flight_line_values, i),
+                is_synthetic: flag_value(synthetic_flag_values, i),
+                is_key_point: flag_value(key_point_flag_values, i),
+                is_withheld: flag_value(withheld_flag_values, i),
+                is_overlap: flag_value(overlap_flag_values, i),
+                user_data: user_data_values.and_then(|v| v.get(i)).unwrap_or_default(),
+                scanner_channel: scanner_channel_values
+                    .and_then(|v| v.get(i))
+                    .unwrap_or_default(),
+                point_source_id: point_source_id_values
+                    .and_then(|v| v.get(i))
+                    .unwrap_or_default(),
+                extra_bytes: extra_bytes[i].clone(),
+                ..Default::default()
+            })
             .collect();
-        Some(values)
-    } else {
-        None
+
+        Ok(converted_points)
+    }
+}
+
+/// Columns that are stored in the standard fields of the LAS point records.
+const POINT_RECORD_COLUMN_TYPES: [PointDataColumnType; 23] = [
+    PointDataColumnType::X,
+    PointDataColumnType::Y,
+    PointDataColumnType::Z,
+    PointDataColumnType::TimestampSecond,
+    PointDataColumnType::TimestampNanoSecond,
+    PointDataColumnType::Intensity,
+    PointDataColumnType::ColorRed,
+    PointDataColumnType::ColorGreen,
+    PointDataColumnType::ColorBlue,
+    PointDataColumnType::PointSourceId,
+    PointDataColumnType::Classification,
+    PointDataColumnType::ReturnNumber,
+    PointDataColumnType::NumberOfReturns,
+    PointDataColumnType::ScanAngle,
+    PointDataColumnType::ScanDirectionFlag,
+    PointDataColumnType::EdgeOfFlightLine,
+    PointDataColumnType::SyntheticFlag,
+    PointDataColumnType::KeyPointFlag,
+    PointDataColumnType::WithheldFlag,
+    PointDataColumnType::OverlapFlag,
+    PointDataColumnType::UserData,
+    PointDataColumnType::ScannerChannel,
+    PointDataColumnType::Nir,
+];
+
+/// Creates the extra bytes descriptors for all numeric columns without a standard LAS field.
+fn create_extra_bytes_descriptors(point_data: &PointData) -> Vec<ExtraBytesDescriptor> {
+    let mut descriptors: Vec<ExtraBytesDescriptor> = Vec::new();
+
+    for current_column in point_data.data_frame.get_columns() {
+        let is_point_record_column = POINT_RECORD_COLUMN_TYPES
+            .iter()
+            .any(|t| t.as_str() == current_column.name().as_str());
+        if is_point_record_column {
+            continue;
+        }
+        if let Some(descriptor) = ExtraBytesDescriptor::from_column(current_column) {
+            descriptors.push(descriptor);
+        }
+    }
+
+    descriptors
+}
+
+/// Encodes the extra bytes per point, while missing columns are filled with zeros.
+fn encode_extra_bytes(
+    point_data: &PointData,
+    descriptors: &[ExtraBytesDescriptor],
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut extra_bytes: Vec<Vec<u8>> = vec![Vec::new(); point_data.height()];
+
+    for current_descriptor in descriptors {
+        let Ok(column) = point_data.data_frame.column(&current_descriptor.name) else {
+            let zeros = vec![0u8; current_descriptor.data_type.size()];
+            extra_bytes.iter_mut().for_each(|b| b.extend(&zeros));
+            continue;
+        };
+
+        let values = encode_column(column, current_descriptor.data_type)?;
+        for (point_extra_bytes, value) in extra_bytes.iter_mut().zip(values) {
+            point_extra_bytes.extend(value);
+        }
+    }
+
+    Ok(extra_bytes)
+}
+
+/// Selects the smallest point format that holds the available columns.
+///
+/// The extended point formats are only selected if requested or if the attributes cannot be
+/// represented by the legacy point formats 0-3.
+fn select_point_format(
+    point_data: &PointData,
+    extended_point_format: bool,
+) -> Result<las::point::Format, Error> {
+    let has_gps_time = point_data.contains_timestamps();
+    let has_color = point_data.contains_colors();
+    let has_nir = point_data.contains_nir_column();
+
+    let requires_extended_format = extended_point_format
+        || has_nir
+        || point_data
+            .get_classification_values()
+            .is_ok_and(|v| v.max().unwrap_or_default() > 31)
+        || point_data
+            .get_return_number_values()
+            .is_ok_and(|v| v.max().unwrap_or_default() > 7)
+        || point_data
+            .get_number_of_returns_values()
+            .is_ok_and(|v| v.max().unwrap_or_default() > 7)
+        || point_data
+            .get_scanner_channel_values()
+            .is_ok_and(|v| v.max().unwrap_or_default() > 0)
+        || point_data.get_overlap_flag_values().is_ok_and(|v| v.any());
+
+    let format_number = match (requires_extended_format, has_gps_time, has_color, has_nir) {
+        (false, false, false, _) => 0,
+        (false, true, false, _) => 1,
+        (false, false, true, _) => 2,
+        (false, true, true, _) => 3,
+        (true, _, false, false) => 6,
+        (true, _, true, false) => 7,
+        (true, _, _, true) => 8,
     };
 
-    let converted_intensity_values = point_cloud.point_data.get_intensity_values().ok();
-
-    let converted_point_source_id_values = point_cloud.point_data.get_point_source_id_values().ok();
-
-    let converted_points: Vec<las::Point> = point_cloud
-        .point_data
-        .get_all_points()
-        .par_iter()
-        .enumerate()
-        .map(|(i, p)| las::Point {
-            x: p.x,
-            y: p.y,
-            z: p.z,
-            gps_time: converted_timestamps
-                .as_ref()
-                .and_then(|v| v.get(i).copied()),
-            intensity: converted_intensity_values
-                .map_or(0, |v| v.get(i).expect("must be available") as u16),
-            color: converted_colors.as_ref().and_then(|v| v.get(i).copied()),
-            point_source_id: converted_point_source_id_values
-                .map_or(0, |v| v.get(i).expect("must be available")),
-            ..Default::default()
-        })
-        .collect();
+    Ok(las::point::Format::new(format_number)?)
+}
 
-    for current_point in converted_points {
-        las_writer.write_point(current_point)?;
+/// Returns the offset and factor for converting the intensity values according to the scaling.
+fn intensity_transform(
+    point_data: &PointData,
+    intensity_scaling: LasIntensityScaling,
+) -> (f32, f32) {
+    match intensity_scaling {
+        LasIntensityScaling::Raw => (0.0, 1.0),
+        LasIntensityScaling::Normalized => (0.0, u16::MAX as f32),
+        LasIntensityScaling::MinMax => {
+            let Ok(intensity_values) = point_data.get_intensity_values() else {
+                return (0.0, 1.0);
+            };
+            let min = intensity_values.min().unwrap_or_default();
+            let max = intensity_values.max().unwrap_or_default();
+            let factor = if max > min {
+                u16::MAX as f32 / (max - min)
+            } else {
+                1.0
+            };
+            (min, factor)
+        }
     }
+}
 
-    las_writer.close()?;
-    Ok(())
+fn flag_value(values: Option<&BooleanChunked>, index: usize) -> bool {
+    values.and_then(|v| v.get(index)).unwrap_or_default()
 }
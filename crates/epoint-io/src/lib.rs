@@ -1,9 +1,14 @@
 mod auto;
+mod copc;
 mod e57;
 mod epoint;
 mod error;
 mod format;
 pub(crate) mod las;
+mod pcd;
+mod ply;
+mod potree;
+mod tiles3d;
 mod xyz;
 
 #[doc(inline)]
@@ -18,12 +23,48 @@ pub use crate::auto::write::AutoWriter;
 #[doc(inline)]
 pub use crate::epoint::read::EpointReader;
 
+#[doc(inline)]
+pub use crate::epoint::read::EpointReadInfo;
+
 #[doc(inline)]
 pub use crate::epoint::write::EpointWriter;
 
+#[doc(inline)]
+pub use crate::epoint::edit::EpointEditor;
+
+#[doc(inline)]
+pub use crate::epoint::EpointColumnDescription;
+
+#[doc(inline)]
+pub use crate::epoint::EpointPartitioning;
+
+#[doc(inline)]
+pub use crate::epoint::EpointParquetCompression;
+
+#[doc(inline)]
+pub use crate::epoint::EpointParquetOptions;
+
+#[doc(inline)]
+pub use crate::epoint::EpointQuantization;
+
+#[doc(inline)]
+pub use crate::copc::read::CopcReader;
+
+#[doc(inline)]
+pub use crate::copc::read::CopcReadInfo;
+
+#[doc(inline)]
+pub use crate::copc::write::CopcWriter;
+
+#[doc(inline)]
+pub use crate::copc::CopcInfo;
+
 #[doc(inline)]
 pub use crate::e57::read::E57Reader;
 
+#[doc(inline)]
+pub use crate::e57::write::E57Writer;
+
 #[doc(inline)]
 pub use crate::las::read::LasReader;
 
@@ -33,9 +74,39 @@ pub use crate::las::read::LasReadInfo;
 #[doc(inline)]
 pub use crate::las::LasVersion;
 
+#[doc(inline)]
+pub use crate::las::LasIntensityScaling;
+
 #[doc(inline)]
 pub use crate::las::write::LasWriter;
 
+#[doc(inline)]
+pub use crate::las::write::IncrementalLasWriter;
+
+#[doc(inline)]
+pub use crate::pcd::read::PcdReader;
+
+#[doc(inline)]
+pub use crate::pcd::write::PcdWriter;
+
+#[doc(inline)]
+pub use crate::pcd::PcdEncoding;
+
+#[doc(inline)]
+pub use crate::ply::read::PlyReader;
+
+#[doc(inline)]
+pub use crate::ply::write::PlyWriter;
+
+#[doc(inline)]
+pub use crate::ply::PlyEncoding;
+
+#[doc(inline)]
+pub use crate::potree::write::PotreeWriter;
+
+#[doc(inline)]
+pub use crate::tiles3d::write::Tiles3dWriter;
+
 #[doc(inline)]
 pub use crate::xyz::read::XyzReader;
 
@@ -48,9 +119,18 @@ pub use crate::format::PointCloudFormat;
 #[doc(inline)]
 pub use crate::epoint::FILE_EXTENSION_EPOINT_FORMAT;
 
+#[doc(inline)]
+pub use crate::epoint::EPOINT_FORMAT_VERSION;
+
 #[doc(inline)]
 pub use crate::epoint::FILE_EXTENSION_EPOINT_TAR_FORMAT;
 
+#[doc(inline)]
+pub use crate::epoint::FILE_EXTENSION_EPOINT_DIRECTORY_FORMAT;
+
+#[doc(inline)]
+pub use crate::copc::FILE_EXTENSION_COPC_FORMAT;
+
 #[doc(inline)]
 pub use crate::e57::FILE_EXTENSION_E57_FORMAT;
 
@@ -60,6 +140,15 @@ pub use crate::las::FILE_EXTENSION_LAZ_FORMAT;
 #[doc(inline)]
 pub use crate::las::FILE_EXTENSION_LAS_FORMAT;
 
+#[doc(inline)]
+pub use crate::las::METADATA_KEY_CRS_WKT;
+
+#[doc(inline)]
+pub use crate::pcd::FILE_EXTENSION_PCD_FORMAT;
+
+#[doc(inline)]
+pub use crate::ply::FILE_EXTENSION_PLY_FORMAT;
+
 #[doc(inline)]
 pub use crate::xyz::FILE_EXTENSION_XYZ_ZST_FORMAT;
 
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("unknown field type `{0}` with size {1}")]
+    UnknownFieldType(String, usize),
+    #[error("unknown data encoding `{0}`")]
+    UnknownEncoding(String),
+    #[error("point data does not contain the field `{0}`")]
+    MissingField(&'static str),
+    #[error("value `{0}` cannot be parsed")]
+    InvalidValue(String),
+    #[error("unexpected end of point data")]
+    UnexpectedEndOfData(),
+    #[error("compressed point data is corrupt")]
+    InvalidCompressedData(),
+    #[error("point data of {0} bytes exceeds the size limit of 4 GiB for binary_compressed")]
+    CompressedDataTooLarge(usize),
+}
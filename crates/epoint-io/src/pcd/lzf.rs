@@ -0,0 +1,120 @@
+//! Minimal implementation of the LZF compression format as used by PCL for `binary_compressed`
+//! point data.
+//!
+//! See also: <http://oldhome.schmorp.de/marc/liblzf.html>
+
+use crate::pcd::error::Error;
+use crate::pcd::error::Error::InvalidCompressedData;
+
+const MAX_LITERAL_LENGTH: usize = 32;
+const MAX_OFFSET: usize = 1 << 13;
+const MAX_REFERENCE_LENGTH: usize = 264;
+const HASH_BITS: u32 = 14;
+
+pub fn decompress(input: &[u8], uncompressed_size: usize) -> Result<Vec<u8>, Error> {
+    // the size is taken from the file, so that the output only grows with the decompressed data
+    let mut output: Vec<u8> = Vec::with_capacity(uncompressed_size.min(input.len()));
+    let mut input_index = 0;
+
+    while input_index < input.len() {
+        let control = input[input_index] as usize;
+        input_index += 1;
+
+        if control < MAX_LITERAL_LENGTH {
+            // literal run
+            let length = control + 1;
+            let literal = input
+                .get(input_index..input_index + length)
+                .ok_or(InvalidCompressedData())?;
+            output.extend_from_slice(literal);
+            input_index += length;
+        } else {
+            // back reference
+            let mut length = control >> 5;
+            if length == 7 {
+                length += *input.get(input_index).ok_or(InvalidCompressedData())? as usize;
+                input_index += 1;
+            }
+            length += 2;
+
+            let offset_low = *input.get(input_index).ok_or(InvalidCompressedData())? as usize;
+            input_index += 1;
+            let offset = ((control & 0x1f) << 8) + offset_low + 1;
+            if offset > output.len() {
+                return Err(InvalidCompressedData());
+            }
+
+            // references may overlap with the bytes being produced
+            let start = output.len() - offset;
+            for i in 0..length {
+                output.push(output[start + i]);
+            }
+        }
+
+        if output.len() > uncompressed_size {
+            return Err(InvalidCompressedData());
+        }
+    }
+
+    if output.len() != uncompressed_size {
+        return Err(InvalidCompressedData());
+    }
+    Ok(output)
+}
+
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut output: Vec<u8> = Vec::with_capacity(input.len() / 2 + MAX_LITERAL_LENGTH);
+    let mut hash_table: Vec<Option<usize>> = vec![None; 1 << HASH_BITS];
+    let mut literal_start = 0;
+    let mut input_index = 0;
+
+    while input_index + 2 < input.len() {
+        let hash = hash_triple(&input[input_index..input_index + 3]);
+        let reference = hash_table[hash].replace(input_index);
+
+        let match_reference = reference.filter(|r| {
+            input_index - r <= MAX_OFFSET
+                && input[*r..*r + 3] == input[input_index..input_index + 3]
+        });
+        let Some(reference) = match_reference else {
+            input_index += 1;
+            continue;
+        };
+
+        let max_length = (input.len() - input_index).min(MAX_REFERENCE_LENGTH);
+        let mut length = 3;
+        while length < max_length && input[reference + length] == input[input_index + length] {
+            length += 1;
+        }
+
+        write_literals(&mut output, &input[literal_start..input_index]);
+
+        let offset = input_index - reference - 1;
+        let encoded_length = length - 2;
+        if encoded_length < 7 {
+            output.push(((encoded_length << 5) | (offset >> 8)) as u8);
+        } else {
+            output.push(((7 << 5) | (offset >> 8)) as u8);
+            output.push((encoded_length - 7) as u8);
+        }
+        output.push((offset & 0xff) as u8);
+
+        input_index += length;
+        literal_start = input_index;
+    }
+
+    write_literals(&mut output, &input[literal_start..]);
+    output
+}
+
+fn write_literals(output: &mut Vec<u8>, literals: &[u8]) {
+    for chunk in literals.chunks(MAX_LITERAL_LENGTH) {
+        output.push((chunk.len() - 1) as u8);
+        output.extend_from_slice(chunk);
+    }
+}
+
+fn hash_triple(bytes: &[u8]) -> usize {
+    let value = ((bytes[0] as u32) << 16) | ((bytes[1] as u32) << 8) | bytes[2] as u32;
+    (value.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
@@ -0,0 +1,134 @@
+use crate::pcd::error::Error::UnknownFieldType;
+
+pub mod error;
+mod lzf;
+pub mod read;
+mod read_impl;
+pub mod write;
+mod write_impl;
+
+pub const FILE_EXTENSION_PCD_FORMAT: &str = "pcd";
+
+const PCD_FORMAT_VERSION: &str = "0.7";
+
+const PCD_FIELD_X: &str = "x";
+const PCD_FIELD_Y: &str = "y";
+const PCD_FIELD_Z: &str = "z";
+const PCD_FIELD_RGB: &str = "rgb";
+const PCD_FIELD_RGBA: &str = "rgba";
+/// Field name used by PCL for padding bytes.
+const PCD_FIELD_PADDING: &str = "_";
+
+/// Encoding of the point data following the PCD header.
+///
+/// See also: <https://pointclouds.org/documentation/tutorials/pcd_file_format.html>
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum PcdEncoding {
+    /// Whitespace separated text, one point per line.
+    Ascii,
+    /// Little endian binary data, one point after another.
+    Binary,
+    /// Little endian binary data, stored field by field and compressed with LZF.
+    #[default]
+    BinaryCompressed,
+}
+
+impl PcdEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PcdEncoding::Ascii => "ascii",
+            PcdEncoding::Binary => "binary",
+            PcdEncoding::BinaryCompressed => "binary_compressed",
+        }
+    }
+}
+
+impl std::str::FromStr for PcdEncoding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ascii" => Ok(PcdEncoding::Ascii),
+            "binary" => Ok(PcdEncoding::Binary),
+            "binary_compressed" => Ok(PcdEncoding::BinaryCompressed),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Types of PCD fields, given by the combination of `TYPE` and `SIZE` in the header.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum PcdFieldType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+}
+
+impl PcdFieldType {
+    fn from_type_and_size(type_name: &str, size: usize) -> Result<Self, error::Error> {
+        match (type_name, size) {
+            ("I", 1) => Ok(PcdFieldType::Int8),
+            ("I", 2) => Ok(PcdFieldType::Int16),
+            ("I", 4) => Ok(PcdFieldType::Int32),
+            ("I", 8) => Ok(PcdFieldType::Int64),
+            ("U", 1) => Ok(PcdFieldType::UInt8),
+            ("U", 2) => Ok(PcdFieldType::UInt16),
+            ("U", 4) => Ok(PcdFieldType::UInt32),
+            ("U", 8) => Ok(PcdFieldType::UInt64),
+            ("F", 4) => Ok(PcdFieldType::Float32),
+            ("F", 8) => Ok(PcdFieldType::Float64),
+            _ => Err(UnknownFieldType(type_name.to_string(), size)),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            PcdFieldType::Int8
+            | PcdFieldType::Int16
+            | PcdFieldType::Int32
+            | PcdFieldType::Int64 => "I",
+            PcdFieldType::UInt8
+            | PcdFieldType::UInt16
+            | PcdFieldType::UInt32
+            | PcdFieldType::UInt64 => "U",
+            PcdFieldType::Float32 | PcdFieldType::Float64 => "F",
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            PcdFieldType::Int8 | PcdFieldType::UInt8 => 1,
+            PcdFieldType::Int16 | PcdFieldType::UInt16 => 2,
+            PcdFieldType::Int32 | PcdFieldType::UInt32 | PcdFieldType::Float32 => 4,
+            PcdFieldType::Int64 | PcdFieldType::UInt64 | PcdFieldType::Float64 => 8,
+        }
+    }
+}
+
+/// A field of the PCD point layout, which may span multiple elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PcdField {
+    name: String,
+    field_type: PcdFieldType,
+    count: usize,
+}
+
+impl PcdField {
+    /// Size of the field in bytes per point.
+    fn size(&self) -> usize {
+        self.field_type.size() * self.count
+    }
+
+    fn is_packed_color(&self) -> bool {
+        (self.name == PCD_FIELD_RGB || self.name == PCD_FIELD_RGBA)
+            && self.field_type.size() == 4
+            && self.count == 1
+    }
+}
@@ -0,0 +1,61 @@
+use crate::Error;
+use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::pcd::FILE_EXTENSION_PCD_FORMAT;
+use crate::pcd::read_impl::import_point_cloud_from_pcd_reader;
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// `PcdReader` imports a point cloud from a PCL PCD file with `ascii`, `binary` or
+/// `binary_compressed` data.
+///
+/// The fields `x`, `y`, `z`, `intensity` and `normal_*` are mapped onto the corresponding point
+/// data columns, packed `rgb`/`rgba` fields are split into the color columns and all remaining
+/// fields are kept as additional columns. Fields with a `COUNT` greater than one result in a
+/// column per element with the element index as suffix.
+///
+/// The `VIEWPOINT` is imported as static transform from the reference frame to the sensor frame,
+/// which is the frame the points are given in. Organized point clouds (`HEIGHT` greater than one)
+/// keep their structure by means of the `row_index` and `column_index` columns.
+#[derive(Debug, Clone)]
+pub struct PcdReader<R: Read> {
+    reader: R,
+    reference_frame_id: FrameId,
+    sensor_frame_id: FrameId,
+}
+
+impl<R: Read> PcdReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            reference_frame_id: FrameId::global(),
+            sensor_frame_id: FrameId::sensor(),
+        }
+    }
+
+    pub fn finish(self) -> Result<PointCloud, Error> {
+        let point_cloud = import_point_cloud_from_pcd_reader(
+            self.reader,
+            self.reference_frame_id,
+            self.sensor_frame_id,
+        )?;
+
+        Ok(point_cloud)
+    }
+}
+
+impl PcdReader<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+        if extension != FILE_EXTENSION_PCD_FORMAT {
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::open(path)?;
+        Ok(Self::new(file))
+    }
+}
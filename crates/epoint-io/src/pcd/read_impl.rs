@@ -0,0 +1,439 @@
+use crate::Error;
+use crate::epoint::read_impl::cast_data_frame;
+use crate::pcd::error::Error::{
+    InvalidHeader, InvalidValue, MissingField, UnexpectedEndOfData, UnknownEncoding,
+};
+use crate::pcd::{
+    PCD_FIELD_PADDING, PCD_FIELD_X, PCD_FIELD_Y, PCD_FIELD_Z, PcdEncoding, PcdField, PcdFieldType,
+    lzf,
+};
+use ecoord::{FrameId, StaticTransform, Transform, TransformEdge, TransformTree};
+use epoint_core::{PointCloud, PointCloudInfo, PointDataColumnType};
+use nalgebra::{Isometry3, Quaternion, UnitQuaternion, Vector3};
+use polars::prelude::{Column, DataFrame};
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+struct PcdHeader {
+    fields: Vec<PcdField>,
+    width: usize,
+    height: usize,
+    viewpoint: Transform,
+    points: usize,
+    encoding: PcdEncoding,
+}
+
+impl PcdHeader {
+    /// Size of a single point in bytes.
+    fn point_size(&self) -> usize {
+        self.fields.iter().map(|f| f.size()).sum()
+    }
+}
+
+/// Arrangement of the decoded point data buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataLayout {
+    /// All fields of a point are stored consecutively (`ascii` and `binary`).
+    PointWise,
+    /// All points of a field are stored consecutively (`binary_compressed`).
+    FieldWise,
+}
+
+pub fn import_point_cloud_from_pcd_reader<R: Read>(
+    reader: R,
+    reference_frame_id: FrameId,
+    sensor_frame_id: FrameId,
+) -> Result<PointCloud, Error> {
+    let mut reader = BufReader::new(reader);
+    let header = read_header(&mut reader)?;
+
+    let (buffer, layout) = match header.encoding {
+        PcdEncoding::Ascii => (
+            read_ascii_data(&mut reader, &header)?,
+            DataLayout::PointWise,
+        ),
+        PcdEncoding::Binary => {
+            let buffer = read_bytes(&mut reader, header.points * header.point_size())?;
+            (buffer, DataLayout::PointWise)
+        }
+        PcdEncoding::BinaryCompressed => (
+            read_compressed_data(&mut reader, &header)?,
+            DataLayout::FieldWise,
+        ),
+    };
+
+    let point_data = convert_to_data_frame(&header, &buffer, layout)?;
+
+    // the viewpoint is the sensor pose relative to the reference frame
+    let static_transform = StaticTransform::new(
+        reference_frame_id,
+        sensor_frame_id.clone(),
+        header.viewpoint,
+    );
+    let transform_tree =
+        TransformTree::new(vec![TransformEdge::Static(static_transform)], Vec::new())?;
+    let info = PointCloudInfo::new(Some(sensor_frame_id));
+
+    let point_cloud = PointCloud::from_data_frame(point_data, info, transform_tree)?;
+    Ok(point_cloud)
+}
+
+fn read_header<R: BufRead>(reader: &mut R) -> Result<PcdHeader, Error> {
+    let mut names: Vec<String> = Vec::new();
+    let mut sizes: Vec<usize> = Vec::new();
+    let mut types: Vec<String> = Vec::new();
+    let mut counts: Option<Vec<usize>> = None;
+    let mut width: Option<usize> = None;
+    let mut height: usize = 1;
+    let mut viewpoint = Transform::from(Isometry3::identity());
+    let mut points: Option<usize> = None;
+
+    let encoding = loop {
+        let line = read_header_line(reader)?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.as_slice() {
+            [] => continue,
+            [comment, ..] if comment.starts_with('#') => continue,
+            ["VERSION", ..] => continue,
+            ["FIELDS", values @ ..] => names = values.iter().map(|v| v.to_string()).collect(),
+            ["SIZE", values @ ..] => sizes = parse_values(values)?,
+            ["TYPE", values @ ..] => types = values.iter().map(|v| v.to_string()).collect(),
+            ["COUNT", values @ ..] => counts = Some(parse_values(values)?),
+            ["WIDTH", value] => width = Some(parse_value(value)?),
+            ["HEIGHT", value] => height = parse_value(value)?,
+            ["VIEWPOINT", values @ ..] => {
+                let values: Vec<f64> = parse_values(values)?;
+                let [tx, ty, tz, qw, qx, qy, qz] = values.as_slice() else {
+                    return Err(InvalidHeader("VIEWPOINT requires seven values".to_string()).into());
+                };
+                viewpoint = Transform::new(
+                    Vector3::new(*tx, *ty, *tz),
+                    UnitQuaternion::from_quaternion(Quaternion::new(*qw, *qx, *qy, *qz)),
+                );
+            }
+            ["POINTS", value] => points = Some(parse_value(value)?),
+            ["DATA", value] => {
+                break PcdEncoding::from_str(value)
+                    .map_err(|_| UnknownEncoding(value.to_string()))?;
+            }
+            _ => return Err(InvalidHeader(format!("unknown line `{line}`")).into()),
+        }
+    };
+
+    let counts = counts.unwrap_or(vec![1; names.len()]);
+    if sizes.len() != names.len() || types.len() != names.len() || counts.len() != names.len() {
+        return Err(
+            InvalidHeader("number of FIELDS, SIZE, TYPE and COUNT differ".to_string()).into(),
+        );
+    }
+    let fields = names
+        .into_iter()
+        .zip(sizes)
+        .zip(types)
+        .zip(counts)
+        .map(|(((name, size), type_name), count)| {
+            Ok(PcdField {
+                name,
+                field_type: PcdFieldType::from_type_and_size(&type_name, size)?,
+                count,
+            })
+        })
+        .collect::<Result<Vec<_>, crate::pcd::error::Error>>()?;
+
+    let width = width.ok_or(InvalidHeader("missing WIDTH".to_string()))?;
+    let grid_size = width
+        .checked_mul(height)
+        .ok_or(InvalidHeader("WIDTH and HEIGHT are too large".to_string()))?;
+    let points = points.unwrap_or(grid_size);
+    if points != grid_size {
+        return Err(InvalidHeader("POINTS does not match WIDTH and HEIGHT".to_string()).into());
+    }
+    // the sizes of the point data buffers are derived from the fields and points
+    fields
+        .iter()
+        .try_fold(0usize, |point_size, f| {
+            point_size.checked_add(f.field_type.size().checked_mul(f.count)?)
+        })
+        .and_then(|point_size| point_size.checked_mul(points))
+        .ok_or(InvalidHeader(
+            "size of the point data is too large".to_string(),
+        ))?;
+
+    Ok(PcdHeader {
+        fields,
+        width,
+        height,
+        viewpoint,
+        points,
+        encoding,
+    })
+}
+
+fn read_header_line<R: BufRead>(reader: &mut R) -> Result<String, Error> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let read_bytes = reader.read_until(b'\n', &mut buffer)?;
+    if read_bytes == 0 {
+        return Err(InvalidHeader("missing DATA".to_string()).into());
+    }
+
+    let line = String::from_utf8_lossy(&buffer).trim().to_string();
+    Ok(line)
+}
+
+fn parse_value<T: FromStr>(token: &str) -> Result<T, Error> {
+    token
+        .parse::<T>()
+        .map_err(|_| InvalidValue(token.to_string()).into())
+}
+
+fn parse_values<T: FromStr>(tokens: &[&str]) -> Result<Vec<T>, Error> {
+    tokens.iter().map(|t| parse_value(t)).collect()
+}
+
+/// Reads the given number of bytes, while the buffer only grows with the bytes actually read, so
+/// that overstated sizes do not cause huge allocations.
+fn read_bytes<R: Read>(reader: &mut R, length: usize) -> Result<Vec<u8>, Error> {
+    let mut buffer: Vec<u8> = Vec::new();
+    reader.take(length as u64).read_to_end(&mut buffer)?;
+    if buffer.len() != length {
+        return Err(UnexpectedEndOfData().into());
+    }
+    Ok(buffer)
+}
+
+fn read_compressed_data<R: Read>(reader: &mut R, header: &PcdHeader) -> Result<Vec<u8>, Error> {
+    let expected_size = header.points * header.point_size();
+    if expected_size == 0 {
+        return Ok(Vec::new());
+    }
+
+    let sizes = read_bytes(reader, 8)?;
+    let compressed_size = u32::from_le_bytes(sizes[0..4].try_into().expect("must be 4 bytes"));
+    let uncompressed_size = u32::from_le_bytes(sizes[4..8].try_into().expect("must be 4 bytes"));
+    if uncompressed_size as usize != expected_size {
+        return Err(InvalidHeader(format!(
+            "uncompressed size of {uncompressed_size} bytes does not match the point layout"
+        ))
+        .into());
+    }
+
+    let compressed_data = read_bytes(reader, compressed_size as usize)?;
+    let data = lzf::decompress(&compressed_data, expected_size)?;
+    Ok(data)
+}
+
+/// Parses the text point data into a point-wise buffer of little endian values.
+fn read_ascii_data<R: Read>(reader: &mut R, header: &PcdHeader) -> Result<Vec<u8>, Error> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let mut tokens = content.split_ascii_whitespace();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    for _ in 0..header.points {
+        for current_field in &header.fields {
+            for _ in 0..current_field.count {
+                let token = tokens.next().ok_or(UnexpectedEndOfData())?;
+                push_ascii_value(&mut buffer, current_field, token)?;
+            }
+        }
+    }
+
+    Ok(buffer)
+}
+
+fn push_ascii_value(buffer: &mut Vec<u8>, field: &PcdField, token: &str) -> Result<(), Error> {
+    // PCL writes packed colors as unsigned integers, regardless of the field type
+    if field.is_packed_color() {
+        let value = match token.parse::<u32>() {
+            Ok(value) => value,
+            Err(_) => parse_value::<f32>(token)?.to_bits(),
+        };
+        buffer.extend_from_slice(&value.to_le_bytes());
+        return Ok(());
+    }
+
+    match field.field_type {
+        PcdFieldType::Int8 => buffer.extend_from_slice(&parse_value::<i8>(token)?.to_le_bytes()),
+        PcdFieldType::Int16 => buffer.extend_from_slice(&parse_value::<i16>(token)?.to_le_bytes()),
+        PcdFieldType::Int32 => buffer.extend_from_slice(&parse_value::<i32>(token)?.to_le_bytes()),
+        PcdFieldType::Int64 => buffer.extend_from_slice(&parse_value::<i64>(token)?.to_le_bytes()),
+        PcdFieldType::UInt8 => buffer.extend_from_slice(&parse_value::<u8>(token)?.to_le_bytes()),
+        PcdFieldType::UInt16 => buffer.extend_from_slice(&parse_value::<u16>(token)?.to_le_bytes()),
+        PcdFieldType::UInt32 => buffer.extend_from_slice(&parse_value::<u32>(token)?.to_le_bytes()),
+        PcdFieldType::UInt64 => buffer.extend_from_slice(&parse_value::<u64>(token)?.to_le_bytes()),
+        PcdFieldType::Float32 => {
+            buffer.extend_from_slice(&parse_value::<f32>(token)?.to_le_bytes())
+        }
+        PcdFieldType::Float64 => {
+            buffer.extend_from_slice(&parse_value::<f64>(token)?.to_le_bytes())
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_to_data_frame(
+    header: &PcdHeader,
+    buffer: &[u8],
+    layout: DataLayout,
+) -> Result<DataFrame, Error> {
+    let point_size = header.point_size();
+    let mut point_data_columns: Vec<Column> = Vec::new();
+
+    let mut field_offset = 0;
+    for current_field in &header.fields {
+        let (field_start, stride) = match layout {
+            DataLayout::PointWise => (field_offset, point_size),
+            DataLayout::FieldWise => (field_offset * header.points, current_field.size()),
+        };
+        field_offset += current_field.size();
+
+        if current_field.name == PCD_FIELD_PADDING {
+            continue;
+        }
+
+        if current_field.is_packed_color() {
+            let packed_values: Vec<u32> = decode_values(
+                buffer,
+                field_start,
+                stride,
+                header.points,
+                u32::from_le_bytes,
+            );
+            for (column_type, shift) in [
+                (PointDataColumnType::ColorRed, 16),
+                (PointDataColumnType::ColorGreen, 8),
+                (PointDataColumnType::ColorBlue, 0),
+            ] {
+                let values: Vec<u16> = packed_values
+                    .iter()
+                    .map(|v| ((v >> shift) & 0xff) as u16 * 257)
+                    .collect();
+                point_data_columns.push(Column::new(column_type.into(), values));
+            }
+            continue;
+        }
+
+        for element_index in 0..current_field.count {
+            let name = if current_field.count == 1 {
+                current_field.name.clone()
+            } else {
+                format!("{}_{element_index}", current_field.name)
+            };
+            let element_start = field_start + element_index * current_field.field_type.size();
+
+            point_data_columns.push(decode_column(
+                &name,
+                current_field.field_type,
+                buffer,
+                element_start,
+                stride,
+                header.points,
+            ));
+        }
+    }
+
+    for field_name in [PCD_FIELD_X, PCD_FIELD_Y, PCD_FIELD_Z] {
+        if !point_data_columns
+            .iter()
+            .any(|c| c.name().as_str() == field_name)
+        {
+            return Err(MissingField(field_name).into());
+        }
+    }
+
+    // organized point clouds retain their image-like structure as grid indices
+    if header.height > 1 {
+        let row_index_values: Vec<u32> = (0..header.points)
+            .map(|i| (i / header.width) as u32)
+            .collect();
+        let column_index_values: Vec<u32> = (0..header.points)
+            .map(|i| (i % header.width) as u32)
+            .collect();
+        point_data_columns.push(Column::new(
+            PointDataColumnType::RowIndex.into(),
+            row_index_values,
+        ));
+        point_data_columns.push(Column::new(
+            PointDataColumnType::ColumnIndex.into(),
+            column_index_values,
+        ));
+    }
+
+    // fields carrying epoint column names (e.g. x, y, z, intensity and normals) get their native type
+    let data_frame = cast_data_frame(DataFrame::new(point_data_columns)?)?;
+    Ok(data_frame)
+}
+
+fn decode_column(
+    name: &str,
+    field_type: PcdFieldType,
+    buffer: &[u8],
+    start: usize,
+    stride: usize,
+    points: usize,
+) -> Column {
+    match field_type {
+        PcdFieldType::Int8 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, i8::from_le_bytes),
+        ),
+        PcdFieldType::Int16 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, i16::from_le_bytes),
+        ),
+        PcdFieldType::Int32 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, i32::from_le_bytes),
+        ),
+        PcdFieldType::Int64 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, i64::from_le_bytes),
+        ),
+        PcdFieldType::UInt8 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, u8::from_le_bytes),
+        ),
+        PcdFieldType::UInt16 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, u16::from_le_bytes),
+        ),
+        PcdFieldType::UInt32 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, u32::from_le_bytes),
+        ),
+        PcdFieldType::UInt64 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, u64::from_le_bytes),
+        ),
+        PcdFieldType::Float32 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, f32::from_le_bytes),
+        ),
+        PcdFieldType::Float64 => Column::new(
+            name.into(),
+            decode_values(buffer, start, stride, points, f64::from_le_bytes),
+        ),
+    }
+}
+
+fn decode_values<T, const N: usize>(
+    buffer: &[u8],
+    start: usize,
+    stride: usize,
+    points: usize,
+    from_le_bytes: fn([u8; N]) -> T,
+) -> Vec<T> {
+    (0..points)
+        .map(|i| {
+            let offset = start + i * stride;
+            from_le_bytes(
+                buffer[offset..offset + N]
+                    .try_into()
+                    .expect("buffer must contain all points"),
+            )
+        })
+        .collect()
+}
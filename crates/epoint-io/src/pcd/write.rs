@@ -0,0 +1,91 @@
+use crate::Error;
+use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::pcd::write_impl::write_pcd_format;
+use crate::pcd::{FILE_EXTENSION_PCD_FORMAT, PcdEncoding};
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// `PcdWriter` exports a point cloud as PCL PCD file.
+///
+/// Coordinates are written as single precision `F 4` fields by default, as required by PCL point
+/// types such as `pcl::PointXYZ`. Double precision coordinates can be kept for georeferenced point
+/// clouds, which would otherwise lose precision. Colors are written as packed 8 bit `rgb` field. If the point cloud contains `row_index` and
+/// `column_index` columns describing a complete grid, it is written as organized point cloud.
+/// The `VIEWPOINT` is derived from the static transform between the viewpoint reference frame
+/// (global by default) and the frame of the point cloud, if available.
+///
+/// Columns without a numeric representation, such as `frame_id`, are not written.
+#[derive(Debug, Clone)]
+pub struct PcdWriter<W: Write> {
+    writer: W,
+    frame_id: Option<FrameId>,
+    viewpoint_reference_frame_id: FrameId,
+    encoding: PcdEncoding,
+    double_precision_coordinates: bool,
+}
+
+impl<W: Write> PcdWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            frame_id: None,
+            viewpoint_reference_frame_id: FrameId::global(),
+            encoding: PcdEncoding::default(),
+            double_precision_coordinates: false,
+        }
+    }
+
+    pub fn with_frame_id(mut self, frame_id: FrameId) -> Self {
+        self.frame_id = Some(frame_id);
+        self
+    }
+
+    pub fn with_viewpoint_reference_frame_id(mut self, frame_id: FrameId) -> Self {
+        self.viewpoint_reference_frame_id = frame_id;
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: PcdEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Writes the coordinates as `F 8` fields, which standard PCL point types cannot load.
+    pub fn with_double_precision_coordinates(mut self, double_precision_coordinates: bool) -> Self {
+        self.double_precision_coordinates = double_precision_coordinates;
+        self
+    }
+
+    pub fn finish(self, mut point_cloud: PointCloud) -> Result<(), Error> {
+        if let Some(frame_id) = self.frame_id {
+            point_cloud.resolve_to_frame(frame_id)?;
+        }
+
+        write_pcd_format(
+            self.writer,
+            point_cloud,
+            &self.viewpoint_reference_frame_id,
+            self.encoding,
+            self.double_precision_coordinates,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl PcdWriter<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+        if extension != FILE_EXTENSION_PCD_FORMAT {
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::create(path)?;
+        Ok(Self::new(file))
+    }
+}
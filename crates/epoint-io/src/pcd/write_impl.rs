@@ -0,0 +1,372 @@
+use crate::Error;
+use crate::pcd::error::Error::CompressedDataTooLarge;
+use crate::pcd::{PCD_FIELD_RGB, PCD_FORMAT_VERSION, PcdEncoding, PcdField, PcdFieldType, lzf};
+use ecoord::{FrameId, Transform, TransformId};
+use epoint_core::{PointCloud, PointDataColumnType};
+use nalgebra::Isometry3;
+use polars::prelude::{ChunkAgg, Column, DataFrame, DataType, SortMultipleOptions};
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+
+/// Values of a PCD field as little endian bytes, stored point after point.
+#[derive(Debug, Clone, PartialEq)]
+struct PcdFieldValues {
+    field: PcdField,
+    bytes: Vec<u8>,
+}
+
+impl PcdFieldValues {
+    fn point_bytes(&self, index: usize) -> &[u8] {
+        let size = self.field.size();
+        &self.bytes[index * size..(index + 1) * size]
+    }
+}
+
+pub fn write_pcd_format<W: Write>(
+    writer: W,
+    point_cloud: PointCloud,
+    viewpoint_reference_frame_id: &FrameId,
+    encoding: PcdEncoding,
+    double_precision_coordinates: bool,
+) -> Result<(), Error> {
+    let viewpoint = derive_viewpoint(&point_cloud, viewpoint_reference_frame_id);
+    let (data_frame, grid_dimensions) = organize_data_frame(&point_cloud.point_data.data_frame)?;
+    let number_of_points = data_frame.height();
+    let (width, height) = grid_dimensions.unwrap_or((number_of_points, 1));
+
+    let field_values = convert_columns(
+        &data_frame,
+        grid_dimensions.is_some(),
+        double_precision_coordinates,
+    )?;
+
+    let mut writer = BufWriter::new(writer);
+
+    // header
+    writeln!(
+        writer,
+        "# .PCD v{PCD_FORMAT_VERSION} - Point Cloud Data file format"
+    )?;
+    writeln!(writer, "VERSION {PCD_FORMAT_VERSION}")?;
+    write_header_line(&mut writer, "FIELDS", &field_values, |f| f.name.clone())?;
+    write_header_line(&mut writer, "SIZE", &field_values, |f| {
+        f.field_type.size().to_string()
+    })?;
+    write_header_line(&mut writer, "TYPE", &field_values, |f| {
+        f.field_type.type_name().to_string()
+    })?;
+    write_header_line(&mut writer, "COUNT", &field_values, |f| f.count.to_string())?;
+    writeln!(writer, "WIDTH {width}")?;
+    writeln!(writer, "HEIGHT {height}")?;
+    writeln!(
+        writer,
+        "VIEWPOINT {} {} {} {} {} {} {}",
+        viewpoint.translation.x,
+        viewpoint.translation.y,
+        viewpoint.translation.z,
+        viewpoint.rotation.w,
+        viewpoint.rotation.i,
+        viewpoint.rotation.j,
+        viewpoint.rotation.k
+    )?;
+    writeln!(writer, "POINTS {number_of_points}")?;
+    writeln!(writer, "DATA {}", encoding.as_str())?;
+
+    // point data
+    match encoding {
+        PcdEncoding::Ascii => {
+            for i in 0..number_of_points {
+                let line: Vec<String> = field_values
+                    .iter()
+                    .map(|v| format_ascii_value(&v.field, v.point_bytes(i)))
+                    .collect();
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+        }
+        PcdEncoding::Binary => {
+            for i in 0..number_of_points {
+                for current_values in &field_values {
+                    writer.write_all(current_values.point_bytes(i))?;
+                }
+            }
+        }
+        PcdEncoding::BinaryCompressed => {
+            let uncompressed_data: Vec<u8> = field_values
+                .iter()
+                .flat_map(|v| v.bytes.iter().copied())
+                .collect();
+            if !uncompressed_data.is_empty() {
+                // both sizes are stored as 32 bit values
+                let to_stored_size = |size: usize| {
+                    u32::try_from(size).map_err(|_| Error::from(CompressedDataTooLarge(size)))
+                };
+                let uncompressed_size = to_stored_size(uncompressed_data.len())?;
+                let compressed_data = lzf::compress(&uncompressed_data);
+                writer.write_all(&to_stored_size(compressed_data.len())?.to_le_bytes())?;
+                writer.write_all(&uncompressed_size.to_le_bytes())?;
+                writer.write_all(&compressed_data)?;
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_header_line<W: Write>(
+    writer: &mut W,
+    keyword: &str,
+    field_values: &[PcdFieldValues],
+    value: impl Fn(&PcdField) -> String,
+) -> Result<(), Error> {
+    let values: Vec<String> = field_values.iter().map(|v| value(&v.field)).collect();
+    writeln!(writer, "{keyword} {}", values.join(" "))?;
+    Ok(())
+}
+
+/// Returns the pose of the point cloud frame relative to the reference frame, or the identity if
+/// it cannot be determined.
+fn derive_viewpoint(point_cloud: &PointCloud, reference_frame_id: &FrameId) -> Transform {
+    let identity = Transform::from(Isometry3::identity());
+    let Some(frame_id) = point_cloud.info_frame_id() else {
+        return identity;
+    };
+    if frame_id == reference_frame_id {
+        return identity;
+    }
+
+    point_cloud
+        .transform_tree
+        .get_static_transform(&TransformId::new(
+            reference_frame_id.clone(),
+            frame_id.clone(),
+        ))
+        .unwrap_or(identity)
+}
+
+/// Sorts the points by their grid indices, if these describe a complete grid.
+///
+/// Returns the grid dimensions as width and height for organized point clouds.
+fn organize_data_frame(
+    data_frame: &DataFrame,
+) -> Result<(DataFrame, Option<(usize, usize)>), Error> {
+    let row_index_name = PointDataColumnType::RowIndex.as_str();
+    let column_index_name = PointDataColumnType::ColumnIndex.as_str();
+    let (Ok(row_indices), Ok(column_indices)) = (
+        data_frame.column(row_index_name),
+        data_frame.column(column_index_name),
+    ) else {
+        return Ok((data_frame.clone(), None));
+    };
+    if data_frame.height() == 0 {
+        return Ok((data_frame.clone(), None));
+    }
+
+    let width = column_indices.u32()?.max().unwrap_or_default() as usize + 1;
+    let height = row_indices.u32()?.max().unwrap_or_default() as usize + 1;
+    if width * height != data_frame.height() {
+        return Ok((data_frame.clone(), None));
+    }
+
+    let sorted_data_frame = data_frame.sort(
+        [row_index_name, column_index_name],
+        SortMultipleOptions::default(),
+    )?;
+    let is_complete_grid = sorted_data_frame
+        .column(row_index_name)?
+        .u32()?
+        .into_no_null_iter()
+        .zip(
+            sorted_data_frame
+                .column(column_index_name)?
+                .u32()?
+                .into_no_null_iter(),
+        )
+        .enumerate()
+        .all(|(i, (row, column))| row as usize == i / width && column as usize == i % width);
+    if !is_complete_grid {
+        return Ok((data_frame.clone(), None));
+    }
+
+    Ok((sorted_data_frame, Some((width, height))))
+}
+
+fn convert_columns(
+    data_frame: &DataFrame,
+    is_organized: bool,
+    double_precision_coordinates: bool,
+) -> Result<Vec<PcdFieldValues>, Error> {
+    let mut field_values: Vec<PcdFieldValues> = Vec::new();
+
+    for current_column in data_frame.get_columns() {
+        let column_type = PointDataColumnType::from_str(current_column.name().as_str()).ok();
+        match column_type {
+            // the grid structure is given by the point order
+            Some(PointDataColumnType::RowIndex | PointDataColumnType::ColumnIndex)
+                if is_organized => {}
+            // packed into a single field
+            Some(
+                PointDataColumnType::ColorRed
+                | PointDataColumnType::ColorGreen
+                | PointDataColumnType::ColorBlue,
+            ) => {}
+            // PCL point types such as `pcl::PointXYZ` require single precision coordinates
+            Some(PointDataColumnType::X | PointDataColumnType::Y | PointDataColumnType::Z)
+                if !double_precision_coordinates =>
+            {
+                if let Some(values) = convert_column(&current_column.cast(&DataType::Float32)?)? {
+                    field_values.push(values);
+                }
+            }
+            _ => {
+                if let Some(values) = convert_column(current_column)? {
+                    field_values.push(values);
+                }
+            }
+        }
+    }
+
+    if let Some(color_values) = convert_color_columns(data_frame)? {
+        // place colors directly after the coordinates, as commonly done by PCL
+        let index = field_values.len().min(3);
+        field_values.insert(index, color_values);
+    }
+
+    Ok(field_values)
+}
+
+/// Converts a point data column into a PCD field.
+///
+/// Returns `None` for columns that cannot be represented as PCD field.
+fn convert_column(column: &Column) -> Result<Option<PcdFieldValues>, Error> {
+    let (field_type, column) = match column.dtype() {
+        DataType::Int8 => (PcdFieldType::Int8, column.clone()),
+        DataType::Int16 => (PcdFieldType::Int16, column.clone()),
+        DataType::Int32 => (PcdFieldType::Int32, column.clone()),
+        DataType::Int64 => (PcdFieldType::Int64, column.clone()),
+        DataType::UInt8 => (PcdFieldType::UInt8, column.clone()),
+        DataType::Boolean => (PcdFieldType::UInt8, column.cast(&DataType::UInt8)?),
+        DataType::UInt16 => (PcdFieldType::UInt16, column.clone()),
+        DataType::UInt32 => (PcdFieldType::UInt32, column.clone()),
+        DataType::UInt64 => (PcdFieldType::UInt64, column.clone()),
+        DataType::Float32 => (PcdFieldType::Float32, column.clone()),
+        DataType::Float64 => (PcdFieldType::Float64, column.clone()),
+        _ => return Ok(None),
+    };
+
+    let bytes: Vec<u8> = match field_type {
+        PcdFieldType::Int8 => encode_values(column.i8()?.iter(), i8::to_le_bytes),
+        PcdFieldType::Int16 => encode_values(column.i16()?.iter(), i16::to_le_bytes),
+        PcdFieldType::Int32 => encode_values(column.i32()?.iter(), i32::to_le_bytes),
+        PcdFieldType::Int64 => encode_values(column.i64()?.iter(), i64::to_le_bytes),
+        PcdFieldType::UInt8 => encode_values(column.u8()?.iter(), u8::to_le_bytes),
+        PcdFieldType::UInt16 => encode_values(column.u16()?.iter(), u16::to_le_bytes),
+        PcdFieldType::UInt32 => encode_values(column.u32()?.iter(), u32::to_le_bytes),
+        PcdFieldType::UInt64 => encode_values(column.u64()?.iter(), u64::to_le_bytes),
+        PcdFieldType::Float32 => column
+            .f32()?
+            .iter()
+            .flat_map(|v| v.unwrap_or(f32::NAN).to_le_bytes())
+            .collect(),
+        PcdFieldType::Float64 => column
+            .f64()?
+            .iter()
+            .flat_map(|v| v.unwrap_or(f64::NAN).to_le_bytes())
+            .collect(),
+    };
+
+    Ok(Some(PcdFieldValues {
+        field: PcdField {
+            name: column.name().to_string(),
+            field_type,
+            count: 1,
+        },
+        bytes,
+    }))
+}
+
+fn encode_values<T: Default, const N: usize>(
+    values: impl Iterator<Item = Option<T>>,
+    to_le_bytes: fn(T) -> [u8; N],
+) -> Vec<u8> {
+    values
+        .flat_map(|v| to_le_bytes(v.unwrap_or_default()))
+        .collect()
+}
+
+/// Packs the color columns into an `rgb` field with 8 bit per channel, as expected by PCL.
+fn convert_color_columns(data_frame: &DataFrame) -> Result<Option<PcdFieldValues>, Error> {
+    let (Ok(red), Ok(green), Ok(blue)) = (
+        data_frame.column(PointDataColumnType::ColorRed.as_str()),
+        data_frame.column(PointDataColumnType::ColorGreen.as_str()),
+        data_frame.column(PointDataColumnType::ColorBlue.as_str()),
+    ) else {
+        return Ok(None);
+    };
+
+    let bytes: Vec<u8> = red
+        .u16()?
+        .iter()
+        .zip(green.u16()?.iter())
+        .zip(blue.u16()?.iter())
+        .flat_map(|((r, g), b)| {
+            let packed = (to_eight_bit(r.unwrap_or_default()) << 16)
+                | (to_eight_bit(g.unwrap_or_default()) << 8)
+                | to_eight_bit(b.unwrap_or_default());
+            packed.to_le_bytes()
+        })
+        .collect();
+
+    Ok(Some(PcdFieldValues {
+        field: PcdField {
+            name: PCD_FIELD_RGB.to_string(),
+            field_type: PcdFieldType::Float32,
+            count: 1,
+        },
+        bytes,
+    }))
+}
+
+fn to_eight_bit(value: u16) -> u32 {
+    (value as u32 * u8::MAX as u32 + 32_767) / u16::MAX as u32
+}
+
+fn format_ascii_value(field: &PcdField, bytes: &[u8]) -> String {
+    // PCL expects packed colors as unsigned integers
+    if field.is_packed_color() {
+        return u32::from_le_bytes(bytes.try_into().expect("must be 4 bytes")).to_string();
+    }
+
+    match field.field_type {
+        PcdFieldType::Int8 => {
+            i8::from_le_bytes(bytes.try_into().expect("must be 1 byte")).to_string()
+        }
+        PcdFieldType::Int16 => {
+            i16::from_le_bytes(bytes.try_into().expect("must be 2 bytes")).to_string()
+        }
+        PcdFieldType::Int32 => {
+            i32::from_le_bytes(bytes.try_into().expect("must be 4 bytes")).to_string()
+        }
+        PcdFieldType::Int64 => {
+            i64::from_le_bytes(bytes.try_into().expect("must be 8 bytes")).to_string()
+        }
+        PcdFieldType::UInt8 => {
+            u8::from_le_bytes(bytes.try_into().expect("must be 1 byte")).to_string()
+        }
+        PcdFieldType::UInt16 => {
+            u16::from_le_bytes(bytes.try_into().expect("must be 2 bytes")).to_string()
+        }
+        PcdFieldType::UInt32 => {
+            u32::from_le_bytes(bytes.try_into().expect("must be 4 bytes")).to_string()
+        }
+        PcdFieldType::UInt64 => {
+            u64::from_le_bytes(bytes.try_into().expect("must be 8 bytes")).to_string()
+        }
+        PcdFieldType::Float32 => {
+            f32::from_le_bytes(bytes.try_into().expect("must be 4 bytes")).to_string()
+        }
+        PcdFieldType::Float64 => {
+            f64::from_le_bytes(bytes.try_into().expect("must be 8 bytes")).to_string()
+        }
+    }
+}
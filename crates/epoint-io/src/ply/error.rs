@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("unknown property type `{0}`")]
+    UnknownPropertyType(String),
+    #[error("unknown format `{0}`")]
+    UnknownFormat(String),
+    #[error("file does not contain a vertex element")]
+    NoVertexElement(),
+    #[error("vertex element does not contain the property `{0}`")]
+    MissingProperty(&'static str),
+    #[error("value `{0}` cannot be parsed")]
+    InvalidValue(String),
+    #[error("unexpected end of element data")]
+    UnexpectedEndOfData(),
+}
@@ -0,0 +1,119 @@
+use crate::ply::error::Error::UnknownPropertyType;
+
+pub mod error;
+pub mod read;
+mod read_impl;
+mod values;
+pub mod write;
+mod write_impl;
+
+pub const FILE_EXTENSION_PLY_FORMAT: &str = "ply";
+
+const PLY_MAGIC_NUMBER: &str = "ply";
+const PLY_FORMAT_VERSION: &str = "1.0";
+const PLY_END_HEADER: &str = "end_header";
+const PLY_ELEMENT_VERTEX: &str = "vertex";
+
+const PLY_PROPERTY_X: &str = "x";
+const PLY_PROPERTY_Y: &str = "y";
+const PLY_PROPERTY_Z: &str = "z";
+const PLY_PROPERTY_RED: &str = "red";
+const PLY_PROPERTY_GREEN: &str = "green";
+const PLY_PROPERTY_BLUE: &str = "blue";
+const PLY_PROPERTY_DIFFUSE_RED: &str = "diffuse_red";
+const PLY_PROPERTY_DIFFUSE_GREEN: &str = "diffuse_green";
+const PLY_PROPERTY_DIFFUSE_BLUE: &str = "diffuse_blue";
+const PLY_PROPERTY_INTENSITY: &str = "intensity";
+/// Intensity property name as written by CloudCompare.
+const PLY_PROPERTY_SCALAR_INTENSITY: &str = "scalar_intensity";
+const PLY_PROPERTY_NORMAL_X: &str = "nx";
+const PLY_PROPERTY_NORMAL_Y: &str = "ny";
+const PLY_PROPERTY_NORMAL_Z: &str = "nz";
+
+/// Encoding of the element data following the PLY header.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
+pub enum PlyEncoding {
+    /// Whitespace separated text, one element per line.
+    Ascii,
+    /// Binary data in little endian byte order.
+    #[default]
+    BinaryLittleEndian,
+    /// Binary data in big endian byte order.
+    BinaryBigEndian,
+}
+
+impl PlyEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlyEncoding::Ascii => "ascii",
+            PlyEncoding::BinaryLittleEndian => "binary_little_endian",
+            PlyEncoding::BinaryBigEndian => "binary_big_endian",
+        }
+    }
+}
+
+impl std::str::FromStr for PlyEncoding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(PlyEncoding::Ascii),
+            "binary_little_endian" => Ok(PlyEncoding::BinaryLittleEndian),
+            "binary_big_endian" => Ok(PlyEncoding::BinaryBigEndian),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Scalar types of PLY properties.
+///
+/// See also: <https://paulbourke.net/dataformats/ply/>
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum PlyScalarType {
+    Char,
+    UChar,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Float,
+    Double,
+}
+
+impl PlyScalarType {
+    fn from_name(name: &str) -> Result<Self, error::Error> {
+        match name {
+            "char" | "int8" => Ok(PlyScalarType::Char),
+            "uchar" | "uint8" => Ok(PlyScalarType::UChar),
+            "short" | "int16" => Ok(PlyScalarType::Short),
+            "ushort" | "uint16" => Ok(PlyScalarType::UShort),
+            "int" | "int32" => Ok(PlyScalarType::Int),
+            "uint" | "uint32" => Ok(PlyScalarType::UInt),
+            "float" | "float32" => Ok(PlyScalarType::Float),
+            "double" | "float64" => Ok(PlyScalarType::Double),
+            _ => Err(UnknownPropertyType(name.to_string())),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            PlyScalarType::Char => "char",
+            PlyScalarType::UChar => "uchar",
+            PlyScalarType::Short => "short",
+            PlyScalarType::UShort => "ushort",
+            PlyScalarType::Int => "int",
+            PlyScalarType::UInt => "uint",
+            PlyScalarType::Float => "float",
+            PlyScalarType::Double => "double",
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            PlyScalarType::Char | PlyScalarType::UChar => 1,
+            PlyScalarType::Short | PlyScalarType::UShort => 2,
+            PlyScalarType::Int | PlyScalarType::UInt | PlyScalarType::Float => 4,
+            PlyScalarType::Double => 8,
+        }
+    }
+}
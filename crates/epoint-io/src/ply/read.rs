@@ -0,0 +1,49 @@
+use crate::Error;
+use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::ply::FILE_EXTENSION_PLY_FORMAT;
+use crate::ply::read_impl::import_point_cloud_from_ply_reader;
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// `PlyReader` imports a point cloud from the vertices of an ASCII or binary PLY file.
+///
+/// Known vertex properties (`x`, `y`, `z`, `red`, `green`, `blue`, `intensity`, `nx`, `ny`, `nz`)
+/// are mapped onto the corresponding point data columns, all remaining scalar properties are
+/// kept as additional columns with their original name and type.
+#[derive(Debug, Clone)]
+pub struct PlyReader<R: Read> {
+    reader: R,
+    reference_frame_id: FrameId,
+}
+
+impl<R: Read> PlyReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            reference_frame_id: FrameId::global(),
+        }
+    }
+
+    pub fn finish(self) -> Result<PointCloud, Error> {
+        let point_cloud = import_point_cloud_from_ply_reader(self.reader, self.reference_frame_id)?;
+
+        Ok(point_cloud)
+    }
+}
+
+impl PlyReader<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+        if extension != FILE_EXTENSION_PLY_FORMAT {
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::open(path)?;
+        Ok(Self::new(file))
+    }
+}
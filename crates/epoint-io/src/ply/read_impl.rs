@@ -0,0 +1,317 @@
+use crate::Error;
+use crate::epoint::read_impl::cast_data_frame;
+use crate::ply::error::Error::{
+    InvalidHeader, InvalidValue, MissingProperty, NoVertexElement, UnexpectedEndOfData,
+    UnknownFormat,
+};
+use crate::ply::values::{PlyValues, read_binary_count};
+use crate::ply::{
+    PLY_ELEMENT_VERTEX, PLY_END_HEADER, PLY_MAGIC_NUMBER, PLY_PROPERTY_BLUE,
+    PLY_PROPERTY_DIFFUSE_BLUE, PLY_PROPERTY_DIFFUSE_GREEN, PLY_PROPERTY_DIFFUSE_RED,
+    PLY_PROPERTY_GREEN, PLY_PROPERTY_INTENSITY, PLY_PROPERTY_NORMAL_X, PLY_PROPERTY_NORMAL_Y,
+    PLY_PROPERTY_NORMAL_Z, PLY_PROPERTY_RED, PLY_PROPERTY_SCALAR_INTENSITY, PLY_PROPERTY_X,
+    PLY_PROPERTY_Y, PLY_PROPERTY_Z, PlyEncoding, PlyScalarType,
+};
+use ecoord::{FrameId, TransformTree};
+use epoint_core::{PointCloud, PointCloudInfo, PointDataColumnType};
+use polars::prelude::{Column, DataFrame};
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlyHeader {
+    encoding: PlyEncoding,
+    elements: Vec<PlyElement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PlyProperty {
+    Scalar {
+        name: String,
+        scalar_type: PlyScalarType,
+    },
+    List {
+        name: String,
+        count_type: PlyScalarType,
+        item_type: PlyScalarType,
+    },
+}
+
+pub fn import_point_cloud_from_ply_reader<R: Read>(
+    reader: R,
+    reference_frame_id: FrameId,
+) -> Result<PointCloud, Error> {
+    let mut reader = BufReader::new(reader);
+    let header = read_header(&mut reader)?;
+
+    let vertex_values = match header.encoding {
+        PlyEncoding::Ascii => read_ascii_vertex_values(&mut reader, &header)?,
+        PlyEncoding::BinaryLittleEndian | PlyEncoding::BinaryBigEndian => {
+            read_binary_vertex_values(&mut reader, &header)?
+        }
+    };
+
+    let point_data = convert_to_data_frame(vertex_values)?;
+    let info = PointCloudInfo::new(Some(reference_frame_id));
+    let point_cloud = PointCloud::from_data_frame(point_data, info, TransformTree::default())?;
+
+    Ok(point_cloud)
+}
+
+fn read_header<R: BufRead>(reader: &mut R) -> Result<PlyHeader, Error> {
+    let mut encoding: Option<PlyEncoding> = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+
+    let magic_number = read_header_line(reader)?;
+    if magic_number != PLY_MAGIC_NUMBER {
+        return Err(InvalidHeader("missing magic number".to_string()).into());
+    }
+
+    loop {
+        let line = read_header_line(reader)?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.as_slice() {
+            [] => continue,
+            [PLY_END_HEADER] => break,
+            ["comment", ..] | ["obj_info", ..] => continue,
+            ["format", format, _version] => {
+                encoding = Some(
+                    PlyEncoding::from_str(format).map_err(|_| UnknownFormat(format.to_string()))?,
+                );
+            }
+            ["element", name, count] => {
+                let count = count
+                    .parse::<usize>()
+                    .map_err(|_| InvalidValue(count.to_string()))?;
+                elements.push(PlyElement {
+                    name: name.to_string(),
+                    count,
+                    properties: Vec::new(),
+                });
+            }
+            ["property", "list", count_type, item_type, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or(InvalidHeader("property defined before element".to_string()))?;
+                element.properties.push(PlyProperty::List {
+                    name: name.to_string(),
+                    count_type: PlyScalarType::from_name(count_type)?,
+                    item_type: PlyScalarType::from_name(item_type)?,
+                });
+            }
+            ["property", scalar_type, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or(InvalidHeader("property defined before element".to_string()))?;
+                element.properties.push(PlyProperty::Scalar {
+                    name: name.to_string(),
+                    scalar_type: PlyScalarType::from_name(scalar_type)?,
+                });
+            }
+            _ => return Err(InvalidHeader(format!("unknown line `{line}`")).into()),
+        }
+    }
+
+    let encoding = encoding.ok_or(InvalidHeader("missing format".to_string()))?;
+    Ok(PlyHeader { encoding, elements })
+}
+
+fn read_header_line<R: BufRead>(reader: &mut R) -> Result<String, Error> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let read_bytes = reader.read_until(b'\n', &mut buffer)?;
+    if read_bytes == 0 {
+        return Err(InvalidHeader("missing end_header".to_string()).into());
+    }
+
+    let line = String::from_utf8_lossy(&buffer).trim().to_string();
+    Ok(line)
+}
+
+fn create_vertex_values(header: &PlyHeader) -> Result<Vec<(String, PlyValues)>, Error> {
+    let vertex_element = header
+        .elements
+        .iter()
+        .find(|e| e.name == PLY_ELEMENT_VERTEX)
+        .ok_or(NoVertexElement())?;
+
+    // the values grow while reading, since the vertex count of the header is not trusted
+    let vertex_values = vertex_element
+        .properties
+        .iter()
+        .filter_map(|p| match p {
+            PlyProperty::Scalar { name, scalar_type } => {
+                Some((name.clone(), PlyValues::new(*scalar_type)))
+            }
+            PlyProperty::List { .. } => None,
+        })
+        .collect();
+
+    Ok(vertex_values)
+}
+
+fn read_binary_vertex_values<R: Read>(
+    reader: &mut R,
+    header: &PlyHeader,
+) -> Result<Vec<(String, PlyValues)>, Error> {
+    let mut vertex_values = create_vertex_values(header)?;
+
+    for current_element in &header.elements {
+        let is_vertex_element = current_element.name == PLY_ELEMENT_VERTEX;
+
+        for _ in 0..current_element.count {
+            let mut scalar_index = 0;
+            for current_property in &current_element.properties {
+                match current_property {
+                    PlyProperty::Scalar { scalar_type, .. } if !is_vertex_element => {
+                        skip_bytes(reader, scalar_type.size())?;
+                    }
+                    PlyProperty::Scalar { .. } => {
+                        vertex_values[scalar_index]
+                            .1
+                            .push_binary(reader, header.encoding)?;
+                        scalar_index += 1;
+                    }
+                    PlyProperty::List {
+                        count_type,
+                        item_type,
+                        ..
+                    } => {
+                        let count = read_binary_count(reader, *count_type, header.encoding)?;
+                        skip_bytes(reader, count * item_type.size())?;
+                    }
+                }
+            }
+        }
+
+        // elements following the vertices are not needed
+        if is_vertex_element {
+            break;
+        }
+    }
+
+    Ok(vertex_values)
+}
+
+fn read_ascii_vertex_values<R: Read>(
+    reader: &mut R,
+    header: &PlyHeader,
+) -> Result<Vec<(String, PlyValues)>, Error> {
+    let mut vertex_values = create_vertex_values(header)?;
+
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+    let mut tokens = content.split_ascii_whitespace();
+
+    for current_element in &header.elements {
+        let is_vertex_element = current_element.name == PLY_ELEMENT_VERTEX;
+
+        for _ in 0..current_element.count {
+            let mut scalar_index = 0;
+            for current_property in &current_element.properties {
+                match current_property {
+                    PlyProperty::Scalar { .. } if !is_vertex_element => {
+                        tokens.next().ok_or(UnexpectedEndOfData())?;
+                    }
+                    PlyProperty::Scalar { .. } => {
+                        let token = tokens.next().ok_or(UnexpectedEndOfData())?;
+                        vertex_values[scalar_index].1.push_ascii(token)?;
+                        scalar_index += 1;
+                    }
+                    PlyProperty::List { .. } => {
+                        let token = tokens.next().ok_or(UnexpectedEndOfData())?;
+                        let count = token
+                            .parse::<usize>()
+                            .map_err(|_| InvalidValue(token.to_string()))?;
+                        for _ in 0..count {
+                            tokens.next().ok_or(UnexpectedEndOfData())?;
+                        }
+                    }
+                }
+            }
+        }
+
+        if is_vertex_element {
+            break;
+        }
+    }
+
+    Ok(vertex_values)
+}
+
+fn skip_bytes<R: Read>(reader: &mut R, length: usize) -> Result<(), Error> {
+    let skipped = std::io::copy(&mut reader.take(length as u64), &mut std::io::sink())?;
+    if skipped != length as u64 {
+        return Err(UnexpectedEndOfData().into());
+    }
+
+    Ok(())
+}
+
+fn convert_to_data_frame(vertex_values: Vec<(String, PlyValues)>) -> Result<DataFrame, Error> {
+    let mut point_data_columns: Vec<Column> = Vec::with_capacity(vertex_values.len());
+
+    for (name, values) in vertex_values {
+        let column = match name.to_lowercase().as_str() {
+            PLY_PROPERTY_X => Column::new(PointDataColumnType::X.into(), values.to_f64_values()),
+            PLY_PROPERTY_Y => Column::new(PointDataColumnType::Y.into(), values.to_f64_values()),
+            PLY_PROPERTY_Z => Column::new(PointDataColumnType::Z.into(), values.to_f64_values()),
+            PLY_PROPERTY_RED | PLY_PROPERTY_DIFFUSE_RED => Column::new(
+                PointDataColumnType::ColorRed.into(),
+                values.to_color_values(),
+            ),
+            PLY_PROPERTY_GREEN | PLY_PROPERTY_DIFFUSE_GREEN => Column::new(
+                PointDataColumnType::ColorGreen.into(),
+                values.to_color_values(),
+            ),
+            PLY_PROPERTY_BLUE | PLY_PROPERTY_DIFFUSE_BLUE => Column::new(
+                PointDataColumnType::ColorBlue.into(),
+                values.to_color_values(),
+            ),
+            PLY_PROPERTY_INTENSITY | PLY_PROPERTY_SCALAR_INTENSITY => Column::new(
+                PointDataColumnType::Intensity.into(),
+                values.to_f32_values(),
+            ),
+            PLY_PROPERTY_NORMAL_X => {
+                Column::new(PointDataColumnType::NormalX.into(), values.to_f32_values())
+            }
+            PLY_PROPERTY_NORMAL_Y => {
+                Column::new(PointDataColumnType::NormalY.into(), values.to_f32_values())
+            }
+            PLY_PROPERTY_NORMAL_Z => {
+                Column::new(PointDataColumnType::NormalZ.into(), values.to_f32_values())
+            }
+            _ => values.into_column(&name),
+        };
+
+        // only the first property mapped to a column is kept, e.g. `red` before `diffuse_red`
+        if point_data_columns.iter().any(|c| c.name() == column.name()) {
+            continue;
+        }
+        point_data_columns.push(column);
+    }
+
+    for (column_type, property_name) in [
+        (PointDataColumnType::X, PLY_PROPERTY_X),
+        (PointDataColumnType::Y, PLY_PROPERTY_Y),
+        (PointDataColumnType::Z, PLY_PROPERTY_Z),
+    ] {
+        if !point_data_columns
+            .iter()
+            .any(|c| c.name().as_str() == column_type.as_str())
+        {
+            return Err(MissingProperty(property_name).into());
+        }
+    }
+
+    // properties carrying epoint column names (e.g. written by `PlyWriter`) get their native type
+    let data_frame = cast_data_frame(DataFrame::new(point_data_columns)?)?;
+    Ok(data_frame)
+}
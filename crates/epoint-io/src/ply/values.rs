@@ -0,0 +1,260 @@
+use crate::Error;
+use crate::ply::error::Error::{InvalidValue, UnexpectedEndOfData};
+use crate::ply::{PlyEncoding, PlyScalarType};
+use polars::prelude::Column;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// Column-wise storage of the values of a single PLY scalar property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlyValues {
+    Char(Vec<i8>),
+    UChar(Vec<u8>),
+    Short(Vec<i16>),
+    UShort(Vec<u16>),
+    Int(Vec<i32>),
+    UInt(Vec<u32>),
+    Float(Vec<f32>),
+    Double(Vec<f64>),
+}
+
+impl PlyValues {
+    pub fn new(scalar_type: PlyScalarType) -> Self {
+        match scalar_type {
+            PlyScalarType::Char => PlyValues::Char(Vec::new()),
+            PlyScalarType::UChar => PlyValues::UChar(Vec::new()),
+            PlyScalarType::Short => PlyValues::Short(Vec::new()),
+            PlyScalarType::UShort => PlyValues::UShort(Vec::new()),
+            PlyScalarType::Int => PlyValues::Int(Vec::new()),
+            PlyScalarType::UInt => PlyValues::UInt(Vec::new()),
+            PlyScalarType::Float => PlyValues::Float(Vec::new()),
+            PlyScalarType::Double => PlyValues::Double(Vec::new()),
+        }
+    }
+
+    pub fn scalar_type(&self) -> PlyScalarType {
+        match self {
+            PlyValues::Char(_) => PlyScalarType::Char,
+            PlyValues::UChar(_) => PlyScalarType::UChar,
+            PlyValues::Short(_) => PlyScalarType::Short,
+            PlyValues::UShort(_) => PlyScalarType::UShort,
+            PlyValues::Int(_) => PlyScalarType::Int,
+            PlyValues::UInt(_) => PlyScalarType::UInt,
+            PlyValues::Float(_) => PlyScalarType::Float,
+            PlyValues::Double(_) => PlyScalarType::Double,
+        }
+    }
+
+    pub fn push_binary<R: Read>(
+        &mut self,
+        reader: &mut R,
+        encoding: PlyEncoding,
+    ) -> Result<(), Error> {
+        let big_endian = encoding == PlyEncoding::BinaryBigEndian;
+        match self {
+            PlyValues::Char(v) => v.push(i8::from_le_bytes(read_bytes(reader)?)),
+            PlyValues::UChar(v) => v.push(u8::from_le_bytes(read_bytes(reader)?)),
+            PlyValues::Short(v) => {
+                let bytes = read_bytes(reader)?;
+                v.push(if big_endian {
+                    i16::from_be_bytes(bytes)
+                } else {
+                    i16::from_le_bytes(bytes)
+                })
+            }
+            PlyValues::UShort(v) => {
+                let bytes = read_bytes(reader)?;
+                v.push(if big_endian {
+                    u16::from_be_bytes(bytes)
+                } else {
+                    u16::from_le_bytes(bytes)
+                })
+            }
+            PlyValues::Int(v) => {
+                let bytes = read_bytes(reader)?;
+                v.push(if big_endian {
+                    i32::from_be_bytes(bytes)
+                } else {
+                    i32::from_le_bytes(bytes)
+                })
+            }
+            PlyValues::UInt(v) => {
+                let bytes = read_bytes(reader)?;
+                v.push(if big_endian {
+                    u32::from_be_bytes(bytes)
+                } else {
+                    u32::from_le_bytes(bytes)
+                })
+            }
+            PlyValues::Float(v) => {
+                let bytes = read_bytes(reader)?;
+                v.push(if big_endian {
+                    f32::from_be_bytes(bytes)
+                } else {
+                    f32::from_le_bytes(bytes)
+                })
+            }
+            PlyValues::Double(v) => {
+                let bytes = read_bytes(reader)?;
+                v.push(if big_endian {
+                    f64::from_be_bytes(bytes)
+                } else {
+                    f64::from_le_bytes(bytes)
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn push_ascii(&mut self, token: &str) -> Result<(), Error> {
+        match self {
+            PlyValues::Char(v) => v.push(parse_token(token)?),
+            PlyValues::UChar(v) => v.push(parse_token(token)?),
+            PlyValues::Short(v) => v.push(parse_token(token)?),
+            PlyValues::UShort(v) => v.push(parse_token(token)?),
+            PlyValues::Int(v) => v.push(parse_token(token)?),
+            PlyValues::UInt(v) => v.push(parse_token(token)?),
+            PlyValues::Float(v) => v.push(parse_token(token)?),
+            PlyValues::Double(v) => v.push(parse_token(token)?),
+        }
+
+        Ok(())
+    }
+
+    pub fn write_binary<W: Write>(
+        &self,
+        writer: &mut W,
+        index: usize,
+        encoding: PlyEncoding,
+    ) -> Result<(), Error> {
+        let big_endian = encoding == PlyEncoding::BinaryBigEndian;
+        match self {
+            PlyValues::Char(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::UChar(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::Short(v) if big_endian => writer.write_all(&v[index].to_be_bytes())?,
+            PlyValues::Short(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::UShort(v) if big_endian => writer.write_all(&v[index].to_be_bytes())?,
+            PlyValues::UShort(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::Int(v) if big_endian => writer.write_all(&v[index].to_be_bytes())?,
+            PlyValues::Int(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::UInt(v) if big_endian => writer.write_all(&v[index].to_be_bytes())?,
+            PlyValues::UInt(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::Float(v) if big_endian => writer.write_all(&v[index].to_be_bytes())?,
+            PlyValues::Float(v) => writer.write_all(&v[index].to_le_bytes())?,
+            PlyValues::Double(v) if big_endian => writer.write_all(&v[index].to_be_bytes())?,
+            PlyValues::Double(v) => writer.write_all(&v[index].to_le_bytes())?,
+        }
+
+        Ok(())
+    }
+
+    pub fn format_ascii(&self, index: usize) -> String {
+        match self {
+            PlyValues::Char(v) => v[index].to_string(),
+            PlyValues::UChar(v) => v[index].to_string(),
+            PlyValues::Short(v) => v[index].to_string(),
+            PlyValues::UShort(v) => v[index].to_string(),
+            PlyValues::Int(v) => v[index].to_string(),
+            PlyValues::UInt(v) => v[index].to_string(),
+            PlyValues::Float(v) => v[index].to_string(),
+            PlyValues::Double(v) => v[index].to_string(),
+        }
+    }
+
+    pub fn to_f64_values(&self) -> Vec<f64> {
+        match self {
+            PlyValues::Char(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::UChar(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::Short(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::UShort(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::Int(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::UInt(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::Float(v) => v.iter().map(|x| *x as f64).collect(),
+            PlyValues::Double(v) => v.clone(),
+        }
+    }
+
+    pub fn to_f32_values(&self) -> Vec<f32> {
+        match self {
+            PlyValues::Float(v) => v.clone(),
+            _ => self.to_f64_values().iter().map(|x| *x as f32).collect(),
+        }
+    }
+
+    /// Returns the values as 16 bit color channel values.
+    ///
+    /// 8 bit values are scaled to the full 16 bit range and floating point values are expected to
+    /// be normalized to `[0, 1]`.
+    pub fn to_color_values(&self) -> Vec<u16> {
+        match self {
+            PlyValues::UChar(v) => v.iter().map(|x| *x as u16 * 257).collect(),
+            PlyValues::UShort(v) => v.clone(),
+            PlyValues::Float(_) | PlyValues::Double(_) => self
+                .to_f64_values()
+                .iter()
+                .map(|x| (x.clamp(0.0, 1.0) * u16::MAX as f64).round() as u16)
+                .collect(),
+            _ => self
+                .to_f64_values()
+                .iter()
+                .map(|x| x.clamp(0.0, u16::MAX as f64) as u16)
+                .collect(),
+        }
+    }
+
+    pub fn into_column(self, name: &str) -> Column {
+        match self {
+            PlyValues::Char(v) => Column::new(name.into(), v),
+            PlyValues::UChar(v) => Column::new(name.into(), v),
+            PlyValues::Short(v) => Column::new(name.into(), v),
+            PlyValues::UShort(v) => Column::new(name.into(), v),
+            PlyValues::Int(v) => Column::new(name.into(), v),
+            PlyValues::UInt(v) => Column::new(name.into(), v),
+            PlyValues::Float(v) => Column::new(name.into(), v),
+            PlyValues::Double(v) => Column::new(name.into(), v),
+        }
+    }
+}
+
+/// Reads a scalar value of the given type and returns it as usize, as needed for list counts.
+pub fn read_binary_count<R: Read>(
+    reader: &mut R,
+    scalar_type: PlyScalarType,
+    encoding: PlyEncoding,
+) -> Result<usize, Error> {
+    let mut values = PlyValues::new(scalar_type);
+    values.push_binary(reader, encoding)?;
+    let count = values.to_f64_values()[0];
+    if count < 0.0 {
+        return Err(InvalidValue(count.to_string()).into());
+    }
+
+    Ok(count as usize)
+}
+
+fn read_bytes<const N: usize, R: Read>(reader: &mut R) -> Result<[u8; N], Error> {
+    let mut buffer = [0u8; N];
+    reader.read_exact(&mut buffer).map_err(|e| match e.kind() {
+        std::io::ErrorKind::UnexpectedEof => UnexpectedEndOfData().into(),
+        _ => Error::from(e),
+    })?;
+    Ok(buffer)
+}
+
+fn parse_token<T: FromStr>(token: &str) -> Result<T, Error> {
+    if let Ok(value) = token.parse::<T>() {
+        return Ok(value);
+    }
+
+    // some writers emit integral properties in floating point notation
+    let value = token
+        .parse::<f64>()
+        .map_err(|_| InvalidValue(token.to_string()))?;
+    if value.fract() != 0.0 {
+        return Err(InvalidValue(token.to_string()).into());
+    }
+    format!("{value:.0}")
+        .parse::<T>()
+        .map_err(|_| InvalidValue(token.to_string()).into())
+}
@@ -0,0 +1,70 @@
+use crate::Error::{InvalidFileExtension, NoFileExtension};
+use crate::ply::write_impl::write_ply_format;
+use crate::ply::{FILE_EXTENSION_PLY_FORMAT, PlyEncoding};
+use crate::{ColorDepth, Error};
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+/// `PlyWriter` exports a point cloud as vertices of a PLY file.
+///
+/// Columns without a numeric representation, such as `frame_id`, are not written.
+#[derive(Debug, Clone)]
+pub struct PlyWriter<W: Write> {
+    writer: W,
+    frame_id: Option<FrameId>,
+    encoding: PlyEncoding,
+    color_depth: ColorDepth,
+}
+
+impl<W: Write> PlyWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            frame_id: None,
+            encoding: PlyEncoding::default(),
+            color_depth: ColorDepth::default(),
+        }
+    }
+
+    pub fn with_frame_id(mut self, frame_id: FrameId) -> Self {
+        self.frame_id = Some(frame_id);
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: PlyEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn with_color_depth(mut self, color_depth: ColorDepth) -> Self {
+        self.color_depth = color_depth;
+        self
+    }
+
+    pub fn finish(self, mut point_cloud: PointCloud) -> Result<(), Error> {
+        if let Some(frame_id) = self.frame_id {
+            point_cloud.resolve_to_frame(frame_id)?;
+        }
+
+        write_ply_format(self.writer, point_cloud, self.encoding, self.color_depth)?;
+
+        Ok(())
+    }
+}
+
+impl PlyWriter<File> {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let extension = path.as_ref().extension().ok_or(NoFileExtension())?;
+        if extension != FILE_EXTENSION_PLY_FORMAT {
+            return Err(InvalidFileExtension(
+                extension.to_str().unwrap_or_default().to_string(),
+            ));
+        }
+
+        let file = File::create(path)?;
+        Ok(Self::new(file))
+    }
+}
@@ -0,0 +1,199 @@
+use crate::ply::values::PlyValues;
+use crate::ply::{
+    PLY_ELEMENT_VERTEX, PLY_END_HEADER, PLY_FORMAT_VERSION, PLY_MAGIC_NUMBER, PLY_PROPERTY_BLUE,
+    PLY_PROPERTY_GREEN, PLY_PROPERTY_INTENSITY, PLY_PROPERTY_NORMAL_X, PLY_PROPERTY_NORMAL_Y,
+    PLY_PROPERTY_NORMAL_Z, PLY_PROPERTY_RED, PLY_PROPERTY_X, PLY_PROPERTY_Y, PLY_PROPERTY_Z,
+    PlyEncoding,
+};
+use crate::{ColorDepth, Error};
+use epoint_core::{PointCloud, PointDataColumnType};
+use polars::prelude::{Column, DataType};
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+
+pub fn write_ply_format<W: Write>(
+    writer: W,
+    point_cloud: PointCloud,
+    encoding: PlyEncoding,
+    color_depth: ColorDepth,
+) -> Result<(), Error> {
+    let vertex_values: Vec<(String, PlyValues)> = point_cloud
+        .point_data
+        .data_frame
+        .get_columns()
+        .iter()
+        .map(|c| convert_column(c, color_depth))
+        .collect::<Result<Vec<_>, Error>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let mut writer = BufWriter::new(writer);
+
+    // header
+    writeln!(writer, "{PLY_MAGIC_NUMBER}")?;
+    writeln!(writer, "format {} {PLY_FORMAT_VERSION}", encoding.as_str())?;
+    writeln!(writer, "comment generated by epoint")?;
+    writeln!(
+        writer,
+        "element {PLY_ELEMENT_VERTEX} {}",
+        point_cloud.point_data.height()
+    )?;
+    for (name, values) in &vertex_values {
+        writeln!(writer, "property {} {name}", values.scalar_type().as_str())?;
+    }
+    writeln!(writer, "{PLY_END_HEADER}")?;
+
+    // vertex data
+    for i in 0..point_cloud.point_data.height() {
+        match encoding {
+            PlyEncoding::Ascii => {
+                let line: Vec<String> = vertex_values
+                    .iter()
+                    .map(|(_, v)| v.format_ascii(i))
+                    .collect();
+                writeln!(writer, "{}", line.join(" "))?;
+            }
+            PlyEncoding::BinaryLittleEndian | PlyEncoding::BinaryBigEndian => {
+                for (_, values) in &vertex_values {
+                    values.write_binary(&mut writer, i, encoding)?;
+                }
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Converts a point data column into a PLY property.
+///
+/// Returns `None` for columns that cannot be represented as PLY scalar property.
+fn convert_column(
+    column: &Column,
+    color_depth: ColorDepth,
+) -> Result<Option<(String, PlyValues)>, Error> {
+    let column_type = PointDataColumnType::from_str(column.name().as_str()).ok();
+
+    let property = match column_type {
+        Some(PointDataColumnType::X) => (PLY_PROPERTY_X, PlyValues::Double(f64_values(column)?)),
+        Some(PointDataColumnType::Y) => (PLY_PROPERTY_Y, PlyValues::Double(f64_values(column)?)),
+        Some(PointDataColumnType::Z) => (PLY_PROPERTY_Z, PlyValues::Double(f64_values(column)?)),
+        Some(PointDataColumnType::ColorRed) => {
+            (PLY_PROPERTY_RED, color_values(column, color_depth)?)
+        }
+        Some(PointDataColumnType::ColorGreen) => {
+            (PLY_PROPERTY_GREEN, color_values(column, color_depth)?)
+        }
+        Some(PointDataColumnType::ColorBlue) => {
+            (PLY_PROPERTY_BLUE, color_values(column, color_depth)?)
+        }
+        Some(PointDataColumnType::Intensity) => (
+            PLY_PROPERTY_INTENSITY,
+            PlyValues::Float(f32_values(column)?),
+        ),
+        Some(PointDataColumnType::NormalX) => {
+            (PLY_PROPERTY_NORMAL_X, PlyValues::Float(f32_values(column)?))
+        }
+        Some(PointDataColumnType::NormalY) => {
+            (PLY_PROPERTY_NORMAL_Y, PlyValues::Float(f32_values(column)?))
+        }
+        Some(PointDataColumnType::NormalZ) => {
+            (PLY_PROPERTY_NORMAL_Z, PlyValues::Float(f32_values(column)?))
+        }
+        _ => {
+            let Some(values) = convert_values(column)? else {
+                return Ok(None);
+            };
+            return Ok(Some((column.name().to_string(), values)));
+        }
+    };
+
+    Ok(Some((property.0.to_string(), property.1)))
+}
+
+fn convert_values(column: &Column) -> Result<Option<PlyValues>, Error> {
+    let values = match column.dtype() {
+        DataType::Int8 => {
+            PlyValues::Char(column.i8()?.iter().map(|v| v.unwrap_or_default()).collect())
+        }
+        DataType::UInt8 | DataType::Boolean => PlyValues::UChar(
+            column
+                .cast(&DataType::UInt8)?
+                .u8()?
+                .iter()
+                .map(|v| v.unwrap_or_default())
+                .collect(),
+        ),
+        DataType::Int16 => PlyValues::Short(
+            column
+                .i16()?
+                .iter()
+                .map(|v| v.unwrap_or_default())
+                .collect(),
+        ),
+        DataType::UInt16 => PlyValues::UShort(
+            column
+                .u16()?
+                .iter()
+                .map(|v| v.unwrap_or_default())
+                .collect(),
+        ),
+        DataType::Int32 => PlyValues::Int(
+            column
+                .i32()?
+                .iter()
+                .map(|v| v.unwrap_or_default())
+                .collect(),
+        ),
+        DataType::UInt32 => PlyValues::UInt(
+            column
+                .u32()?
+                .iter()
+                .map(|v| v.unwrap_or_default())
+                .collect(),
+        ),
+        DataType::Float32 => PlyValues::Float(f32_values(column)?),
+        // PLY has no 64 bit integer type
+        DataType::Float64 | DataType::Int64 | DataType::UInt64 => {
+            PlyValues::Double(f64_values(column)?)
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(values))
+}
+
+fn f64_values(column: &Column) -> Result<Vec<f64>, Error> {
+    let column = column.cast(&DataType::Float64)?;
+    let values = column
+        .f64()?
+        .iter()
+        .map(|v| v.unwrap_or(f64::NAN))
+        .collect();
+    Ok(values)
+}
+
+fn f32_values(column: &Column) -> Result<Vec<f32>, Error> {
+    let column = column.cast(&DataType::Float32)?;
+    let values = column
+        .f32()?
+        .iter()
+        .map(|v| v.unwrap_or(f32::NAN))
+        .collect();
+    Ok(values)
+}
+
+fn color_values(column: &Column, color_depth: ColorDepth) -> Result<PlyValues, Error> {
+    let values = column.u16()?.iter().map(|v| v.unwrap_or_default());
+
+    let values = match color_depth {
+        ColorDepth::EightBit => PlyValues::UChar(
+            values
+                .map(|v| ((v as u32 * u8::MAX as u32 + 32_767) / u16::MAX as u32) as u8)
+                .collect(),
+        ),
+        ColorDepth::SixteenBit => PlyValues::UShort(values.collect()),
+    };
+    Ok(values)
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+/// Document of the `metadata.json` describing a Potree 2.0 dataset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PotreeMetadataDocument {
+    pub version: String,
+    pub name: String,
+    pub description: String,
+    pub points: u64,
+    pub projection: String,
+    pub hierarchy: PotreeHierarchyDocument,
+    pub offset: [f64; 3],
+    pub scale: [f64; 3],
+    pub spacing: f64,
+    pub bounding_box: PotreeBoundingBoxDocument,
+    pub encoding: String,
+    pub attributes: Vec<PotreeAttributeDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PotreeHierarchyDocument {
+    /// Size of the hierarchy chunk of the root node in bytes.
+    pub first_chunk_size: u64,
+    /// Number of levels covered by a hierarchy chunk.
+    pub step_size: u32,
+    pub depth: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PotreeBoundingBoxDocument {
+    pub min: [f64; 3],
+    pub max: [f64; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PotreeAttributeDocument {
+    pub name: String,
+    pub description: String,
+    pub size: u32,
+    pub num_elements: u32,
+    pub element_size: u32,
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<[f64; 3]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<[f64; 3]>,
+}
+
+impl PotreeAttributeDocument {
+    pub fn new(
+        name: &str,
+        num_elements: u32,
+        element_size: u32,
+        data_type: &str,
+        min: Vec<f64>,
+        max: Vec<f64>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            size: num_elements * element_size,
+            num_elements,
+            element_size,
+            data_type: data_type.to_string(),
+            min,
+            max,
+            scale: None,
+            offset: None,
+        }
+    }
+}
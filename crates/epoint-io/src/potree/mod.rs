@@ -0,0 +1,10 @@
+mod documents;
+pub mod write;
+mod write_impl;
+
+pub const FILE_NAME_METADATA: &str = "metadata.json";
+pub const FILE_NAME_HIERARCHY: &str = "hierarchy.bin";
+pub const FILE_NAME_OCTREE: &str = "octree.bin";
+
+/// Size of a node entry in the hierarchy file.
+const HIERARCHY_NODE_SIZE: usize = 22;
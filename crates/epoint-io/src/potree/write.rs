@@ -0,0 +1,78 @@
+use crate::Error;
+use crate::potree::write_impl::write_potree_format;
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use nalgebra::Vector3;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_MAX_POINTS_PER_OCTANT: usize = 20_000;
+
+/// `PotreeWriter` exports a point cloud to a Potree 2.0 dataset for web visualization.
+///
+/// The dataset directory contains the `metadata.json`, `hierarchy.bin` and `octree.bin` files.
+/// The points are distributed to an octree, where each octant keeps a random subsample of at most
+/// the maximum number of points and passes the remaining points to its children. Coarser levels
+/// thus provide the levels of detail. Besides the positions, the intensity, classification and
+/// color attributes are written if available.
+///
+/// See also: <https://github.com/potree/potree>
+#[derive(Debug, Clone)]
+pub struct PotreeWriter {
+    directory_path: PathBuf,
+    frame_id: Option<FrameId>,
+    max_points_per_octant: usize,
+    scale: Vector3<f64>,
+    shuffle_seed_number: Option<u64>,
+}
+
+impl PotreeWriter {
+    pub fn new(directory_path: impl AsRef<Path>) -> Self {
+        Self {
+            directory_path: directory_path.as_ref().to_path_buf(),
+            frame_id: None,
+            max_points_per_octant: DEFAULT_MAX_POINTS_PER_OCTANT,
+            scale: Vector3::from_element(0.001),
+            shuffle_seed_number: Some(0),
+        }
+    }
+
+    pub fn with_frame_id(mut self, frame_id: FrameId) -> Self {
+        self.frame_id = Some(frame_id);
+        self
+    }
+
+    /// Sets the maximum number of points per octant, which is 20,000 by default.
+    pub fn with_max_points_per_octant(mut self, max_points_per_octant: usize) -> Self {
+        self.max_points_per_octant = max_points_per_octant;
+        self
+    }
+
+    /// Sets the scale factors of the coordinates, which are 0.001 by default.
+    pub fn with_scale(mut self, scale: Vector3<f64>) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the seed for shuffling the points before the subsampling, which is 0 by default.
+    ///
+    /// If `None`, the points are not shuffled and the subsample of each octant consists of the
+    /// first points in the order of the point cloud.
+    pub fn with_shuffle_seed_number(mut self, shuffle_seed_number: Option<u64>) -> Self {
+        self.shuffle_seed_number = shuffle_seed_number;
+        self
+    }
+
+    pub fn finish(self, mut point_cloud: PointCloud) -> Result<(), Error> {
+        if let Some(frame_id) = self.frame_id.clone() {
+            point_cloud.resolve_to_frame(frame_id)?;
+        };
+
+        write_potree_format(
+            &self.directory_path,
+            point_cloud,
+            self.max_points_per_octant,
+            self.scale,
+            self.shuffle_seed_number,
+        )
+    }
+}
@@ -0,0 +1,275 @@
+use crate::Error;
+use crate::Error::CoordinateScaleTooSmall;
+use crate::potree::documents::{
+    PotreeAttributeDocument, PotreeBoundingBoxDocument, PotreeHierarchyDocument,
+    PotreeMetadataDocument,
+};
+use crate::potree::{
+    FILE_NAME_HIERARCHY, FILE_NAME_METADATA, FILE_NAME_OCTREE, HIERARCHY_NODE_SIZE,
+};
+use ecoord::octree::{OctantIndex, StorageMode};
+use epoint_core::octree::PointCloudOctree;
+use epoint_core::{PointCloud, PointData};
+use nalgebra::{Point3, Vector3};
+use polars::prelude::ChunkAgg;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+const POTREE_VERSION: &str = "2.0";
+/// Number of cells per axis of the spacing grid of the root node.
+const ROOT_SPACING_CELLS: f64 = 128.0;
+
+const NODE_TYPE_NORMAL: u8 = 0;
+const NODE_TYPE_LEAF: u8 = 1;
+
+pub fn write_potree_format(
+    directory_path: &Path,
+    point_cloud: PointCloud,
+    max_points_per_octant: usize,
+    scale: Vector3<f64>,
+    shuffle_seed_number: Option<u64>,
+) -> Result<(), Error> {
+    if point_cloud.point_data.is_empty() {
+        return Err(epoint_core::Error::NoRemainingPoints.into());
+    }
+
+    let point_cloud_octree = PointCloudOctree::new(
+        point_cloud,
+        max_points_per_octant,
+        StorageMode::AllOctants,
+        shuffle_seed_number,
+    )?;
+    let point_data = &point_cloud_octree.point_cloud.point_data;
+    let bounding_box = point_cloud_octree.octree.bounds().bounding_box();
+    let enclosing_cube = point_cloud_octree.octree.bounds().enclosing_cube();
+    let offset = enclosing_cube.get_lower_bound();
+    let minimum_scale = scale.min();
+    if enclosing_cube.edge_length() / minimum_scale > i32::MAX as f64 {
+        return Err(CoordinateScaleTooSmall(minimum_scale));
+    }
+
+    let mut position_attribute = PotreeAttributeDocument::new(
+        "position",
+        3,
+        4,
+        "int32",
+        bounding_box.lower_bound().coords.as_slice().to_vec(),
+        bounding_box.upper_bound().coords.as_slice().to_vec(),
+    );
+    position_attribute.scale = Some(scale.into());
+    position_attribute.offset = Some(offset.into());
+    let mut attributes = vec![position_attribute];
+    attributes.extend(create_attribute_documents(point_data)?);
+
+    let node_indices = collect_nodes_breadth_first(&point_cloud_octree.cell_indices());
+    let node_index_set: HashSet<OctantIndex> = node_indices.iter().copied().collect();
+
+    fs::create_dir_all(directory_path)?;
+    let mut octree_writer = BufWriter::new(File::create(directory_path.join(FILE_NAME_OCTREE))?);
+    let mut hierarchy_data: Vec<u8> = Vec::with_capacity(node_indices.len() * HIERARCHY_NODE_SIZE);
+    let mut byte_offset: u64 = 0;
+    for current_index in &node_indices {
+        let (point_count, node_data) = match point_cloud_octree.octree.cell(*current_index) {
+            Some(points) if !points.is_empty() => {
+                let octant_point_cloud = point_cloud_octree.extract_octant(*current_index)?;
+                let node_data =
+                    encode_points(&octant_point_cloud.point_data, offset, scale, &attributes)?;
+                (points.len() as u32, node_data)
+            }
+            _ => (0, Vec::new()),
+        };
+        octree_writer.write_all(&node_data)?;
+
+        let child_mask = get_potree_children(current_index)
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| node_index_set.contains(c))
+            .fold(0u8, |mask, (i, _)| mask | (1 << i));
+        let node_type = if child_mask == 0 {
+            NODE_TYPE_LEAF
+        } else {
+            NODE_TYPE_NORMAL
+        };
+        hierarchy_data.push(node_type);
+        hierarchy_data.push(child_mask);
+        hierarchy_data.extend(point_count.to_le_bytes());
+        hierarchy_data.extend(byte_offset.to_le_bytes());
+        hierarchy_data.extend((node_data.len() as u64).to_le_bytes());
+        byte_offset += node_data.len() as u64;
+    }
+    octree_writer.flush()?;
+    fs::write(directory_path.join(FILE_NAME_HIERARCHY), &hierarchy_data)?;
+
+    // all nodes are stored in a single hierarchy chunk without proxy nodes
+    let depth = node_indices
+        .iter()
+        .map(|i| i.level)
+        .max()
+        .unwrap_or_default();
+    let metadata_document = PotreeMetadataDocument {
+        version: POTREE_VERSION.to_string(),
+        name: directory_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        description: String::new(),
+        points: point_data.height() as u64,
+        projection: String::new(),
+        hierarchy: PotreeHierarchyDocument {
+            first_chunk_size: hierarchy_data.len() as u64,
+            step_size: depth + 1,
+            depth,
+        },
+        offset: offset.into(),
+        scale: scale.into(),
+        spacing: enclosing_cube.edge_length() / ROOT_SPACING_CELLS,
+        bounding_box: PotreeBoundingBoxDocument {
+            min: offset.into(),
+            max: enclosing_cube.get_upper_bound().into(),
+        },
+        encoding: "DEFAULT".to_string(),
+        attributes,
+    };
+    let metadata_file = File::create(directory_path.join(FILE_NAME_METADATA))?;
+    serde_json::to_writer_pretty(metadata_file, &metadata_document)?;
+
+    Ok(())
+}
+
+/// Creates the documents of the available intensity, classification and color attributes.
+fn create_attribute_documents(
+    point_data: &PointData,
+) -> Result<Vec<PotreeAttributeDocument>, Error> {
+    let mut attributes: Vec<PotreeAttributeDocument> = Vec::new();
+
+    if point_data.contains_intensity_column() {
+        let values = point_data.get_intensity_values()?;
+        attributes.push(PotreeAttributeDocument::new(
+            "intensity",
+            1,
+            4,
+            "float",
+            vec![values.min().unwrap_or_default() as f64],
+            vec![values.max().unwrap_or_default() as f64],
+        ));
+    }
+    if point_data.contains_classification_column() {
+        let values = point_data.get_classification_values()?;
+        attributes.push(PotreeAttributeDocument::new(
+            "classification",
+            1,
+            1,
+            "uint8",
+            vec![values.min().unwrap_or_default() as f64],
+            vec![values.max().unwrap_or_default() as f64],
+        ));
+    }
+    if point_data.contains_colors() {
+        let values = [
+            point_data.get_color_red_values()?,
+            point_data.get_color_green_values()?,
+            point_data.get_color_blue_values()?,
+        ];
+        attributes.push(PotreeAttributeDocument::new(
+            "rgb",
+            3,
+            2,
+            "uint16",
+            values
+                .iter()
+                .map(|v| v.min().unwrap_or_default() as f64)
+                .collect(),
+            values
+                .iter()
+                .map(|v| v.max().unwrap_or_default() as f64)
+                .collect(),
+        ));
+    }
+
+    Ok(attributes)
+}
+
+/// Encodes the points with the attributes interleaved per point.
+fn encode_points(
+    point_data: &PointData,
+    offset: Point3<f64>,
+    scale: Vector3<f64>,
+    attributes: &[PotreeAttributeDocument],
+) -> Result<Vec<u8>, Error> {
+    let bytes_per_point: usize = attributes.iter().map(|a| a.size as usize).sum();
+    let mut data: Vec<Vec<u8>> = vec![Vec::with_capacity(bytes_per_point); point_data.height()];
+
+    for current_attribute in attributes {
+        match current_attribute.name.as_str() {
+            "position" => {
+                for (point_bytes, point) in data.iter_mut().zip(point_data.get_all_points()) {
+                    let quantized = (point - offset).component_div(&scale);
+                    quantized
+                        .iter()
+                        .for_each(|v| point_bytes.extend((v.round() as i32).to_le_bytes()));
+                }
+            }
+            "intensity" => {
+                let values = point_data.get_intensity_values()?;
+                for (point_bytes, value) in data.iter_mut().zip(values) {
+                    point_bytes.extend(value.unwrap_or_default().to_le_bytes());
+                }
+            }
+            "classification" => {
+                let values = point_data.get_classification_values()?;
+                for (point_bytes, value) in data.iter_mut().zip(values) {
+                    point_bytes.push(value.unwrap_or_default());
+                }
+            }
+            "rgb" => {
+                for (point_bytes, color) in data.iter_mut().zip(point_data.get_all_colors()?) {
+                    [color.red, color.green, color.blue]
+                        .iter()
+                        .for_each(|v| point_bytes.extend(v.to_le_bytes()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(data.concat())
+}
+
+/// Returns the children ordered by the Potree child index, which encodes the x, y and z offset
+/// in its third, second and first bit.
+fn get_potree_children(index: &OctantIndex) -> [OctantIndex; 8] {
+    std::array::from_fn(|i| {
+        let i = i as u64;
+        OctantIndex::new_unchecked(
+            index.level + 1,
+            2 * index.x + ((i >> 2) & 1),
+            2 * index.y + ((i >> 1) & 1),
+            2 * index.z + (i & 1),
+        )
+    })
+}
+
+/// Collects the octants containing points and their ancestors in breadth-first order, where
+/// siblings are ordered by the Potree child index.
+fn collect_nodes_breadth_first(cell_indices: &HashSet<OctantIndex>) -> Vec<OctantIndex> {
+    let all_indices: HashSet<OctantIndex> = cell_indices
+        .iter()
+        .flat_map(|i| i.get_ancestors().into_iter().chain(std::iter::once(*i)))
+        .collect();
+
+    let mut node_indices: Vec<OctantIndex> = Vec::with_capacity(all_indices.len());
+    let mut pending_indices: VecDeque<OctantIndex> = VecDeque::from([OctantIndex::origin()]);
+    while let Some(current_index) = pending_indices.pop_front() {
+        pending_indices.extend(
+            get_potree_children(&current_index)
+                .into_iter()
+                .filter(|c| all_indices.contains(c)),
+        );
+        node_indices.push(current_index);
+    }
+
+    node_indices
+}
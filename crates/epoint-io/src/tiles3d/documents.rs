@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Document of the `tileset.json` describing a 3D Tiles tileset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TilesetDocument {
+    pub asset: AssetDocument,
+    pub geometric_error: f64,
+    pub root: TileDocument,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetDocument {
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TileDocument {
+    pub bounding_volume: BoundingVolumeDocument,
+    pub geometric_error: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refine: Option<String>,
+    /// Column-major matrix transforming the tile's coordinates to the parent's coordinates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[f64; 16]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<ContentDocument>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TileDocument>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoundingVolumeDocument {
+    /// Center followed by the x, y and z half-axes.
+    #[serde(rename = "box")]
+    pub oriented_box: [f64; 12],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentDocument {
+    pub uri: String,
+}
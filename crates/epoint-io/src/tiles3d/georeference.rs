@@ -0,0 +1,157 @@
+use crate::Error;
+use crate::Error::UnsupportedCrs;
+use nalgebra::{Matrix4, Point3, Vector3};
+
+/// EPSG code of the Earth-centered, Earth-fixed system of WGS 84.
+const EPSG_ECEF: u32 = 4978;
+
+const WGS84_SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+const WGS84_FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+const UTM_SCALE_FACTOR: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+
+/// Universal Transverse Mercator zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UtmZone {
+    number: u32,
+    is_south: bool,
+}
+
+impl UtmZone {
+    /// Derives the zone of the WGS 84 (EPSG:326xx, EPSG:327xx) and ETRS89 (EPSG:258xx) UTM systems.
+    fn from_epsg(epsg: u32) -> Option<Self> {
+        match epsg {
+            32601..=32660 => Some(Self {
+                number: epsg - 32600,
+                is_south: false,
+            }),
+            32701..=32760 => Some(Self {
+                number: epsg - 32700,
+                is_south: true,
+            }),
+            25828..=25838 => Some(Self {
+                number: epsg - 25800,
+                is_south: false,
+            }),
+            _ => None,
+        }
+    }
+
+    fn central_meridian(&self) -> f64 {
+        (self.number as f64 * 6.0 - 183.0).to_radians()
+    }
+
+    /// Converts easting and northing to latitude and longitude in radians.
+    ///
+    /// See also: Snyder, J. P. (1987). Map projections: A working manual, p. 63.
+    fn to_geodetic(self, easting: f64, northing: f64) -> (f64, f64) {
+        let a = WGS84_SEMI_MAJOR_AXIS;
+        let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+        let ep2 = e2 / (1.0 - e2);
+        let false_northing = if self.is_south {
+            UTM_FALSE_NORTHING_SOUTH
+        } else {
+            0.0
+        };
+
+        let m = (northing - false_northing) / UTM_SCALE_FACTOR;
+        let mu = m / (a * (1.0 - e2 / 4.0 - 3.0 * e2.powi(2) / 64.0 - 5.0 * e2.powi(3) / 256.0));
+        let e1 = (1.0 - (1.0 - e2).sqrt()) / (1.0 + (1.0 - e2).sqrt());
+        let phi1 = mu
+            + (3.0 * e1 / 2.0 - 27.0 * e1.powi(3) / 32.0) * (2.0 * mu).sin()
+            + (21.0 * e1.powi(2) / 16.0 - 55.0 * e1.powi(4) / 32.0) * (4.0 * mu).sin()
+            + (151.0 * e1.powi(3) / 96.0) * (6.0 * mu).sin()
+            + (1097.0 * e1.powi(4) / 512.0) * (8.0 * mu).sin();
+
+        let c1 = ep2 * phi1.cos().powi(2);
+        let t1 = phi1.tan().powi(2);
+        let n1 = a / (1.0 - e2 * phi1.sin().powi(2)).sqrt();
+        let r1 = a * (1.0 - e2) / (1.0 - e2 * phi1.sin().powi(2)).powf(1.5);
+        let d = (easting - UTM_FALSE_EASTING) / (n1 * UTM_SCALE_FACTOR);
+
+        let latitude = phi1
+            - (n1 * phi1.tan() / r1)
+                * (d.powi(2) / 2.0
+                    - (5.0 + 3.0 * t1 + 10.0 * c1 - 4.0 * c1.powi(2) - 9.0 * ep2) * d.powi(4)
+                        / 24.0
+                    + (61.0 + 90.0 * t1 + 298.0 * c1 + 45.0 * t1.powi(2)
+                        - 252.0 * ep2
+                        - 3.0 * c1.powi(2))
+                        * d.powi(6)
+                        / 720.0);
+        let longitude = self.central_meridian()
+            + (d - (1.0 + 2.0 * t1 + c1) * d.powi(3) / 6.0
+                + (5.0 - 2.0 * c1 + 28.0 * t1 - 3.0 * c1.powi(2) + 8.0 * ep2 + 24.0 * t1.powi(2))
+                    * d.powi(5)
+                    / 120.0)
+                / phi1.cos();
+
+        (latitude, longitude)
+    }
+}
+
+/// Creates the transform of the tileset's local coordinates, which are relative to the origin, to
+/// the Earth-centered, Earth-fixed coordinates used by 3D Tiles.
+///
+/// Without a coordinate reference system, the local coordinates are only translated back to the
+/// origin. For UTM systems, the local frame is placed east-north-up on the ellipsoid at the origin,
+/// while the meridian convergence and the scale factor at the origin are compensated. The heights
+/// are considered as ellipsoidal heights.
+pub fn create_root_transform(
+    origin: Point3<f64>,
+    crs_epsg: Option<u32>,
+) -> Result<Matrix4<f64>, Error> {
+    let Some(crs_epsg) = crs_epsg else {
+        return Ok(Matrix4::new_translation(&origin.coords));
+    };
+    if crs_epsg == EPSG_ECEF {
+        return Ok(Matrix4::new_translation(&origin.coords));
+    }
+    let utm_zone = UtmZone::from_epsg(crs_epsg).ok_or(UnsupportedCrs(crs_epsg))?;
+
+    let (latitude, longitude) = utm_zone.to_geodetic(origin.x, origin.y);
+    let delta_longitude = longitude - utm_zone.central_meridian();
+    let convergence = (delta_longitude.tan() * latitude.sin()).atan();
+    let scale_factor =
+        UTM_SCALE_FACTOR / (1.0 - (latitude.cos() * delta_longitude.sin()).powi(2)).sqrt();
+
+    let east = Vector3::new(-longitude.sin(), longitude.cos(), 0.0);
+    let north = Vector3::new(
+        -latitude.sin() * longitude.cos(),
+        -latitude.sin() * longitude.sin(),
+        latitude.cos(),
+    );
+    let up = Vector3::new(
+        latitude.cos() * longitude.cos(),
+        latitude.cos() * longitude.sin(),
+        latitude.sin(),
+    );
+    // grid north deviates from true north by the meridian convergence
+    let grid_east = (east * convergence.cos() - north * convergence.sin()) / scale_factor;
+    let grid_north = (east * convergence.sin() + north * convergence.cos()) / scale_factor;
+
+    let mut transform = Matrix4::identity();
+    transform.fixed_view_mut::<3, 1>(0, 0).copy_from(&grid_east);
+    transform
+        .fixed_view_mut::<3, 1>(0, 1)
+        .copy_from(&grid_north);
+    transform.fixed_view_mut::<3, 1>(0, 2).copy_from(&up);
+    transform
+        .fixed_view_mut::<3, 1>(0, 3)
+        .copy_from(&geodetic_to_ecef(latitude, longitude, origin.z).coords);
+
+    Ok(transform)
+}
+
+fn geodetic_to_ecef(latitude: f64, longitude: f64, height: f64) -> Point3<f64> {
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let n = WGS84_SEMI_MAJOR_AXIS / (1.0 - e2 * latitude.sin().powi(2)).sqrt();
+
+    Point3::new(
+        (n + height) * latitude.cos() * longitude.cos(),
+        (n + height) * latitude.cos() * longitude.sin(),
+        (n * (1.0 - e2) + height) * latitude.sin(),
+    )
+}
@@ -0,0 +1,9 @@
+mod documents;
+mod georeference;
+mod pnts;
+pub mod write;
+mod write_impl;
+
+pub const FILE_NAME_TILESET: &str = "tileset.json";
+pub const DIRECTORY_NAME_TILES: &str = "tiles";
+pub const FILE_EXTENSION_PNTS_FORMAT: &str = "pnts";
@@ -0,0 +1,119 @@
+use crate::Error;
+use epoint_core::PointData;
+use nalgebra::{Point3, Vector3};
+use serde_json::json;
+
+const PNTS_MAGIC: &[u8; 4] = b"pnts";
+const PNTS_VERSION: u32 = 1;
+const PNTS_HEADER_SIZE: usize = 28;
+
+/// Encodes the points of a tile as `.pnts` payload.
+///
+/// The positions are quantized to 16 bits within the tile's cube, which is given by its center as
+/// `RTC_CENTER` and its half edge length. The colors are reduced to 8 bits. Intensity and
+/// classification values are stored as per-point properties in the batch table.
+///
+/// See also: <https://github.com/CesiumGS/3d-tiles/tree/main/specification/TileFormats/PointCloud>
+pub fn encode_pnts(
+    point_data: &PointData,
+    origin: Point3<f64>,
+    rtc_center: Point3<f64>,
+    half_edge_length: f64,
+) -> Result<Vec<u8>, Error> {
+    let quantized_volume_offset = Vector3::from_element(-half_edge_length);
+    let quantized_volume_scale = Vector3::from_element(2.0 * half_edge_length);
+
+    let mut feature_table_binary: Vec<u8> = Vec::with_capacity(point_data.height() * 9);
+    for current_point in point_data.get_all_points() {
+        let relative_position = current_point - origin - rtc_center.coords;
+        let quantized_position = (relative_position - quantized_volume_offset)
+            .component_div(&quantized_volume_scale)
+            * u16::MAX as f64;
+        quantized_position.iter().for_each(|v| {
+            feature_table_binary
+                .extend((v.round().clamp(0.0, u16::MAX as f64) as u16).to_le_bytes())
+        });
+    }
+    let mut feature_table = json!({
+        "POINTS_LENGTH": point_data.height(),
+        "RTC_CENTER": rtc_center.coords.as_slice(),
+        "QUANTIZED_VOLUME_OFFSET": quantized_volume_offset.as_slice(),
+        "QUANTIZED_VOLUME_SCALE": quantized_volume_scale.as_slice(),
+        "POSITION_QUANTIZED": { "byteOffset": 0 },
+    });
+    if point_data.contains_colors() {
+        feature_table["RGB"] = json!({ "byteOffset": feature_table_binary.len() });
+        for current_color in point_data.get_all_colors()? {
+            feature_table_binary.extend([
+                (current_color.red >> 8) as u8,
+                (current_color.green >> 8) as u8,
+                (current_color.blue >> 8) as u8,
+            ]);
+        }
+    }
+
+    let mut batch_table = json!({});
+    let mut batch_table_binary: Vec<u8> = Vec::new();
+    if point_data.contains_intensity_column() {
+        batch_table["intensity"] = json!({
+            "byteOffset": batch_table_binary.len(),
+            "componentType": "FLOAT",
+            "type": "SCALAR",
+        });
+        for current_value in point_data.get_intensity_values()? {
+            batch_table_binary.extend(current_value.unwrap_or_default().to_le_bytes());
+        }
+    }
+    if point_data.contains_classification_column() {
+        batch_table["classification"] = json!({
+            "byteOffset": batch_table_binary.len(),
+            "componentType": "UNSIGNED_BYTE",
+            "type": "SCALAR",
+        });
+        for current_value in point_data.get_classification_values()? {
+            batch_table_binary.push(current_value.unwrap_or_default());
+        }
+    }
+
+    // each section starts and ends at an 8-byte boundary of the payload
+    let mut feature_table_json = serde_json::to_vec(&feature_table)?;
+    pad_to_alignment(&mut feature_table_json, PNTS_HEADER_SIZE, b' ');
+    pad_to_alignment(&mut feature_table_binary, 0, 0);
+    let (mut batch_table_json, batch_table_binary) = if batch_table_binary.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        let mut batch_table_json = serde_json::to_vec(&batch_table)?;
+        pad_to_alignment(&mut batch_table_json, 0, b' ');
+        pad_to_alignment(&mut batch_table_binary, 0, 0);
+        (batch_table_json, batch_table_binary)
+    };
+
+    let byte_length = PNTS_HEADER_SIZE
+        + feature_table_json.len()
+        + feature_table_binary.len()
+        + batch_table_json.len()
+        + batch_table_binary.len();
+    let mut data: Vec<u8> = Vec::with_capacity(byte_length);
+    data.extend(PNTS_MAGIC);
+    data.extend(PNTS_VERSION.to_le_bytes());
+    for current_length in [
+        byte_length,
+        feature_table_json.len(),
+        feature_table_binary.len(),
+        batch_table_json.len(),
+        batch_table_binary.len(),
+    ] {
+        data.extend((current_length as u32).to_le_bytes());
+    }
+    data.append(&mut feature_table_json);
+    data.append(&mut feature_table_binary);
+    data.append(&mut batch_table_json);
+    data.extend(batch_table_binary);
+
+    Ok(data)
+}
+
+fn pad_to_alignment(data: &mut Vec<u8>, preceding_length: usize, padding: u8) {
+    let length = preceding_length + data.len();
+    data.resize(data.len() + (8 - length % 8) % 8, padding);
+}
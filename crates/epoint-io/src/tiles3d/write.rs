@@ -0,0 +1,70 @@
+use crate::Error;
+use crate::tiles3d::write_impl::write_tiles3d_format;
+use ecoord::FrameId;
+use epoint_core::PointCloud;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_MAX_POINTS_PER_TILE: usize = 50_000;
+
+/// `Tiles3dWriter` exports a point cloud to a Cesium 3D Tiles tileset.
+///
+/// The tileset directory contains the `tileset.json` and a `.pnts` file per tile in the `tiles`
+/// directory. The tiles correspond to the octants of an octree, where each octant keeps a random
+/// subsample of at most the maximum number of points and passes the remaining points to its
+/// children, which are additively refined. Besides the quantized positions, the colors are written
+/// to the feature table, and the intensity and classification values to the batch table.
+///
+/// If the frame info of the point cloud's frame holds an EPSG code, the tileset is
+/// georeferenced by the root tile's transform. Supported are the Earth-centered, Earth-fixed
+/// system (EPSG:4978) and the UTM systems of WGS 84 (EPSG:326xx, EPSG:327xx) and ETRS89
+/// (EPSG:258xx), while the heights are considered as ellipsoidal heights.
+///
+/// See also: <https://github.com/CesiumGS/3d-tiles>
+#[derive(Debug, Clone)]
+pub struct Tiles3dWriter {
+    directory_path: PathBuf,
+    frame_id: Option<FrameId>,
+    max_points_per_tile: usize,
+    shuffle_seed_number: Option<u64>,
+}
+
+impl Tiles3dWriter {
+    pub fn new(directory_path: impl AsRef<Path>) -> Self {
+        Self {
+            directory_path: directory_path.as_ref().to_path_buf(),
+            frame_id: None,
+            max_points_per_tile: DEFAULT_MAX_POINTS_PER_TILE,
+            shuffle_seed_number: Some(0),
+        }
+    }
+
+    pub fn with_frame_id(mut self, frame_id: FrameId) -> Self {
+        self.frame_id = Some(frame_id);
+        self
+    }
+
+    /// Sets the maximum number of points per tile, which is 50,000 by default.
+    pub fn with_max_points_per_tile(mut self, max_points_per_tile: usize) -> Self {
+        self.max_points_per_tile = max_points_per_tile;
+        self
+    }
+
+    /// Sets the seed for shuffling the points before the subsampling, which is 0 by default.
+    pub fn with_shuffle_seed_number(mut self, shuffle_seed_number: Option<u64>) -> Self {
+        self.shuffle_seed_number = shuffle_seed_number;
+        self
+    }
+
+    pub fn finish(self, mut point_cloud: PointCloud) -> Result<(), Error> {
+        if let Some(frame_id) = self.frame_id.clone() {
+            point_cloud.resolve_to_frame(frame_id)?;
+        };
+
+        write_tiles3d_format(
+            &self.directory_path,
+            point_cloud,
+            self.max_points_per_tile,
+            self.shuffle_seed_number,
+        )
+    }
+}
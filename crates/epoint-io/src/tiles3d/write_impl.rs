@@ -0,0 +1,160 @@
+use crate::Error;
+use crate::tiles3d::documents::{
+    AssetDocument, BoundingVolumeDocument, ContentDocument, TileDocument, TilesetDocument,
+};
+use crate::tiles3d::georeference::create_root_transform;
+use crate::tiles3d::pnts::encode_pnts;
+use crate::tiles3d::{DIRECTORY_NAME_TILES, FILE_EXTENSION_PNTS_FORMAT, FILE_NAME_TILESET};
+use ecoord::octree::{OctantIndex, StorageMode};
+use epoint_core::PointCloud;
+use epoint_core::octree::PointCloudOctree;
+use nalgebra::Point3;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+
+const TILES_VERSION: &str = "1.0";
+
+pub fn write_tiles3d_format(
+    directory_path: &Path,
+    point_cloud: PointCloud,
+    max_points_per_tile: usize,
+    shuffle_seed_number: Option<u64>,
+) -> Result<(), Error> {
+    if point_cloud.point_data.is_empty() {
+        return Err(epoint_core::Error::NoRemainingPoints.into());
+    }
+    let crs_epsg = point_cloud
+        .info_frame_id()
+        .and_then(|f| point_cloud.transform_tree.frames().get(f))
+        .and_then(|f| f.crs_epsg);
+
+    let point_cloud_octree = PointCloudOctree::new(
+        point_cloud,
+        max_points_per_tile,
+        StorageMode::AllOctants,
+        shuffle_seed_number,
+    )?;
+    let origin = point_cloud_octree.octree.bounds().enclosing_cube().center();
+    let root_transform = create_root_transform(origin, crs_epsg)?;
+
+    let cell_indices = point_cloud_octree.cell_indices();
+    let tile_indices: HashSet<OctantIndex> = cell_indices
+        .iter()
+        .flat_map(|i| i.get_ancestors().into_iter().chain(std::iter::once(*i)))
+        .collect();
+
+    fs::create_dir_all(directory_path.join(DIRECTORY_NAME_TILES))?;
+    let mut root_tile = create_tile(
+        directory_path,
+        &point_cloud_octree,
+        &tile_indices,
+        origin,
+        OctantIndex::origin(),
+    )?;
+    root_tile.refine = Some("ADD".to_string());
+    root_tile.transform = Some(
+        root_transform
+            .as_slice()
+            .try_into()
+            .expect("matrix has 16 elements"),
+    );
+
+    let tileset_document = TilesetDocument {
+        asset: AssetDocument {
+            version: TILES_VERSION.to_string(),
+        },
+        geometric_error: point_cloud_octree
+            .octree
+            .bounds()
+            .enclosing_cube()
+            .edge_length(),
+        root: root_tile,
+    };
+    let tileset_file = File::create(directory_path.join(FILE_NAME_TILESET))?;
+    serde_json::to_writer_pretty(tileset_file, &tileset_document)?;
+
+    Ok(())
+}
+
+/// Creates the tile of an octant including its descendants and writes their contents.
+///
+/// The geometric error of a tile is the spacing of its points, which is estimated by assuming
+/// that the points are distributed over a surface of the octant. Leaf tiles have no geometric
+/// error.
+fn create_tile(
+    directory_path: &Path,
+    point_cloud_octree: &PointCloudOctree,
+    tile_indices: &HashSet<OctantIndex>,
+    origin: Point3<f64>,
+    index: OctantIndex,
+) -> Result<TileDocument, Error> {
+    let bounding_cube = point_cloud_octree
+        .octree
+        .bounds()
+        .get_octant_bounding_cube(index);
+    let center = bounding_cube.center() - origin.coords;
+    let half_edge_length = bounding_cube.half_edge_length();
+
+    let children: Vec<TileDocument> = index
+        .get_children()
+        .into_iter()
+        .filter(|c| tile_indices.contains(c))
+        .map(|c| create_tile(directory_path, point_cloud_octree, tile_indices, origin, c))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let point_count = point_cloud_octree.octree.cell(index).map_or(0, |p| p.len());
+    let content = if point_count > 0 {
+        let octant_point_cloud = point_cloud_octree.extract_octant(index)?;
+        let pnts_data = encode_pnts(
+            &octant_point_cloud.point_data,
+            origin,
+            center,
+            half_edge_length,
+        )?;
+        let uri = format!(
+            "{}/{}-{}-{}-{}.{}",
+            DIRECTORY_NAME_TILES,
+            index.level,
+            index.x,
+            index.y,
+            index.z,
+            FILE_EXTENSION_PNTS_FORMAT
+        );
+        fs::write(directory_path.join(&uri), pnts_data)?;
+        Some(ContentDocument { uri })
+    } else {
+        None
+    };
+
+    let geometric_error = if children.is_empty() {
+        0.0
+    } else {
+        bounding_cube.edge_length() / (point_count.max(1) as f64).sqrt()
+    };
+
+    Ok(TileDocument {
+        bounding_volume: BoundingVolumeDocument {
+            oriented_box: [
+                center.x,
+                center.y,
+                center.z,
+                half_edge_length,
+                0.0,
+                0.0,
+                0.0,
+                half_edge_length,
+                0.0,
+                0.0,
+                0.0,
+                half_edge_length,
+            ],
+        },
+        geometric_error,
+        refine: None,
+        transform: None,
+        content,
+        children,
+    })
+}
@@ -0,0 +1,389 @@
+#[cfg(test)]
+mod copc_round_trip_test {
+
+    use ecoord::octree::{OctantIndex, StorageMode};
+    use ecoord::{AxisAlignedBoundingBox, TransformTree};
+    use epoint_core::octree::PointCloudOctree;
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumns};
+    use epoint_io::{CopcReader, CopcWriter, Error, LasReader};
+    use laz::record::{LayeredPointRecordCompressor, RecordCompressor};
+    use nalgebra::Point3;
+    use std::io::Cursor;
+
+    const HALFSIZE: f64 = 8.0;
+
+    fn create_las_point(x: f64) -> las::Point {
+        las::Point {
+            x,
+            y: x,
+            z: x,
+            gps_time: Some(1.0),
+            ..Default::default()
+        }
+    }
+
+    /// Assembles a COPC file with the octants given by level, x, y, z and their points.
+    fn write_copc_octants(octants: Vec<([i32; 4], Vec<las::Point>)>) -> Cursor<Vec<u8>> {
+        let laz_vlr = laz::LazVlrBuilder::default()
+            .with_point_format(6, 0)
+            .unwrap()
+            .with_variable_chunk_size()
+            .build();
+        let mut laz_vlr_data = Vec::new();
+        laz_vlr.write_to(&mut laz_vlr_data).unwrap();
+
+        let mut builder = las::Builder::from((1, 4));
+        builder.point_format = las::point::Format::new(6).unwrap();
+        builder.point_format.is_compressed = true;
+        builder.vlrs.push(las::Vlr {
+            user_id: "copc".into(),
+            record_id: 1,
+            description: String::new(),
+            data: vec![0; 160],
+        });
+        builder.vlrs.push(las::Vlr {
+            user_id: "laszip encoded".into(),
+            record_id: 22204,
+            description: String::new(),
+            data: laz_vlr_data,
+        });
+        let mut header = builder.into_header().unwrap();
+        let transforms = *header.transforms();
+        let point_format = *header.point_format();
+
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        for (_, points) in &octants {
+            let mut compressor = LayeredPointRecordCompressor::new(Cursor::new(Vec::new()));
+            compressor.set_fields_from(laz_vlr.items()).unwrap();
+            for point in points {
+                header.add_point(point);
+                let mut record = Vec::new();
+                point
+                    .clone()
+                    .into_raw(&transforms)
+                    .unwrap()
+                    .write_to(&mut record, &point_format)
+                    .unwrap();
+                compressor.compress_next(&record).unwrap();
+            }
+            compressor.done().unwrap();
+            chunks.push(compressor.into_inner().into_inner());
+        }
+
+        // layout: header, VLRs, chunk table offset, chunks, chunk table, hierarchy EVLR
+        let mut raw_header = header.clone().into_raw().unwrap();
+        let chunks_offset = raw_header.offset_to_point_data as u64 + 8;
+        let chunk_table_offset = chunks_offset + chunks.iter().map(|c| c.len() as u64).sum::<u64>();
+        let mut chunk_table = laz::laszip::ChunkTable::with_capacity(chunks.len());
+        for ((_, points), chunk) in octants.iter().zip(&chunks) {
+            chunk_table.push(laz::laszip::ChunkTableEntry {
+                point_count: points.len() as u64,
+                byte_count: chunk.len() as u64,
+            });
+        }
+        let mut chunk_table_data = Vec::new();
+        chunk_table
+            .write_to(&mut chunk_table_data, &laz_vlr)
+            .unwrap();
+        let evlr_offset = chunk_table_offset + chunk_table_data.len() as u64;
+
+        let mut hierarchy_data = Vec::new();
+        let mut chunk_offset = chunks_offset;
+        for ((key, points), chunk) in octants.iter().zip(&chunks) {
+            key.iter()
+                .for_each(|k| hierarchy_data.extend(k.to_le_bytes()));
+            hierarchy_data.extend(chunk_offset.to_le_bytes());
+            hierarchy_data.extend((chunk.len() as i32).to_le_bytes());
+            hierarchy_data.extend((points.len() as i32).to_le_bytes());
+            chunk_offset += chunk.len() as u64;
+        }
+
+        let mut copc_info_data = Vec::new();
+        [0.0, 0.0, 0.0, HALFSIZE, 1.0]
+            .iter()
+            .for_each(|v: &f64| copc_info_data.extend(v.to_le_bytes()));
+        copc_info_data.extend((evlr_offset + 60).to_le_bytes());
+        copc_info_data.extend((hierarchy_data.len() as u64).to_le_bytes());
+        copc_info_data.resize(160, 0);
+
+        raw_header.evlr = Some(las::raw::header::Evlr {
+            start_of_first_evlr: evlr_offset,
+            number_of_evlrs: 1,
+        });
+        let mut buffer = Vec::new();
+        raw_header.write_to(&mut buffer).unwrap();
+        for vlr in header.vlrs() {
+            let mut vlr = vlr.clone();
+            if vlr.user_id == "copc" {
+                vlr.data = copc_info_data.clone();
+            }
+            vlr.into_raw(false).unwrap().write_to(&mut buffer).unwrap();
+        }
+        buffer.extend((chunk_table_offset as i64).to_le_bytes());
+        chunks.iter().for_each(|c| buffer.extend(c));
+        buffer.extend(chunk_table_data);
+        las::Vlr {
+            user_id: "copc".into(),
+            record_id: 1000,
+            description: String::new(),
+            data: hierarchy_data,
+        }
+        .into_raw(true)
+        .unwrap()
+        .write_to(&mut buffer)
+        .unwrap();
+
+        Cursor::new(buffer)
+    }
+
+    fn create_copc_octants() -> Cursor<Vec<u8>> {
+        write_copc_octants(vec![
+            (
+                [0, 0, 0, 0],
+                vec![create_las_point(-4.0), create_las_point(4.0)],
+            ),
+            (
+                [1, 0, 0, 0],
+                vec![create_las_point(-2.0), create_las_point(-6.0)],
+            ),
+            (
+                [1, 1, 1, 1],
+                vec![create_las_point(2.0), create_las_point(6.0)],
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_read_all_octants() {
+        let (point_cloud, read_info) = CopcReader::new(create_copc_octants())
+            .with_octant_indices(true)
+            .finish()
+            .unwrap();
+
+        assert_eq!(read_info.copc_info.halfsize, HALFSIZE);
+        assert_eq!(
+            point_cloud.point_data.get_x_values().to_vec(),
+            vec![
+                Some(-4.0),
+                Some(4.0),
+                Some(-2.0),
+                Some(-6.0),
+                Some(2.0),
+                Some(6.0)
+            ]
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .data_frame
+                .column("octant_index_level")
+                .unwrap()
+                .u32()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0), Some(0), Some(1), Some(1), Some(1), Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_read_with_max_level() {
+        let (point_cloud, _) = CopcReader::new(create_copc_octants())
+            .with_max_level(Some(0))
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            point_cloud.point_data.get_x_values().to_vec(),
+            vec![Some(-4.0), Some(4.0)]
+        );
+    }
+
+    #[test]
+    fn test_read_with_bounds() {
+        let (point_cloud, _) = CopcReader::new(create_copc_octants())
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(Point3::new(1.0, 1.0, 1.0), Point3::new(8.0, 8.0, 8.0))
+                    .unwrap(),
+            ))
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            point_cloud.point_data.get_x_values().to_vec(),
+            vec![Some(4.0), Some(2.0), Some(6.0)]
+        );
+    }
+
+    #[test]
+    fn test_read_octants_with_varying_point_source_ids() {
+        let buffer = write_copc_octants(vec![
+            ([0, 0, 0, 0], vec![create_las_point(-4.0)]),
+            (
+                [1, 1, 1, 1],
+                vec![las::Point {
+                    point_source_id: 7,
+                    ..create_las_point(2.0)
+                }],
+            ),
+        ]);
+
+        let (point_cloud, _) = CopcReader::new(buffer).finish().unwrap();
+
+        assert_eq!(
+            point_cloud
+                .point_data
+                .data_frame
+                .column("point_source_id")
+                .unwrap()
+                .u16()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0), Some(7)]
+        );
+    }
+
+    #[test]
+    fn test_read_malformed_hierarchy() {
+        // the hierarchy with its three entries is at the end of the file
+        let patch_first_entry = |byte_size: i32, point_count: i32| {
+            let mut data = create_copc_octants().into_inner();
+            let entry_start = data.len() - 3 * 32;
+            let root_hierarchy_offset = entry_start as u64;
+            data[entry_start + 16..entry_start + 24]
+                .copy_from_slice(&root_hierarchy_offset.to_le_bytes());
+            data[entry_start + 24..entry_start + 28].copy_from_slice(&byte_size.to_le_bytes());
+            data[entry_start + 28..entry_start + 32].copy_from_slice(&point_count.to_le_bytes());
+            Cursor::new(data)
+        };
+
+        for (byte_size, point_count) in [
+            // negative chunk size
+            (-1, 2),
+            // chunk beyond the end of the file
+            (i32::MAX, 2),
+            // page referencing itself
+            (3 * 32, -1),
+        ] {
+            let result = CopcReader::new(patch_first_entry(byte_size, point_count)).finish();
+
+            assert!(matches!(result, Err(Error::InvalidCopcFile(_))));
+        }
+    }
+
+    #[test]
+    fn test_read_copc_as_laz() {
+        let (point_cloud, _) = LasReader::new(create_copc_octants()).finish().unwrap();
+
+        assert_eq!(point_cloud.size(), 6);
+    }
+
+    fn create_point_cloud_octree() -> PointCloudOctree {
+        let point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                (0..20)
+                    .map(|i| Point3::new(i as f64, (i % 5) as f64, (i % 3) as f64))
+                    .collect(),
+                Some((0..20).collect()),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
+            PointCloudInfo::new(None),
+            TransformTree::default(),
+        )
+        .unwrap();
+
+        PointCloudOctree::new(point_cloud, 4, StorageMode::AllOctants, Some(0)).unwrap()
+    }
+
+    #[test]
+    fn test_write_point_cloud_octree() {
+        let point_cloud_octree = create_point_cloud_octree();
+        let root_point_count = point_cloud_octree
+            .octree
+            .cell(OctantIndex::origin())
+            .unwrap()
+            .len();
+        let mut buffer = Cursor::new(Vec::new());
+        CopcWriter::new(&mut buffer)
+            .finish(point_cloud_octree.clone())
+            .unwrap();
+
+        buffer.set_position(0);
+        let (point_cloud, read_info) = CopcReader::new(&mut buffer)
+            .with_octant_indices(true)
+            .finish()
+            .unwrap();
+        let enclosing_cube = point_cloud_octree.octree.bounds().enclosing_cube();
+        assert_eq!(read_info.copc_info.center, enclosing_cube.center());
+        assert_eq!(
+            read_info.copc_info.halfsize,
+            enclosing_cube.half_edge_length()
+        );
+        assert_eq!(point_cloud.size(), 20);
+        let data_frame = &point_cloud.point_data.data_frame;
+        let octant_column = |name: &str| -> Vec<u64> {
+            data_frame
+                .column(name)
+                .unwrap()
+                .cast(&polars::prelude::DataType::UInt64)
+                .unwrap()
+                .u64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect()
+        };
+        let mut octant_indices: Vec<(u64, OctantIndex)> = point_cloud
+            .point_data
+            .get_id_values()
+            .unwrap()
+            .into_no_null_iter()
+            .zip(octant_column("octant_index_level"))
+            .zip(octant_column("octant_index_x"))
+            .zip(octant_column("octant_index_y"))
+            .zip(octant_column("octant_index_z"))
+            .map(|((((id, level), x), y), z)| {
+                (id, OctantIndex::new_unchecked(level as u32, x, y, z))
+            })
+            .collect();
+        octant_indices.sort();
+        let mut expected_octant_indices: Vec<(u64, OctantIndex)> = point_cloud_octree
+            .cell_indices()
+            .into_iter()
+            .flat_map(|index| {
+                point_cloud_octree
+                    .extract_octant(index)
+                    .unwrap()
+                    .point_data
+                    .get_id_values()
+                    .unwrap()
+                    .into_no_null_iter()
+                    .map(move |id| (id, index))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        expected_octant_indices.sort();
+        assert_eq!(octant_indices, expected_octant_indices);
+
+        buffer.set_position(0);
+        let (point_cloud, _) = CopcReader::new(&mut buffer)
+            .with_max_level(Some(0))
+            .finish()
+            .unwrap();
+        assert_eq!(point_cloud.size(), root_point_count);
+
+        buffer.set_position(0);
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+        assert_eq!(point_cloud.size(), 20);
+    }
+
+    #[test]
+    fn test_write_requires_copc_file_extension() {
+        let path = std::env::temp_dir().join("epoint_copc_round_trip.laz");
+
+        assert!(CopcWriter::from_path(path).is_err());
+    }
+}
@@ -0,0 +1,624 @@
+#[cfg(test)]
+mod e57_round_trip_test {
+
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+    use ecoord::{FrameId, StaticTransform, Transform, TransformEdge, TransformId, TransformTree};
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumnType, PointDataColumns};
+    use epoint_io::{E57Reader, E57Writer};
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+    use palette::Srgb;
+    use polars::prelude::{NamedFrom, Series};
+    use std::collections::HashSet;
+    use std::io::Cursor;
+
+    fn create_scan_pose(frame_id: &str, x: f64) -> TransformEdge {
+        TransformEdge::Static(StaticTransform::new(
+            FrameId::global(),
+            frame_id.into(),
+            Transform::new(
+                Vector3::new(x, 2.0, 3.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5),
+            ),
+        ))
+    }
+
+    #[test]
+    fn test_single_scan_round_trip() {
+        let points = vec![
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(-4.5, 0.25, 1000.125),
+            Point3::new(6.0, 7.0, -8.0),
+        ];
+        let point_data = PointDataColumns::new(
+            points,
+            None,
+            None,
+            None,
+            Some(vec![0.0, 0.5, 1.0]),
+            None,
+            Some(vec![
+                Srgb::new(0, 257, 65535),
+                Srgb::new(514, 1028, 2056),
+                Srgb::new(65535, 0, 0),
+            ]),
+        )
+        .unwrap();
+        let transform_tree =
+            TransformTree::new(vec![create_scan_pose("sensor", 1.0)], Vec::new()).unwrap();
+        let point_cloud = PointCloud::new(
+            point_data,
+            PointCloudInfo::new(Some(FrameId::sensor())),
+            transform_tree,
+        )
+        .unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        E57Writer::new(&mut buffer)
+            .finish(point_cloud.clone())
+            .unwrap();
+        buffer.set_position(0);
+        let read_point_cloud = E57Reader::new(buffer).finish().unwrap();
+
+        assert_eq!(
+            read_point_cloud.point_data.get_all_points(),
+            point_cloud.point_data.get_all_points()
+        );
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec(),
+            point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec()
+        );
+        assert_eq!(
+            read_point_cloud.point_data.get_all_colors().unwrap(),
+            point_cloud.point_data.get_all_colors().unwrap()
+        );
+        let pose = read_point_cloud
+            .transform_tree
+            .get_static_transform(&TransformId::new(FrameId::global(), FrameId::sensor()))
+            .unwrap();
+        assert_eq!(pose.translation, Vector3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_intensities_beyond_unit_interval_round_trip() {
+        let intensity_values = vec![0.0, 1.5, 12_345.0, 65_535.0];
+        let point_data = PointDataColumns::new(
+            vec![Point3::new(1.0, 2.0, 3.0); intensity_values.len()],
+            None,
+            None,
+            None,
+            Some(intensity_values.clone()),
+            None,
+            None,
+        )
+        .unwrap();
+        let transform_tree =
+            TransformTree::new(vec![create_scan_pose("sensor", 1.0)], Vec::new()).unwrap();
+        let point_cloud = PointCloud::new(
+            point_data,
+            PointCloudInfo::new(Some(FrameId::sensor())),
+            transform_tree,
+        )
+        .unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        E57Writer::new(&mut buffer).finish(point_cloud).unwrap();
+        buffer.set_position(0);
+        let read_point_cloud = E57Reader::new(buffer).finish().unwrap();
+
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec(),
+            intensity_values.into_iter().map(Some).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_write_section_per_frame_id() {
+        let points = vec![
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(3.0, 0.0, 0.0),
+        ];
+        let point_data = PointDataColumns::new(
+            points,
+            None,
+            Some(vec!["scan_b".into(), "scan_a".into(), "scan_b".into()]),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let transform_tree = TransformTree::new(
+            vec![
+                create_scan_pose("scan_a", 10.0),
+                create_scan_pose("scan_b", 20.0),
+            ],
+            Vec::new(),
+        )
+        .unwrap();
+        let point_cloud =
+            PointCloud::new(point_data, PointCloudInfo::new(None), transform_tree).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        E57Writer::new(&mut buffer).finish(point_cloud).unwrap();
+        buffer.set_position(0);
+
+        let e57_reader = e57::E57Reader::new(buffer).unwrap();
+        let e57_point_clouds = e57_reader.pointclouds();
+        assert_eq!(e57_point_clouds.len(), 2);
+        assert_eq!(e57_point_clouds[0].name.as_deref(), Some("scan_a"));
+        assert_eq!(e57_point_clouds[0].records, 1);
+        assert_eq!(e57_point_clouds[1].name.as_deref(), Some("scan_b"));
+        assert_eq!(e57_point_clouds[1].records, 2);
+        assert_eq!(
+            e57_point_clouds[1]
+                .transform
+                .as_ref()
+                .unwrap()
+                .translation
+                .x,
+            20.0
+        );
+    }
+
+    #[test]
+    fn test_multi_scan_round_trip() {
+        let points = vec![
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(3.0, 0.0, 0.0),
+        ];
+        let point_data = PointDataColumns::new(
+            points,
+            Some(vec![0, 1, 2]),
+            Some(vec!["scan_b".into(), "scan_a".into(), "scan_b".into()]),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let transform_tree = TransformTree::new(
+            vec![
+                create_scan_pose("scan_a", 10.0),
+                create_scan_pose("scan_b", 20.0),
+            ],
+            Vec::new(),
+        )
+        .unwrap();
+        let point_cloud =
+            PointCloud::new(point_data, PointCloudInfo::new(None), transform_tree).unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        E57Writer::new(&mut buffer)
+            .finish(point_cloud.clone())
+            .unwrap();
+
+        buffer.set_position(0);
+        let scans = E57Reader::new(&mut buffer).finish_scans().unwrap();
+        assert_eq!(scans.len(), 2);
+        assert_eq!(scans[0].info_frame_id(), Some(&"scan_a".into()));
+        assert_eq!(scans[1].size(), 2);
+
+        buffer.set_position(0);
+        let mut read_point_cloud = E57Reader::new(&mut buffer).finish().unwrap();
+        assert_eq!(read_point_cloud.info_frame_id(), None);
+        assert_eq!(
+            read_point_cloud.get_distinct_frame_ids().unwrap(),
+            HashSet::from(["scan_a".into(), "scan_b".into()])
+        );
+        let pose = read_point_cloud
+            .transform_tree
+            .get_static_transform(&TransformId::new(FrameId::global(), "scan_b".into()))
+            .unwrap();
+        assert_eq!(pose.translation, Vector3::new(20.0, 2.0, 3.0));
+
+        let mut expected_point_cloud = point_cloud;
+        expected_point_cloud
+            .resolve_to_frame(FrameId::global())
+            .unwrap();
+        read_point_cloud
+            .resolve_to_frame(FrameId::global())
+            .unwrap();
+        // ids are not stored in E57, so the points are compared in sorted order
+        let sort_points = |mut points: Vec<Point3<f64>>| {
+            points.sort_by(|a, b| a.x.total_cmp(&b.x));
+            points
+        };
+        for (read_point, expected_point) in
+            sort_points(read_point_cloud.point_data.get_all_points())
+                .iter()
+                .zip(sort_points(
+                    expected_point_cloud.point_data.get_all_points(),
+                ))
+        {
+            assert!((read_point - expected_point).norm() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_read_multi_scan_with_differing_attributes() {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut e57_writer = e57::E57Writer::new(&mut buffer, "file").unwrap();
+            let prototype = vec![
+                e57::Record::CARTESIAN_X_F64,
+                e57::Record::CARTESIAN_Y_F64,
+                e57::Record::CARTESIAN_Z_F64,
+                e57::Record {
+                    name: e57::RecordName::Intensity,
+                    data_type: e57::RecordDataType::Single {
+                        min: Some(0.0),
+                        max: Some(1.0),
+                    },
+                },
+            ];
+            let mut scan_writer = e57_writer.add_pointcloud("scan_a", prototype).unwrap();
+            scan_writer.set_name(Some("scan_a".into()));
+            scan_writer
+                .add_point(vec![
+                    e57::RecordValue::Double(1.0),
+                    e57::RecordValue::Double(0.0),
+                    e57::RecordValue::Double(0.0),
+                    e57::RecordValue::Single(0.5),
+                ])
+                .unwrap();
+            scan_writer.finalize().unwrap();
+            let mut scan_writer = e57_writer
+                .add_pointcloud(
+                    "scan_b",
+                    vec![
+                        e57::Record::CARTESIAN_X_F64,
+                        e57::Record::CARTESIAN_Y_F64,
+                        e57::Record::CARTESIAN_Z_F64,
+                    ],
+                )
+                .unwrap();
+            scan_writer.set_name(Some("scan_b".into()));
+            scan_writer
+                .add_point(vec![
+                    e57::RecordValue::Double(2.0),
+                    e57::RecordValue::Double(0.0),
+                    e57::RecordValue::Double(0.0),
+                ])
+                .unwrap();
+            scan_writer.finalize().unwrap();
+            e57_writer.finalize().unwrap();
+        }
+
+        buffer.set_position(0);
+        let point_cloud = E57Reader::new(&mut buffer).finish().unwrap();
+
+        assert_eq!(point_cloud.size(), 2);
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0.5), None]
+        );
+        assert_eq!(
+            point_cloud.get_distinct_frame_ids().unwrap(),
+            HashSet::from(["scan_a".into(), "scan_b".into()])
+        );
+    }
+
+    #[test]
+    fn test_timestamps_round_trip() {
+        let start_time = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let timestamps: Vec<DateTime<Utc>> = (0..3)
+            .map(|i| start_time + TimeDelta::nanoseconds(i * 250_000_123))
+            .collect();
+        let point_data = PointDataColumns::new(
+            vec![
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(2.0, 0.0, 0.0),
+                Point3::new(3.0, 0.0, 0.0),
+            ],
+            None,
+            None,
+            Some(timestamps.clone()),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let transform_tree =
+            TransformTree::new(vec![create_scan_pose("sensor", 1.0)], Vec::new()).unwrap();
+        let point_cloud = PointCloud::new(
+            point_data,
+            PointCloudInfo::new(Some(FrameId::sensor())),
+            transform_tree,
+        )
+        .unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        E57Writer::new(&mut buffer).finish(point_cloud).unwrap();
+        buffer.set_position(0);
+        let read_point_cloud = E57Reader::new(buffer).finish().unwrap();
+
+        assert_eq!(
+            read_point_cloud.point_data.get_all_timestamps().unwrap(),
+            timestamps
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_intensities_and_timestamps() {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut e57_writer = e57::E57Writer::new(&mut buffer, "guid_file").unwrap();
+            let prototype = vec![
+                e57::Record::CARTESIAN_X_F64,
+                e57::Record::CARTESIAN_Y_F64,
+                e57::Record::CARTESIAN_Z_F64,
+                e57::Record {
+                    name: e57::RecordName::Intensity,
+                    data_type: e57::RecordDataType::Single {
+                        min: Some(0.0),
+                        max: Some(1.0),
+                    },
+                },
+                e57::Record {
+                    name: e57::RecordName::IsIntensityInvalid,
+                    data_type: e57::RecordDataType::Integer { min: 0, max: 1 },
+                },
+                e57::Record {
+                    name: e57::RecordName::TimeStamp,
+                    data_type: e57::RecordDataType::F64,
+                },
+                e57::Record {
+                    name: e57::RecordName::IsTimeStampInvalid,
+                    data_type: e57::RecordDataType::Integer { min: 0, max: 1 },
+                },
+            ];
+            let mut point_cloud_writer = e57_writer.add_pointcloud("guid_scan", prototype).unwrap();
+            for (x, intensity_invalid, timestamp_invalid) in [(1.0, 0, 0), (2.0, 1, 0), (3.0, 0, 1)]
+            {
+                point_cloud_writer
+                    .add_point(vec![
+                        e57::RecordValue::Double(x),
+                        e57::RecordValue::Double(0.0),
+                        e57::RecordValue::Double(0.0),
+                        e57::RecordValue::Single(0.5),
+                        e57::RecordValue::Integer(intensity_invalid),
+                        e57::RecordValue::Double(x),
+                        e57::RecordValue::Integer(timestamp_invalid),
+                    ])
+                    .unwrap();
+            }
+            point_cloud_writer.finalize().unwrap();
+            e57_writer.finalize().unwrap();
+        }
+
+        buffer.set_position(0);
+        let point_cloud = E57Reader::new(buffer.clone()).finish().unwrap();
+        assert_eq!(point_cloud.size(), 1);
+
+        buffer.set_position(0);
+        let point_cloud = E57Reader::new(buffer)
+            .with_keep_invalid_points(true)
+            .finish()
+            .unwrap();
+        assert_eq!(point_cloud.size(), 3);
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0.5), None, Some(0.5)]
+        );
+        let timestamp_second_values = point_cloud
+            .point_data
+            .data_frame
+            .column(PointDataColumnType::TimestampSecond.as_str())
+            .unwrap()
+            .i64()
+            .unwrap()
+            .to_vec();
+        assert!(timestamp_second_values[0].is_some());
+        assert!(timestamp_second_values[1].is_some());
+        assert!(timestamp_second_values[2].is_none());
+    }
+
+    #[test]
+    fn test_read_spherical_grid_with_invalid_points() {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut e57_writer = e57::E57Writer::new(&mut buffer, "guid_file").unwrap();
+            let prototype = vec![
+                e57::Record {
+                    name: e57::RecordName::SphericalRange,
+                    data_type: e57::RecordDataType::F64,
+                },
+                e57::Record {
+                    name: e57::RecordName::SphericalAzimuth,
+                    data_type: e57::RecordDataType::F64,
+                },
+                e57::Record {
+                    name: e57::RecordName::SphericalElevation,
+                    data_type: e57::RecordDataType::F64,
+                },
+                e57::Record {
+                    name: e57::RecordName::SphericalInvalidState,
+                    data_type: e57::RecordDataType::Integer { min: 0, max: 2 },
+                },
+                e57::Record {
+                    name: e57::RecordName::RowIndex,
+                    data_type: e57::RecordDataType::U8,
+                },
+                e57::Record {
+                    name: e57::RecordName::ColumnIndex,
+                    data_type: e57::RecordDataType::U8,
+                },
+            ];
+            let mut point_cloud_writer = e57_writer.add_pointcloud("guid_scan", prototype).unwrap();
+            for (row, column, range, invalid_state) in [
+                (0, 0, 1.0, 0),
+                (0, 1, 2.0, 0),
+                (1, 0, 0.0, 2),
+                (1, 1, 4.0, 0),
+            ] {
+                point_cloud_writer
+                    .add_point(vec![
+                        e57::RecordValue::Double(range),
+                        e57::RecordValue::Double(0.25 * column as f64),
+                        e57::RecordValue::Double(0.1 * row as f64),
+                        e57::RecordValue::Integer(invalid_state),
+                        e57::RecordValue::Integer(row),
+                        e57::RecordValue::Integer(column),
+                    ])
+                    .unwrap();
+            }
+            point_cloud_writer.finalize().unwrap();
+            e57_writer.finalize().unwrap();
+        }
+
+        buffer.set_position(0);
+        let point_cloud = E57Reader::new(buffer.clone()).finish().unwrap();
+        assert_eq!(point_cloud.size(), 3);
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_spherical_range_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(1.0), Some(2.0), Some(4.0)]
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_spherical_azimuth_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0.0), Some(0.25), Some(0.25)]
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_row_index_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0), Some(0), Some(1)]
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_column_index_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0), Some(1), Some(1)]
+        );
+
+        buffer.set_position(0);
+        let point_cloud = E57Reader::new(buffer)
+            .with_keep_invalid_points(true)
+            .finish()
+            .unwrap();
+        assert_eq!(point_cloud.size(), 4);
+        assert!(
+            point_cloud
+                .point_data
+                .get_x_values()
+                .get(2)
+                .unwrap()
+                .is_nan()
+        );
+        assert!(
+            point_cloud
+                .point_data
+                .get_spherical_range_values()
+                .unwrap()
+                .get(2)
+                .unwrap()
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn test_spherical_grid_round_trip() {
+        let mut point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                vec![Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 2.0, 0.0)],
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
+            PointCloudInfo::new(Some(FrameId::sensor())),
+            TransformTree::default(),
+        )
+        .unwrap();
+        let point_data = &mut point_cloud.point_data.data_frame;
+        point_data
+            .with_column(Series::new(
+                PointDataColumnType::SphericalAzimuth.into(),
+                vec![0.0, std::f64::consts::FRAC_PI_2],
+            ))
+            .unwrap();
+        point_data
+            .with_column(Series::new(
+                PointDataColumnType::SphericalElevation.into(),
+                vec![0.0, 0.0],
+            ))
+            .unwrap();
+        point_data
+            .with_column(Series::new(
+                PointDataColumnType::SphericalRange.into(),
+                vec![1.0, 2.0],
+            ))
+            .unwrap();
+        point_data
+            .with_column(Series::new(
+                PointDataColumnType::RowIndex.into(),
+                vec![0u32, 0],
+            ))
+            .unwrap();
+        point_data
+            .with_column(Series::new(
+                PointDataColumnType::ColumnIndex.into(),
+                vec![0u32, 1],
+            ))
+            .unwrap();
+
+        let mut buffer = Cursor::new(Vec::new());
+        E57Writer::new(&mut buffer)
+            .finish(point_cloud.clone())
+            .unwrap();
+        buffer.set_position(0);
+        let read_point_cloud = E57Reader::new(buffer).finish().unwrap();
+
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_all_spherical_points()
+                .unwrap(),
+            point_cloud.point_data.get_all_spherical_points().unwrap()
+        );
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_column_index_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0), Some(1)]
+        );
+    }
+}
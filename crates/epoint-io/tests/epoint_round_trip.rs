@@ -0,0 +1,964 @@
+#[cfg(test)]
+mod epoint_round_trip_test {
+
+    use chrono::{DateTime, Utc};
+    use ecoord::{AxisAlignedBoundingBox, FrameId, FrameInfo, TransformTree};
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumns};
+    use epoint_io::{
+        AutoReader, AutoWriter, EPOINT_FORMAT_VERSION, EpointColumnDescription, EpointEditor,
+        EpointParquetCompression, EpointParquetOptions, EpointPartitioning, EpointQuantization,
+        EpointReader, EpointWriter, Error, PointCloudFormat,
+    };
+    use nalgebra::{Point3, Vector3};
+    use polars::prelude::{
+        DataType, NamedFrom, ParquetCompression, ParquetReader, ParquetWriter, SerReader, Series,
+    };
+    use polars_parquet::write::Encoding;
+    use std::collections::{BTreeMap, HashMap};
+    use std::fs;
+    use std::io::{Cursor, Read};
+    use std::path::PathBuf;
+
+    const POINT_COUNT: usize = 20;
+
+    fn create_point_cloud(points: Vec<Point3<f64>>) -> PointCloud {
+        PointCloud::new(
+            PointDataColumns::new(points, None, None, None, None, None, None).unwrap(),
+            PointCloudInfo::new(None),
+            TransformTree::default(),
+        )
+        .unwrap()
+    }
+
+    /// Writes the point cloud with the configured writer and rewinds the buffer for reading.
+    fn write_to_buffer(
+        point_cloud: PointCloud,
+        configure_writer: impl FnOnce(
+            EpointWriter<&mut Cursor<Vec<u8>>>,
+        ) -> EpointWriter<&mut Cursor<Vec<u8>>>,
+    ) -> Result<Cursor<Vec<u8>>, Error> {
+        let mut buffer = Cursor::new(Vec::new());
+        configure_writer(EpointWriter::new(&mut buffer)).finish(point_cloud)?;
+        buffer.set_position(0);
+        Ok(buffer)
+    }
+
+    fn write_epoint(compressed: bool) -> Cursor<Vec<u8>> {
+        let point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                (0..POINT_COUNT)
+                    .map(|i| Point3::new(i as f64, 2.0, 3.0))
+                    .collect(),
+                None,
+                None,
+                Some(
+                    (0..POINT_COUNT)
+                        .map(|i| DateTime::<Utc>::from_timestamp(i as i64, 500).unwrap())
+                        .collect(),
+                ),
+                Some((0..POINT_COUNT).map(|i| i as f32).collect()),
+                None,
+                None,
+            )
+            .unwrap(),
+            PointCloudInfo::new(None),
+            TransformTree::default(),
+        )
+        .unwrap();
+
+        write_to_buffer(point_cloud, |w| w.with_compressed(compressed)).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for compressed in [true, false] {
+            let point_cloud = EpointReader::new(write_epoint(compressed))
+                .finish()
+                .unwrap();
+
+            assert_eq!(point_cloud.size(), POINT_COUNT);
+            assert!(point_cloud.contains_timestamps());
+            assert_eq!(
+                point_cloud
+                    .point_data
+                    .get_intensity_values()
+                    .unwrap()
+                    .get(5),
+                Some(5.0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_lazy_with_bounds_and_columns() {
+        for compressed in [true, false] {
+            let lazy_point_cloud = EpointReader::new(write_epoint(compressed))
+                .with_bounds(Some(
+                    AxisAlignedBoundingBox::new(
+                        Point3::new(5.0, 0.0, 0.0),
+                        Point3::new(9.0, 2.0, 3.0),
+                    )
+                    .unwrap(),
+                ))
+                .with_columns(Some(vec!["intensity".to_string(), "unknown".to_string()]))
+                .finish_lazy()
+                .unwrap();
+            let point_cloud = lazy_point_cloud.collect().unwrap();
+
+            assert_eq!(point_cloud.size(), 5);
+            assert!(!point_cloud.contains_timestamps());
+            let intensity_values: Vec<Option<f32>> = point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .into_iter()
+                .collect();
+            assert_eq!(
+                intensity_values,
+                vec![Some(5.0), Some(6.0), Some(7.0), Some(8.0), Some(9.0)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_with_time_range() {
+        let point_cloud = EpointReader::new(write_epoint(true))
+            .with_time_range(Some((
+                DateTime::<Utc>::from_timestamp(3, 500).unwrap(),
+                DateTime::<Utc>::from_timestamp(6, 499).unwrap(),
+            )))
+            .finish()
+            .unwrap();
+
+        let x_values: Vec<f64> = point_cloud
+            .point_data
+            .get_all_points()
+            .iter()
+            .map(|p| p.x)
+            .collect();
+        assert_eq!(x_values, vec![3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_read_without_remaining_points() {
+        let result = EpointReader::new(write_epoint(true))
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(100.0, 0.0, 0.0),
+                    Point3::new(200.0, 2.0, 3.0),
+                )
+                .unwrap(),
+            ))
+            .finish();
+
+        assert!(matches!(
+            result,
+            Err(Error::EpointError(epoint_core::Error::NoRemainingPoints))
+        ));
+    }
+
+    fn check_read_info(buffer: Cursor<Vec<u8>>) {
+        let read_info = EpointReader::new(buffer).read_info().unwrap();
+
+        assert_eq!(read_info.point_count, POINT_COUNT);
+        let column_names: Vec<&str> = read_info.schema.iter_names().map(|n| n.as_str()).collect();
+        assert_eq!(
+            column_names,
+            vec![
+                "x",
+                "y",
+                "z",
+                "timestamp_sec",
+                "timestamp_nanosec",
+                "intensity"
+            ]
+        );
+        assert_eq!(
+            read_info.schema.get("intensity"),
+            Some(&polars::prelude::DataType::Float32)
+        );
+        assert_eq!(
+            read_info.bounding_box.lower_bound(),
+            Point3::new(0.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            read_info.bounding_box.upper_bound(),
+            Point3::new((POINT_COUNT - 1) as f64, 2.0, 3.0)
+        );
+        assert_eq!(
+            read_info.time_range,
+            Some((
+                DateTime::<Utc>::from_timestamp(0, 500).unwrap(),
+                DateTime::<Utc>::from_timestamp(POINT_COUNT as i64 - 1, 500).unwrap(),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_read_info() {
+        for compressed in [true, false] {
+            check_read_info(write_epoint(compressed));
+        }
+    }
+
+    /// Replaces the info document of an uncompressed archive.
+    fn replace_info_document(buffer: Cursor<Vec<u8>>, info_document: &[u8]) -> Cursor<Vec<u8>> {
+        let mut archive = tar::Archive::new(buffer);
+        let mut archive_builder = tar::Builder::new(Vec::new());
+        for file in archive.entries().unwrap() {
+            let mut file = file.unwrap();
+            let mut header = file.header().clone();
+            let mut data: Vec<u8> = Vec::new();
+            file.read_to_end(&mut data).unwrap();
+            if file.path().unwrap().to_str() == Some("info.json") {
+                data = info_document.to_vec();
+                header.set_size(data.len() as u64);
+                header.set_cksum();
+            }
+            archive_builder
+                .append_data(&mut header, file.path().unwrap(), data.as_slice())
+                .unwrap();
+        }
+
+        Cursor::new(archive_builder.into_inner().unwrap())
+    }
+
+    #[test]
+    fn test_read_info_of_format_version_1() {
+        let buffer = replace_info_document(write_epoint(false), br#"{"frame_id": null}"#);
+        check_read_info(buffer.clone());
+
+        let read_info = EpointReader::new(buffer.clone()).read_info().unwrap();
+        assert_eq!(read_info.format_version, 1);
+        assert_eq!(read_info.writer, None);
+        assert_eq!(
+            read_info.column_descriptions["timestamp_nanosec"].unit,
+            Some("ns".to_string())
+        );
+        assert_eq!(
+            EpointReader::new(buffer).finish().unwrap().size(),
+            POINT_COUNT
+        );
+    }
+
+    #[test]
+    fn test_read_newer_format_version() {
+        let info_document = format!(
+            r#"{{"format_version": {}, "frame_id": null}}"#,
+            EPOINT_FORMAT_VERSION + 1
+        );
+        let buffer = replace_info_document(write_epoint(false), info_document.as_bytes());
+
+        assert!(matches!(
+            EpointReader::new(buffer.clone()).read_info(),
+            Err(Error::UnsupportedEpointFormatVersion(v)) if v == EPOINT_FORMAT_VERSION + 1
+        ));
+        assert!(matches!(
+            EpointReader::new(buffer).finish(),
+            Err(Error::UnsupportedEpointFormatVersion(v)) if v == EPOINT_FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_schema_and_metadata_round_trip() {
+        let mut point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+        point_cloud.info.metadata = BTreeMap::from([
+            ("project".to_string(), "survey".to_string()),
+            ("operator".to_string(), "unknown".to_string()),
+        ]);
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "reflectance".into(),
+                vec![-10.0f32; POINT_COUNT],
+            ))
+            .unwrap();
+        let buffer = write_to_buffer(point_cloud.clone(), |w| {
+            w.with_column_descriptions(HashMap::from([(
+                "reflectance".to_string(),
+                EpointColumnDescription::new(
+                    Some("dB".to_string()),
+                    Some("Reflectance of the target".to_string()),
+                ),
+            )]))
+        })
+        .unwrap();
+
+        let read_info = EpointReader::new(buffer.clone()).read_info().unwrap();
+        assert_eq!(read_info.format_version, 2);
+        assert!(read_info.writer.unwrap().starts_with("epoint-io "));
+        assert_eq!(read_info.info, point_cloud.info);
+        assert_eq!(
+            read_info.schema.get("reflectance"),
+            Some(&polars::prelude::DataType::Float32)
+        );
+        assert_eq!(
+            read_info.column_descriptions["reflectance"],
+            EpointColumnDescription::new(
+                Some("dB".to_string()),
+                Some("Reflectance of the target".to_string())
+            )
+        );
+        assert_eq!(
+            read_info.column_descriptions["x"],
+            EpointColumnDescription::new(Some("m".to_string()), Some("X coordinate".to_string()))
+        );
+
+        let read_point_cloud = EpointReader::new(buffer).finish().unwrap();
+        assert_eq!(read_point_cloud.info, point_cloud.info);
+    }
+
+    #[test]
+    fn test_temporal_column_round_trip() {
+        let mut point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(
+                Series::new("survey_date".into(), vec![19_000i32; POINT_COUNT])
+                    .cast(&DataType::Date)
+                    .unwrap(),
+            )
+            .unwrap();
+        let buffer = write_to_buffer(point_cloud.clone(), |w| {
+            w.with_partitioning(Some(EpointPartitioning::Grid { tile_size: 5.0 }))
+        })
+        .unwrap();
+
+        let read_info = EpointReader::new(buffer.clone()).read_info().unwrap();
+        assert_eq!(read_info.schema.get("survey_date"), Some(&DataType::Date));
+        let read_point_cloud = EpointReader::new(buffer.clone()).finish().unwrap();
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .data_frame
+                .column("survey_date")
+                .unwrap()
+                .dtype(),
+            &DataType::Date
+        );
+
+        // the bounds select no part, so the schema is taken from a skipped part
+        let lazy_point_cloud = EpointReader::new(buffer)
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(100.0, 0.0, 0.0),
+                    Point3::new(200.0, 5.0, 5.0),
+                )
+                .unwrap(),
+            ))
+            .finish_lazy()
+            .unwrap();
+        let data_frame = lazy_point_cloud.point_data.collect().unwrap();
+        assert_eq!(data_frame.height(), 0);
+        assert_eq!(
+            data_frame.column("survey_date").unwrap().dtype(),
+            &DataType::Date
+        );
+    }
+
+    #[test]
+    fn test_read_partitioned_lazy_without_selected_parts() {
+        let mut point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new("label".into(), vec!["ground"; POINT_COUNT]))
+            .unwrap();
+        let buffer = write_to_buffer(point_cloud, |w| {
+            w.with_partitioning(Some(EpointPartitioning::Grid { tile_size: 5.0 }))
+        })
+        .unwrap();
+
+        let lazy_point_cloud = EpointReader::new(buffer)
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(100.0, 0.0, 0.0),
+                    Point3::new(200.0, 5.0, 5.0),
+                )
+                .unwrap(),
+            ))
+            .finish_lazy()
+            .unwrap();
+        let data_frame = lazy_point_cloud.point_data.collect().unwrap();
+        assert_eq!(data_frame.height(), 0);
+        assert_eq!(
+            data_frame.column("label").unwrap().dtype(),
+            &DataType::String
+        );
+    }
+
+    fn write_partitioned_epoint(
+        compressed: bool,
+        partitioning: EpointPartitioning,
+    ) -> Cursor<Vec<u8>> {
+        let point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+        write_to_buffer(point_cloud, |w| {
+            w.with_compressed(compressed)
+                .with_partitioning(Some(partitioning))
+        })
+        .unwrap()
+    }
+
+    fn read_part_names(buffer: Cursor<Vec<u8>>) -> Vec<String> {
+        tar::Archive::new(buffer)
+            .entries()
+            .unwrap()
+            .map(|f| f.unwrap().path().unwrap().to_str().unwrap().to_string())
+            .filter(|n| n.starts_with("point_data/"))
+            .collect()
+    }
+
+    #[test]
+    fn test_partitioned_round_trip() {
+        for (compressed, partitioning) in [
+            (true, EpointPartitioning::Grid { tile_size: 5.0 }),
+            (false, EpointPartitioning::Grid { tile_size: 5.0 }),
+            (
+                true,
+                EpointPartitioning::Octree {
+                    max_points_per_part: 6,
+                },
+            ),
+        ] {
+            let buffer = write_partitioned_epoint(compressed, partitioning);
+            assert!(read_part_names(buffer.clone()).len() > 1);
+
+            check_read_info(buffer.clone());
+            let point_cloud = EpointReader::new(buffer).finish().unwrap();
+            let mut x_values: Vec<f64> = point_cloud
+                .point_data
+                .get_all_points()
+                .iter()
+                .map(|p| p.x)
+                .collect();
+            x_values.sort_by(f64::total_cmp);
+            assert_eq!(
+                x_values,
+                (0..POINT_COUNT).map(|i| i as f64).collect::<Vec<f64>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_invalid_partitioning() {
+        for partitioning in [
+            EpointPartitioning::Grid { tile_size: 0.0 },
+            EpointPartitioning::Grid { tile_size: -5.0 },
+            EpointPartitioning::Grid {
+                tile_size: f64::NAN,
+            },
+            EpointPartitioning::Grid {
+                tile_size: f64::INFINITY,
+            },
+            EpointPartitioning::Octree {
+                max_points_per_part: 0,
+            },
+        ] {
+            let point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+            let result = write_to_buffer(point_cloud, |w| w.with_partitioning(Some(partitioning)));
+
+            assert!(matches!(result, Err(Error::InvalidPartitioning(_))));
+        }
+    }
+
+    #[test]
+    fn test_read_partitioned_with_bounds() {
+        let buffer = write_partitioned_epoint(true, EpointPartitioning::Grid { tile_size: 5.0 });
+        assert_eq!(
+            read_part_names(buffer.clone()),
+            vec![
+                "point_data/tile_0_0.parquet",
+                "point_data/tile_1_0.parquet",
+                "point_data/tile_2_0.parquet",
+                "point_data/tile_3_0.parquet",
+            ]
+        );
+
+        let bounds = |lower_x: f64, upper_x: f64| {
+            Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(lower_x, 0.0, 0.0),
+                    Point3::new(upper_x, 5.0, 5.0),
+                )
+                .unwrap(),
+            )
+        };
+        let point_cloud = EpointReader::new(buffer.clone())
+            .with_bounds(bounds(4.0, 6.0))
+            .finish()
+            .unwrap();
+        let x_values: Vec<f64> = point_cloud
+            .point_data
+            .get_all_points()
+            .iter()
+            .map(|p| p.x)
+            .collect();
+        assert_eq!(x_values, vec![4.0, 5.0, 6.0]);
+
+        // the gap between the bounds of the first two tiles
+        let result = EpointReader::new(buffer)
+            .with_bounds(bounds(4.2, 4.8))
+            .finish();
+        assert!(matches!(
+            result,
+            Err(Error::EpointError(epoint_core::Error::NoRemainingPoints))
+        ));
+    }
+
+    fn temp_directory_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("epoint_round_trip_{name}.epoint.d"))
+    }
+
+    #[test]
+    fn test_directory_round_trip() {
+        let directory_path = temp_directory_path("directory");
+        let _ = fs::remove_dir_all(&directory_path);
+        assert_eq!(
+            PointCloudFormat::from_path(&directory_path),
+            Some(PointCloudFormat::EpointDirectory)
+        );
+        let point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+
+        AutoWriter::from_path(&directory_path)
+            .unwrap()
+            .finish(point_cloud.clone())
+            .unwrap();
+        assert!(directory_path.join("point_data.parquet").is_file());
+        assert!(directory_path.join("info.json.zst").is_file());
+        assert!(PointCloudFormat::is_supported_point_cloud_format(
+            &directory_path
+        ));
+        let read_point_cloud = AutoReader::from_path(&directory_path)
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(read_point_cloud.point_data, point_cloud.point_data);
+
+        // the point data is replaced by the parts of a partitioned write
+        EpointWriter::from_path(&directory_path)
+            .unwrap()
+            .with_compressed(false)
+            .with_partitioning(Some(EpointPartitioning::Grid { tile_size: 5.0 }))
+            .finish(point_cloud)
+            .unwrap();
+        assert!(!directory_path.join("point_data.parquet").is_file());
+        assert!(directory_path.join("point_data/tile_1_0.xyz").is_file());
+        let read_info = EpointReader::from_path(&directory_path)
+            .unwrap()
+            .read_info()
+            .unwrap();
+        assert_eq!(read_info.format_version, 3);
+        assert_eq!(read_info.point_count, POINT_COUNT);
+        let read_point_cloud = EpointReader::from_path(&directory_path)
+            .unwrap()
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(Point3::new(5.0, 0.0, 0.0), Point3::new(7.0, 5.0, 5.0))
+                    .unwrap(),
+            ))
+            .finish()
+            .unwrap();
+        assert_eq!(read_point_cloud.size(), 3);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+
+    fn read_member(buffer: Cursor<Vec<u8>>, file_name: &str) -> Vec<u8> {
+        let mut archive = tar::Archive::new(buffer);
+        let mut file = archive
+            .entries()
+            .unwrap()
+            .map(|f| f.unwrap())
+            .find(|f| f.path().unwrap().to_str() == Some(file_name))
+            .unwrap();
+        let mut data: Vec<u8> = Vec::new();
+        file.read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_edit_transform_tree_and_info() {
+        for (compressed, point_data_file_name) in
+            [(true, "point_data.parquet"), (false, "point_data.xyz")]
+        {
+            let buffer = write_epoint(compressed);
+            let transform_tree = TransformTree::new(
+                Vec::new(),
+                vec![FrameInfo::new(FrameId::global(), None, Some(25832))],
+            )
+            .unwrap();
+            let info = PointCloudInfo::new(Some(FrameId::global())).with_metadata(BTreeMap::from(
+                [("project".to_string(), "survey".to_string())],
+            ));
+
+            let mut edited_buffer = Cursor::new(Vec::new());
+            EpointEditor::new(buffer.clone())
+                .with_transform_tree(Some(transform_tree))
+                .with_info(Some(info.clone()))
+                .finish(&mut edited_buffer)
+                .unwrap();
+            edited_buffer.set_position(0);
+
+            assert_eq!(
+                read_member(edited_buffer.clone(), point_data_file_name),
+                read_member(buffer, point_data_file_name)
+            );
+            let read_info = EpointReader::new(edited_buffer.clone())
+                .read_info()
+                .unwrap();
+            assert_eq!(read_info.point_count, POINT_COUNT);
+            assert_eq!(read_info.info, info);
+            let point_cloud = EpointReader::new(edited_buffer).finish().unwrap();
+            assert_eq!(point_cloud.size(), POINT_COUNT);
+            assert_eq!(point_cloud.info, info);
+            assert_eq!(
+                point_cloud
+                    .transform_tree
+                    .frames()
+                    .get(&FrameId::global())
+                    .and_then(|f| f.crs_epsg),
+                Some(25832)
+            );
+        }
+    }
+
+    #[test]
+    fn test_edit_without_replacements() {
+        let buffer = write_epoint(true);
+
+        let mut edited_buffer = Cursor::new(Vec::new());
+        EpointEditor::new(buffer.clone())
+            .finish(&mut edited_buffer)
+            .unwrap();
+
+        assert_eq!(edited_buffer.into_inner(), buffer.into_inner());
+    }
+
+    #[test]
+    fn test_edit_directory_in_place() {
+        for (compressed, point_data_file_name) in
+            [(true, "point_data.parquet"), (false, "point_data.xyz")]
+        {
+            let directory_path = temp_directory_path(&format!("edit_{compressed}"));
+            let _ = fs::remove_dir_all(&directory_path);
+            let point_cloud = EpointReader::new(write_epoint(true)).finish().unwrap();
+            EpointWriter::from_path(&directory_path)
+                .unwrap()
+                .with_compressed(compressed)
+                .finish(point_cloud)
+                .unwrap();
+            let point_data = fs::read(directory_path.join(point_data_file_name)).unwrap();
+            let transform_tree = TransformTree::new(
+                Vec::new(),
+                vec![FrameInfo::new(FrameId::global(), None, Some(25832))],
+            )
+            .unwrap();
+            let info = PointCloudInfo::new(Some(FrameId::global()));
+
+            EpointEditor::from_path(&directory_path)
+                .unwrap()
+                .with_transform_tree(Some(transform_tree))
+                .with_info(Some(info.clone()))
+                .finish_in_place()
+                .unwrap();
+
+            assert_eq!(
+                fs::read(directory_path.join(point_data_file_name)).unwrap(),
+                point_data
+            );
+            let point_cloud = EpointReader::from_path(&directory_path)
+                .unwrap()
+                .finish()
+                .unwrap();
+            assert_eq!(point_cloud.size(), POINT_COUNT);
+            assert_eq!(point_cloud.info, info);
+            assert_eq!(
+                point_cloud
+                    .transform_tree
+                    .frames()
+                    .get(&FrameId::global())
+                    .and_then(|f| f.crs_epsg),
+                Some(25832)
+            );
+            fs::remove_dir_all(directory_path).unwrap();
+        }
+    }
+
+    fn write_grid_epoint(parquet_options: EpointParquetOptions) -> Cursor<Vec<u8>> {
+        // points of a 4x4 grid in row-major order
+        let mut point_cloud = create_point_cloud(
+            (0..16)
+                .map(|i| Point3::new((i % 4) as f64, (i / 4) as f64, 0.0))
+                .collect(),
+        );
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "intensity".into(),
+                (0..16).map(|i| (i % 2) as f32).collect::<Vec<f32>>(),
+            ))
+            .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "classification".into(),
+                (0..16).map(|i| (i % 2) as u8).collect::<Vec<u8>>(),
+            ))
+            .unwrap();
+
+        write_to_buffer(point_cloud, |w| w.with_parquet_options(parquet_options)).unwrap()
+    }
+
+    #[test]
+    fn test_parquet_options_round_trip() {
+        for compression in [
+            EpointParquetCompression::Uncompressed,
+            EpointParquetCompression::Snappy,
+            EpointParquetCompression::Lz4,
+            EpointParquetCompression::Gzip(Some(9)),
+            EpointParquetCompression::Brotli(None),
+            EpointParquetCompression::Zstd(Some(19)),
+        ] {
+            for dictionary_encoding in [true, false] {
+                let parquet_options = EpointParquetOptions {
+                    compression,
+                    row_group_size: Some(4),
+                    dictionary_encoding,
+                    spatial_sorting: false,
+                    quantization: None,
+                };
+                let buffer = write_grid_epoint(parquet_options);
+
+                let mut parquet_reader = ParquetReader::new(Cursor::new(read_member(
+                    buffer.clone(),
+                    "point_data.parquet",
+                )));
+                let metadata = parquet_reader.get_metadata().unwrap();
+                assert_eq!(metadata.row_groups.len(), 4);
+                // the classification column is dictionary encoded
+                assert!(metadata.row_groups.iter().all(|r| {
+                    r.parquet_columns()
+                        .iter()
+                        .flat_map(|c| c.column_encoding())
+                        .any(|e| Encoding::try_from(*e).ok() == Some(Encoding::RleDictionary))
+                        == dictionary_encoding
+                }));
+                let point_cloud = EpointReader::new(buffer).finish().unwrap();
+                assert_eq!(point_cloud.size(), 16);
+                assert_eq!(
+                    point_cloud.point_data.get_all_points()[5],
+                    Point3::new(1.0, 1.0, 0.0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_plain_encoded_point_data_matches_parquet_writer() {
+        let buffer = write_grid_epoint(EpointParquetOptions {
+            compression: EpointParquetCompression::Zstd(None),
+            row_group_size: Some(4),
+            dictionary_encoding: false,
+            spatial_sorting: false,
+            quantization: None,
+        });
+        let point_data_buffer = read_member(buffer, "point_data.parquet");
+        let mut data_frame = ParquetReader::new(Cursor::new(point_data_buffer.clone()))
+            .finish()
+            .unwrap();
+        let mut expected_buffer: Vec<u8> = Vec::new();
+        ParquetWriter::new(&mut expected_buffer)
+            .with_compression(ParquetCompression::Zstd(None))
+            .with_row_group_size(Some(4))
+            .finish(&mut data_frame)
+            .unwrap();
+
+        let mut parquet_reader = ParquetReader::new(Cursor::new(point_data_buffer));
+        let mut expected_parquet_reader = ParquetReader::new(Cursor::new(expected_buffer));
+        assert_eq!(
+            parquet_reader.schema().unwrap(),
+            expected_parquet_reader.schema().unwrap()
+        );
+        let metadata = parquet_reader.get_metadata().unwrap().clone();
+        let expected_metadata = expected_parquet_reader.get_metadata().unwrap().clone();
+        assert_eq!(metadata.version, expected_metadata.version);
+        assert_eq!(
+            metadata.row_groups.len(),
+            expected_metadata.row_groups.len()
+        );
+        assert_eq!(
+            metadata.key_value_metadata,
+            expected_metadata.key_value_metadata
+        );
+        assert!(
+            parquet_reader
+                .finish()
+                .unwrap()
+                .equals_missing(&expected_parquet_reader.finish().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_write_with_spatial_sorting() {
+        let buffer = write_grid_epoint(EpointParquetOptions {
+            spatial_sorting: true,
+            ..Default::default()
+        });
+
+        let point_cloud = EpointReader::new(buffer).finish().unwrap();
+        let points: Vec<(f64, f64)> = point_cloud
+            .point_data
+            .get_all_points()
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect();
+        assert_eq!(
+            points[0..8],
+            [
+                (0.0, 0.0),
+                (1.0, 0.0),
+                (0.0, 1.0),
+                (1.0, 1.0),
+                (2.0, 0.0),
+                (3.0, 0.0),
+                (2.0, 1.0),
+                (3.0, 1.0)
+            ]
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .get(2),
+            Some(0.0)
+        );
+    }
+
+    fn write_quantized_epoint(
+        quantization: EpointQuantization,
+        partitioning: Option<EpointPartitioning>,
+    ) -> Result<Cursor<Vec<u8>>, Error> {
+        let point_cloud = create_point_cloud(
+            (0..POINT_COUNT)
+                .map(|i| Point3::new(500_000.0 + i as f64 * 0.1234, 5_000_000.5, -1.0))
+                .collect(),
+        );
+
+        write_to_buffer(point_cloud, |w| {
+            w.with_partitioning(partitioning)
+                .with_parquet_options(EpointParquetOptions {
+                    quantization: Some(quantization),
+                    ..Default::default()
+                })
+        })
+    }
+
+    #[test]
+    fn test_quantized_round_trip() {
+        let quantization = EpointQuantization::new(Vector3::new(0.001, 0.001, 0.01));
+        let buffer = write_quantized_epoint(quantization, None).unwrap();
+
+        let point_data_frame = ParquetReader::new(Cursor::new(read_member(
+            buffer.clone(),
+            "point_data.parquet",
+        )))
+        .finish()
+        .unwrap();
+        for column_name in ["x", "y", "z"] {
+            assert_eq!(
+                point_data_frame.column(column_name).unwrap().dtype(),
+                &DataType::Int32
+            );
+        }
+        let read_info = EpointReader::new(buffer.clone()).read_info().unwrap();
+        assert_eq!(read_info.format_version, EPOINT_FORMAT_VERSION);
+        assert_eq!(read_info.schema.get("x"), Some(&DataType::Float64));
+
+        let point_cloud = EpointReader::new(buffer).finish().unwrap();
+        let points = point_cloud.point_data.get_all_points();
+        assert_eq!(points.len(), POINT_COUNT);
+        for (i, point) in points.iter().enumerate() {
+            assert!((point.x - (500_000.0 + i as f64 * 0.1234)).abs() < 0.0005);
+            assert!((point.y - 5_000_000.5).abs() < 0.0005);
+            assert!((point.z + 1.0).abs() < 0.005);
+        }
+    }
+
+    #[test]
+    fn test_quantized_partitioned_with_bounds() {
+        let quantization = EpointQuantization::new(Vector3::new(0.001, 0.001, 0.001))
+            .with_offset(Some(Point3::new(500_000.0, 5_000_000.0, 0.0)));
+        let buffer = write_quantized_epoint(
+            quantization,
+            Some(EpointPartitioning::Grid { tile_size: 1.0 }),
+        )
+        .unwrap();
+
+        let point_cloud = EpointReader::new(buffer.clone())
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(500_001.0, 0.0, -2.0),
+                    Point3::new(500_002.0, 6_000_000.0, 0.0),
+                )
+                .unwrap(),
+            ))
+            .finish()
+            .unwrap();
+
+        let x_values: Vec<f64> = point_cloud
+            .point_data
+            .get_all_points()
+            .iter()
+            .map(|p| p.x)
+            .collect();
+        assert_eq!(x_values.len(), 8);
+        assert!((x_values[0] - 500_001.110_6).abs() < 0.0005);
+
+        // points on the boundary are included
+        let point_cloud = EpointReader::new(buffer)
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(500_001.234, 5_000_000.5, -1.0),
+                    Point3::new(500_001.234, 5_000_000.5, -1.0),
+                )
+                .unwrap(),
+            ))
+            .finish()
+            .unwrap();
+        assert_eq!(point_cloud.size(), 1);
+    }
+
+    #[test]
+    fn test_quantized_with_too_small_scale() {
+        let quantization = EpointQuantization::new(Vector3::new(1e-9, 1e-9, 1e-9))
+            .with_offset(Some(Point3::origin()));
+
+        let result = write_quantized_epoint(quantization, None);
+
+        assert!(matches!(result, Err(Error::CoordinateScaleTooSmall(_))));
+    }
+
+    #[test]
+    fn test_quantized_with_nan_coordinate() {
+        let point_cloud = create_point_cloud(vec![
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(f64::NAN, 2.0, 3.0),
+        ]);
+
+        let result = write_to_buffer(point_cloud, |w| {
+            w.with_parquet_options(EpointParquetOptions {
+                quantization: Some(
+                    EpointQuantization::new(Vector3::new(0.001, 0.001, 0.001))
+                        .with_offset(Some(Point3::origin())),
+                ),
+                ..Default::default()
+            })
+        });
+
+        assert!(matches!(result, Err(Error::NonFiniteQuantizedValue(_))));
+    }
+}
@@ -0,0 +1,792 @@
+#[cfg(test)]
+mod las_round_trip_test {
+
+    use chrono::{DateTime, Utc};
+    use ecoord::{AxisAlignedBoundingBox, FrameId, FrameInfo, TransformTree};
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumns};
+    use epoint_io::{
+        Error, LasIntensityScaling, LasReader, LasVersion, LasWriter, METADATA_KEY_CRS_WKT,
+    };
+    use las::point::{Classification, ScanDirection};
+    use nalgebra::{Point3, Vector3};
+    use polars::prelude::{NamedFrom, Series};
+    use std::collections::HashSet;
+    use std::fs;
+    use std::io::Cursor;
+    use std::path::PathBuf;
+
+    fn create_las_point(index: u8) -> las::Point {
+        las::Point {
+            x: index as f64,
+            y: 2.0,
+            z: 3.0,
+            intensity: 100,
+            return_number: index + 1,
+            number_of_returns: 3,
+            scan_direction: if index == 0 {
+                ScanDirection::LeftToRight
+            } else {
+                ScanDirection::RightToLeft
+            },
+            is_edge_of_flight_line: index == 1,
+            classification: Classification::new(2 + index).unwrap(),
+            is_synthetic: index == 0,
+            is_key_point: index == 1,
+            is_withheld: index == 2,
+            is_overlap: index == 0,
+            scanner_channel: index,
+            scan_angle: -10.5 + index as f32,
+            user_data: 10 * index,
+            gps_time: Some(1.0),
+            color: Some(las::Color::new(1, 2, 3)),
+            nir: Some(1000 + index as u16),
+            ..Default::default()
+        }
+    }
+
+    fn write_las_points(
+        version: (u8, u8),
+        point_format: u8,
+        points: Vec<las::Point>,
+    ) -> Cursor<Vec<u8>> {
+        let mut builder = las::Builder::from(version);
+        builder.point_format = las::point::Format::new(point_format).unwrap();
+        let mut las_writer =
+            las::Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        for point in points {
+            las_writer.write_point(point).unwrap();
+        }
+        let mut buffer = las_writer.into_inner().unwrap();
+        buffer.set_position(0);
+        buffer
+    }
+
+    fn temp_file_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("epoint_las_round_trip_{name}.las"))
+    }
+
+    #[test]
+    fn test_read_extended_point_attributes() {
+        let buffer = write_las_points((1, 4), 8, (0..3).map(create_las_point).collect());
+
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+        let point_data = &point_cloud.point_data;
+
+        assert_eq!(
+            point_data.get_classification_values().unwrap().to_vec(),
+            vec![Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            point_data.get_return_number_values().unwrap().to_vec(),
+            vec![Some(1), Some(2), Some(3)]
+        );
+        assert_eq!(
+            point_data.get_number_of_returns_values().unwrap().to_vec(),
+            vec![Some(3), Some(3), Some(3)]
+        );
+        assert_eq!(
+            point_data
+                .get_scan_direction_flag_values()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(false)]
+        );
+        assert_eq!(
+            point_data
+                .get_edge_of_flight_line_values()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(false), Some(true), Some(false)]
+        );
+        assert_eq!(
+            point_data
+                .get_synthetic_flag_values()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(false)]
+        );
+        assert_eq!(
+            point_data
+                .get_key_point_flag_values()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(false), Some(true), Some(false)]
+        );
+        assert_eq!(
+            point_data
+                .get_withheld_flag_values()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(false), Some(false), Some(true)]
+        );
+        assert_eq!(
+            point_data
+                .get_overlap_flag_values()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(false)]
+        );
+        assert_eq!(
+            point_data.get_user_data_values().unwrap().to_vec(),
+            vec![Some(0), Some(10), Some(20)]
+        );
+        assert_eq!(
+            point_data.get_scanner_channel_values().unwrap().to_vec(),
+            vec![Some(0), Some(1), Some(2)]
+        );
+        assert_eq!(
+            point_data.get_nir_values().unwrap().to_vec(),
+            vec![Some(1000), Some(1001), Some(1002)]
+        );
+        let scan_angle_values = point_data.get_scan_angle_values().unwrap();
+        assert!((scan_angle_values.get(0).unwrap() + 10.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_read_legacy_point_format() {
+        let mut point = create_las_point(0);
+        point.is_overlap = false;
+        point.scanner_channel = 0;
+        point.color = None;
+        point.nir = None;
+        let buffer = write_las_points((1, 2), 1, vec![point]);
+
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_classification_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(2)]
+        );
+        assert!(!point_cloud.point_data.contains_scanner_channel_column());
+        assert!(!point_cloud.point_data.contains_nir_column());
+    }
+
+    #[test]
+    fn test_attributes_round_trip() {
+        let buffer = write_las_points((1, 4), 8, (0..3).map(create_las_point).collect());
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let path = temp_file_path("attributes");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .finish(point_cloud.clone())
+            .unwrap();
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        assert_eq!(las_reader.header().point_format().to_u8().unwrap(), 8);
+        let (read_point_cloud, _) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read_point_cloud.point_data.data_frame,
+            point_cloud.point_data.data_frame
+        );
+    }
+
+    #[test]
+    fn test_select_legacy_point_format() {
+        let mut point = create_las_point(1);
+        point.is_overlap = false;
+        point.scanner_channel = 0;
+        point.nir = None;
+        let buffer = write_las_points((1, 4), 7, vec![point]);
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let path = temp_file_path("legacy_point_format");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .with_version(LasVersion::V1_2)
+            .with_scale(Vector3::new(0.01, 0.01, 0.01))
+            .with_offset(Vector3::new(100.0, 0.0, 0.0))
+            .finish(point_cloud.clone())
+            .unwrap();
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        let header = las_reader.header();
+        assert_eq!(header.point_format().to_u8().unwrap(), 3);
+        assert_eq!(header.version(), las::Version::new(1, 2));
+        assert_eq!(header.transforms().x.scale, 0.01);
+        assert_eq!(header.transforms().x.offset, 100.0);
+
+        let (read_point_cloud, read_info) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read_info.version, LasVersion::V1_2);
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_classification_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(3)]
+        );
+    }
+
+    #[test]
+    fn test_extended_attributes_require_las_1_4() {
+        let buffer = write_las_points((1, 4), 8, vec![create_las_point(0)]);
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let result = LasWriter::new(Cursor::new(Vec::new()))
+            .with_version(LasVersion::V1_2)
+            .finish(point_cloud);
+
+        assert!(matches!(result, Err(epoint_io::Error::Las(_))));
+    }
+
+    #[test]
+    fn test_intensity_scaling() {
+        let point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)],
+                None,
+                None,
+                None,
+                Some(vec![0.25, 0.5]),
+                None,
+                None,
+            )
+            .unwrap(),
+            PointCloudInfo::new(None),
+            TransformTree::default(),
+        )
+        .unwrap();
+
+        for (intensity_scaling, expected) in [
+            (LasIntensityScaling::Raw, vec![Some(0.0), Some(1.0)]),
+            (
+                LasIntensityScaling::Normalized,
+                vec![Some(16384.0), Some(32768.0)],
+            ),
+            (LasIntensityScaling::MinMax, vec![Some(0.0), Some(65535.0)]),
+        ] {
+            let path = temp_file_path("intensity_scaling");
+            LasWriter::from_path(&path)
+                .unwrap()
+                .with_intensity_scaling(intensity_scaling)
+                .finish(point_cloud.clone())
+                .unwrap();
+            let (read_point_cloud, _) = LasReader::from_path(&path).unwrap().finish().unwrap();
+            fs::remove_file(&path).unwrap();
+
+            assert_eq!(
+                read_point_cloud
+                    .point_data
+                    .get_intensity_values()
+                    .unwrap()
+                    .to_vec(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_laz_round_trip() {
+        let buffer = write_las_points(
+            (1, 4),
+            8,
+            (0..100).map(|i| create_las_point(i % 3)).collect(),
+        );
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let las_path = temp_file_path("uncompressed");
+        LasWriter::from_path(&las_path)
+            .unwrap()
+            .finish(point_cloud.clone())
+            .unwrap();
+        let laz_path = las_path.with_file_name("epoint_las_round_trip_compressed.laz");
+        LasWriter::from_path(&laz_path)
+            .unwrap()
+            .finish(point_cloud.clone())
+            .unwrap();
+
+        let las_reader = las::Reader::from_path(&laz_path).unwrap();
+        assert!(las_reader.header().point_format().is_compressed);
+        assert!(las_reader.header().laz_vlr().is_ok());
+        assert!(fs::metadata(&laz_path).unwrap().len() < fs::metadata(&las_path).unwrap().len());
+
+        let (read_point_cloud, _) = LasReader::from_path(&laz_path).unwrap().finish().unwrap();
+        assert_eq!(
+            read_point_cloud.point_data.data_frame,
+            point_cloud.point_data.data_frame
+        );
+        fs::remove_file(&las_path).unwrap();
+        fs::remove_file(&laz_path).unwrap();
+    }
+
+    #[test]
+    fn test_compression_on_request() {
+        let buffer = write_las_points((1, 4), 8, vec![create_las_point(0)]);
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let path = temp_file_path("compression_on_request");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .with_compression(true)
+            .finish(point_cloud.clone())
+            .unwrap();
+
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        assert!(las_reader.header().point_format().is_compressed);
+        let (read_point_cloud, _) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        assert_eq!(
+            read_point_cloud.point_data.get_all_points(),
+            point_cloud.point_data.get_all_points()
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_extra_bytes_round_trip() {
+        let mut point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                vec![Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0)],
+                Some(vec![7, 8]),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap(),
+            PointCloudInfo::new(None),
+            TransformTree::default(),
+        )
+        .unwrap();
+        point_cloud
+            .point_data
+            .add_f32_column("distance", vec![0.5, -1.25])
+            .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new("cluster_id".into(), vec![3u32, 4]))
+            .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new("is_ground".into(), vec![true, false]))
+            .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "distance_to_nearest_neighbor_in_meters".into(),
+                vec![1.0, 2.0],
+            ))
+            .unwrap();
+
+        let path = temp_file_path("extra_bytes");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .finish(point_cloud.clone())
+            .unwrap();
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        assert_eq!(
+            las_reader.header().point_format().extra_bytes,
+            8 + 4 + 4 + 1
+        );
+        let (read_point_cloud, _) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let data_frame = &read_point_cloud.point_data.data_frame;
+        assert_eq!(
+            data_frame
+                .column("is_ground")
+                .unwrap()
+                .u8()
+                .unwrap()
+                .to_vec(),
+            vec![Some(1), Some(0)]
+        );
+        assert!(
+            data_frame
+                .column("distance_to_nearest_neighbor_in_meters")
+                .is_err()
+        );
+
+        for column_name in ["id", "distance", "cluster_id"] {
+            assert_eq!(
+                read_point_cloud
+                    .point_data
+                    .data_frame
+                    .column(column_name)
+                    .unwrap(),
+                point_cloud
+                    .point_data
+                    .data_frame
+                    .column(column_name)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_scaled_extra_bytes() {
+        let mut descriptor = vec![0u8; 192];
+        descriptor[2] = 3; // unsigned short
+        descriptor[3] = 1 << 3; // scale is set
+        descriptor[4..12].copy_from_slice(b"height_a");
+        descriptor[112..120].copy_from_slice(&0.01f64.to_le_bytes());
+
+        let mut builder = las::Builder::from((1, 4));
+        builder.point_format = las::point::Format::new(0).unwrap();
+        builder.point_format.extra_bytes = 2;
+        builder.vlrs.push(las::Vlr {
+            user_id: "LASF_Spec".into(),
+            record_id: 4,
+            description: "Extra Bytes Record".into(),
+            data: descriptor,
+        });
+        let mut las_writer =
+            las::Writer::new(Cursor::new(Vec::new()), builder.into_header().unwrap()).unwrap();
+        las_writer
+            .write_point(las::Point {
+                extra_bytes: 1234u16.to_le_bytes().to_vec(),
+                ..Default::default()
+            })
+            .unwrap();
+        let mut buffer = las_writer.into_inner().unwrap();
+        buffer.set_position(0);
+
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let values = point_cloud
+            .point_data
+            .data_frame
+            .column("height_a")
+            .unwrap();
+        assert!((values.f64().unwrap().get(0).unwrap() - 12.34).abs() < 1e-9);
+    }
+
+    const ETRS89_UTM_32N_WKT: &str = r#"PROJCS["ETRS89 / UTM zone 32N",GEOGCS["ETRS89",DATUM["European_Terrestrial_Reference_System_1989",SPHEROID["GRS 1980",6378137,298.257222101,AUTHORITY["EPSG","7019"]],AUTHORITY["EPSG","6258"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4258"]],PROJECTION["Transverse_Mercator"],PARAMETER["latitude_of_origin",0],PARAMETER["central_meridian",9],PARAMETER["scale_factor",0.9996],PARAMETER["false_easting",500000],PARAMETER["false_northing",0],UNIT["metre",1,AUTHORITY["EPSG","9001"]],AXIS["Easting",EAST],AXIS["Northing",NORTH],AUTHORITY["EPSG","25832"]]"#;
+
+    #[test]
+    fn test_crs_round_trip() {
+        let buffer = write_las_points((1, 4), 8, vec![create_las_point(0)]);
+        let (mut point_cloud, read_info) = LasReader::new(buffer).finish().unwrap();
+        assert_eq!(read_info.crs_epsg, None);
+        point_cloud.transform_tree = TransformTree::new(
+            Vec::new(),
+            vec![FrameInfo::new(FrameId::global(), None, Some(25832))],
+        )
+        .unwrap();
+
+        let result = LasWriter::new(Cursor::new(Vec::new())).finish(point_cloud.clone());
+        assert!(matches!(result, Err(Error::MissingCrsWkt(25832))));
+        point_cloud.info.metadata.insert(
+            METADATA_KEY_CRS_WKT.to_string(),
+            ETRS89_UTM_32N_WKT.to_string(),
+        );
+
+        let path = temp_file_path("crs");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .finish(point_cloud)
+            .unwrap();
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        assert!(las_reader.header().has_wkt_crs());
+        assert!(las_reader.header().get_geotiff_crs().unwrap().is_none());
+        let (read_point_cloud, read_info) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_info.crs_epsg, Some(25832));
+        assert_eq!(read_info.crs_wkt.as_deref(), Some(ETRS89_UTM_32N_WKT));
+        assert_eq!(
+            read_point_cloud.info.metadata.get(METADATA_KEY_CRS_WKT),
+            read_info.crs_wkt.as_ref()
+        );
+        assert_eq!(
+            read_point_cloud
+                .transform_tree
+                .frames()
+                .get(&FrameId::global())
+                .unwrap()
+                .crs_epsg,
+            Some(25832)
+        );
+    }
+
+    #[test]
+    fn test_read_compound_wkt_crs() {
+        let wkt = r#"COMPD_CS["ETRS89 / UTM zone 32N + DHHN2016 height",
+            PROJCS["ETRS89 / UTM zone 32N",GEOGCS["ETRS89",AUTHORITY["EPSG","4258"]],
+                UNIT["metre",1,AUTHORITY["EPSG","9001"]],AUTHORITY["EPSG","25832"]],
+            VERT_CS["DHHN2016 height",AUTHORITY["EPSG","7837"]]]"#;
+        let mut builder = las::Builder::from((1, 4));
+        builder.point_format = las::point::Format::new(6).unwrap();
+        let mut header = builder.into_header().unwrap();
+        header.set_wkt_crs(wkt.as_bytes().to_vec()).unwrap();
+        let mut las_writer = las::Writer::new(Cursor::new(Vec::new()), header).unwrap();
+        las_writer
+            .write_point(las::Point {
+                gps_time: Some(0.0),
+                ..Default::default()
+            })
+            .unwrap();
+        let mut buffer = las_writer.into_inner().unwrap();
+        buffer.set_position(0);
+
+        let (_, read_info) = LasReader::new(buffer).finish().unwrap();
+
+        assert_eq!(read_info.crs_epsg, Some(25832));
+    }
+
+    #[test]
+    fn test_write_stored_wkt_crs() {
+        let wkt = r#"PROJCS["ETRS89 / UTM zone 32N",GEOGCS["ETRS89",AUTHORITY["EPSG","4258"]],AUTHORITY["EPSG","25832"]]"#;
+        let buffer = write_las_points((1, 4), 8, vec![create_las_point(0)]);
+        let (mut point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+        point_cloud.transform_tree = TransformTree::new(
+            Vec::new(),
+            vec![FrameInfo::new(FrameId::global(), None, Some(25832))],
+        )
+        .unwrap();
+        point_cloud
+            .info
+            .metadata
+            .insert(METADATA_KEY_CRS_WKT.to_string(), wkt.to_string());
+
+        let path = temp_file_path("stored_wkt_crs");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .finish(point_cloud)
+            .unwrap();
+        let (read_point_cloud, read_info) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_info.crs_wkt.as_deref(), Some(wkt));
+        assert_eq!(
+            read_point_cloud
+                .info
+                .metadata
+                .get(METADATA_KEY_CRS_WKT)
+                .map(|w| w.as_str()),
+            Some(wkt)
+        );
+    }
+
+    #[test]
+    fn test_write_geo_key_crs() {
+        let point = las::Point {
+            x: 1.0,
+            ..Default::default()
+        };
+        let buffer = write_las_points((1, 2), 0, vec![point]);
+        let (mut point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+        point_cloud.transform_tree = TransformTree::new(
+            Vec::new(),
+            vec![FrameInfo::new(FrameId::global(), None, Some(4978))],
+        )
+        .unwrap();
+
+        let path = temp_file_path("geo_key_crs");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .finish(point_cloud)
+            .unwrap();
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        assert!(las_reader.header().get_wkt_crs_bytes().is_none());
+        let geotiff_crs = las_reader.header().get_geotiff_crs().unwrap().unwrap();
+        assert_eq!(geotiff_crs.get_gt_model_type_geo_key_value(), Some(3));
+        let (_, read_info) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_info.crs_epsg, Some(4978));
+    }
+
+    #[test]
+    fn test_write_geo_key_crs_out_of_range() {
+        let point = las::Point {
+            x: 1.0,
+            ..Default::default()
+        };
+        let buffer = write_las_points((1, 2), 0, vec![point]);
+        let (mut point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+        point_cloud.transform_tree = TransformTree::new(
+            Vec::new(),
+            vec![FrameInfo::new(FrameId::global(), None, Some(100_000))],
+        )
+        .unwrap();
+
+        let result = LasWriter::new(Cursor::new(Vec::new())).finish(point_cloud);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_chunked_write() {
+        let buffer = write_las_points((1, 4), 8, (0..4).map(create_las_point).collect());
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+
+        let path = temp_file_path("chunked_write");
+        LasWriter::from_path(&path)
+            .unwrap()
+            .with_points_per_chunk(Some(2))
+            .finish(point_cloud.clone())
+            .unwrap();
+        let (read_point_cloud, _) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read_point_cloud.point_data.data_frame,
+            point_cloud.point_data.data_frame
+        );
+    }
+
+    #[test]
+    fn test_incremental_write() {
+        let buffer = write_las_points((1, 4), 8, (0..3).map(create_las_point).collect());
+        let (point_cloud, _) = LasReader::new(buffer).finish().unwrap();
+        let mut other_point_cloud = point_cloud.clone();
+        other_point_cloud
+            .point_data
+            .data_frame
+            .drop_in_place("classification")
+            .unwrap();
+
+        let path = temp_file_path("incremental_write");
+        let mut writer = LasWriter::from_path(&path)
+            .unwrap()
+            .with_points_per_chunk(Some(2))
+            .into_incremental();
+        writer.append(point_cloud.clone()).unwrap();
+        writer.append(other_point_cloud).unwrap();
+        writer.finish().unwrap();
+        let las_reader = las::Reader::from_path(&path).unwrap();
+        assert_eq!(las_reader.header().number_of_points(), 6);
+        let (read_point_cloud, _) = LasReader::from_path(&path).unwrap().finish().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_classification_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(2), Some(3), Some(4), Some(0), Some(0), Some(0)]
+        );
+        assert_eq!(
+            read_point_cloud.point_data.data_frame.slice(0, 3),
+            point_cloud.point_data.data_frame
+        );
+    }
+
+    #[test]
+    fn test_incremental_write_requires_point_cloud() {
+        let writer = LasWriter::new(Cursor::new(Vec::new())).into_incremental();
+
+        assert!(writer.finish().is_err());
+    }
+
+    #[test]
+    fn test_read_with_pushdown() {
+        let points: Vec<las::Point> = (0..4)
+            .map(|i| las::Point {
+                gps_time: Some(10.0 * i as f64),
+                ..create_las_point(i)
+            })
+            .collect();
+        let buffer = write_las_points((1, 4), 8, points);
+
+        let (point_cloud, _) = LasReader::new(buffer)
+            .with_points_per_chunk(Some(1))
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(Point3::new(1.0, 0.0, 0.0), Point3::new(3.0, 5.0, 5.0))
+                    .unwrap(),
+            ))
+            .with_time_range(Some((
+                DateTime::<Utc>::from_timestamp(0, 0).unwrap(),
+                DateTime::<Utc>::from_timestamp(25, 0).unwrap(),
+            )))
+            .with_classifications(Some(HashSet::from([3, 4, 5])))
+            .with_columns(Some(vec!["classification".to_string()]))
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            point_cloud.point_data.get_x_values().to_vec(),
+            vec![Some(1.0), Some(2.0)]
+        );
+        assert_eq!(
+            point_cloud.point_data.data_frame.get_column_names(),
+            vec!["x", "y", "z", "classification"]
+        );
+    }
+
+    #[test]
+    fn test_chunked_read_with_varying_attributes() {
+        let points: Vec<las::Point> = (0..2)
+            .map(|i| las::Point {
+                x: i as f64,
+                point_source_id: 5 * i as u16,
+                gps_time: Some(1.0),
+                color: Some(las::Color::new(10 + 1000 * i as u16, 20, 30)),
+                ..Default::default()
+            })
+            .collect();
+        let buffer = write_las_points((1, 2), 3, points);
+
+        let (point_cloud, _) = LasReader::new(buffer.clone())
+            .normalize_colors(true)
+            .with_points_per_chunk(Some(1))
+            .finish()
+            .unwrap();
+        let (unchunked_point_cloud, _) = LasReader::new(buffer)
+            .normalize_colors(true)
+            .with_points_per_chunk(None)
+            .finish()
+            .unwrap();
+
+        assert_eq!(
+            point_cloud
+                .point_data
+                .data_frame
+                .column("point_source_id")
+                .unwrap()
+                .u16()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0), Some(5)]
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .data_frame
+                .column("color_red")
+                .unwrap()
+                .u16()
+                .unwrap()
+                .to_vec(),
+            vec![Some(10), Some(1010)]
+        );
+        assert_eq!(
+            point_cloud.point_data.data_frame,
+            unchunked_point_cloud.point_data.data_frame
+        );
+    }
+
+    #[test]
+    fn test_read_with_disjoint_bounds() {
+        let buffer = write_las_points((1, 4), 8, (0..3).map(create_las_point).collect());
+
+        let result = LasReader::new(buffer)
+            .with_bounds(Some(
+                AxisAlignedBoundingBox::new(
+                    Point3::new(10.0, 10.0, 10.0),
+                    Point3::new(20.0, 20.0, 20.0),
+                )
+                .unwrap(),
+            ))
+            .finish();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod pcd_round_trip_test {
+
+    use ecoord::{FrameId, StaticTransform, Transform, TransformEdge, TransformId, TransformTree};
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumns};
+    use epoint_io::{PcdEncoding, PcdReader, PcdWriter};
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+    use palette::Srgb;
+    use polars::prelude::{NamedFrom, Series};
+    use std::io::Cursor;
+
+    fn create_point_cloud(number_of_points: usize) -> PointCloud {
+        let points: Vec<Point3<f64>> = (0..number_of_points)
+            .map(|i| Point3::new(i as f64 * 0.5, (i % 7) as f64, -(i as f64) / 3.0))
+            .collect();
+        let intensity: Vec<f32> = (0..number_of_points).map(|i| (i % 13) as f32).collect();
+        let color: Vec<Srgb<u16>> = (0..number_of_points)
+            .map(|i| Srgb::new((i % 256) as u16 * 257, 0, 65535))
+            .collect();
+
+        let point_data =
+            PointDataColumns::new(points, None, None, None, Some(intensity), None, Some(color))
+                .unwrap();
+
+        let sensor_pose = StaticTransform::new(
+            FrameId::global(),
+            FrameId::sensor(),
+            Transform::new(
+                Vector3::new(1.0, 2.0, 3.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5),
+            ),
+        );
+        let transform_tree =
+            TransformTree::new(vec![TransformEdge::Static(sensor_pose)], Vec::new()).unwrap();
+
+        let mut point_cloud = PointCloud::new(
+            point_data,
+            PointCloudInfo::new(Some(FrameId::sensor())),
+            transform_tree,
+        )
+        .unwrap();
+        point_cloud
+            .point_data
+            .add_f32_column(
+                "curvature",
+                (0..number_of_points).map(|i| i as f32 * 0.01).collect(),
+            )
+            .unwrap();
+
+        point_cloud
+    }
+
+    fn write_and_read(point_cloud: PointCloud, encoding: PcdEncoding) -> PointCloud {
+        let mut buffer: Vec<u8> = Vec::new();
+        PcdWriter::new(&mut buffer)
+            .with_encoding(encoding)
+            .with_double_precision_coordinates(true)
+            .finish(point_cloud)
+            .unwrap();
+
+        PcdReader::new(Cursor::new(buffer)).finish().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let point_cloud = create_point_cloud(1000);
+
+        for encoding in [
+            PcdEncoding::Ascii,
+            PcdEncoding::Binary,
+            PcdEncoding::BinaryCompressed,
+        ] {
+            let read_point_cloud = write_and_read(point_cloud.clone(), encoding);
+
+            assert_eq!(
+                read_point_cloud.point_data.get_all_points(),
+                point_cloud.point_data.get_all_points()
+            );
+            assert_eq!(
+                read_point_cloud
+                    .point_data
+                    .get_intensity_values()
+                    .unwrap()
+                    .to_vec(),
+                point_cloud
+                    .point_data
+                    .get_intensity_values()
+                    .unwrap()
+                    .to_vec()
+            );
+            assert_eq!(
+                read_point_cloud.point_data.get_all_colors().unwrap(),
+                point_cloud.point_data.get_all_colors().unwrap()
+            );
+            assert_eq!(
+                read_point_cloud
+                    .point_data
+                    .data_frame
+                    .column("curvature")
+                    .unwrap(),
+                point_cloud
+                    .point_data
+                    .data_frame
+                    .column("curvature")
+                    .unwrap()
+            );
+
+            let viewpoint = read_point_cloud
+                .transform_tree
+                .get_static_transform(&TransformId::new(FrameId::global(), FrameId::sensor()))
+                .unwrap();
+            assert_eq!(viewpoint.translation, Vector3::new(1.0, 2.0, 3.0));
+            assert!(
+                viewpoint
+                    .rotation
+                    .angle_to(&UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5))
+                    < 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_single_precision_coordinates() {
+        let point_cloud = create_point_cloud(10);
+        for (double_precision_coordinates, size, tolerance) in
+            [(false, "4", 1e-6), (true, "8", 0.0)]
+        {
+            let mut buffer: Vec<u8> = Vec::new();
+            PcdWriter::new(&mut buffer)
+                .with_encoding(PcdEncoding::Binary)
+                .with_double_precision_coordinates(double_precision_coordinates)
+                .finish(point_cloud.clone())
+                .unwrap();
+
+            let header = String::from_utf8_lossy(&buffer).to_string();
+            let header_line = |keyword: &str| {
+                header
+                    .lines()
+                    .find(|l| l.starts_with(keyword))
+                    .unwrap()
+                    .to_string()
+            };
+            assert_eq!(
+                header_line("FIELDS"),
+                "FIELDS x y z rgb intensity curvature"
+            );
+            assert_eq!(
+                header_line("SIZE"),
+                format!("SIZE {size} {size} {size} 4 4 4")
+            );
+            assert_eq!(header_line("TYPE"), "TYPE F F F F F F");
+
+            let read_point_cloud = PcdReader::new(Cursor::new(buffer)).finish().unwrap();
+            for (read_point, point) in read_point_cloud
+                .point_data
+                .get_all_points()
+                .iter()
+                .zip(point_cloud.point_data.get_all_points())
+            {
+                assert!((read_point - point).norm() <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_organized_round_trip() {
+        let mut point_cloud = create_point_cloud(12);
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "row_index".into(),
+                (0..12u32).rev().map(|i| i / 4).collect::<Vec<u32>>(),
+            ))
+            .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "column_index".into(),
+                (0..12u32).rev().map(|i| i % 4).collect::<Vec<u32>>(),
+            ))
+            .unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        PcdWriter::new(&mut buffer)
+            .with_encoding(PcdEncoding::Binary)
+            .with_double_precision_coordinates(true)
+            .finish(point_cloud.clone())
+            .unwrap();
+        let header = String::from_utf8_lossy(&buffer);
+        assert!(header.contains("WIDTH 4\nHEIGHT 3\n"));
+
+        let read_point_cloud = PcdReader::new(Cursor::new(buffer)).finish().unwrap();
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_row_index_values()
+                .unwrap()
+                .to_vec(),
+            vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2]
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>()
+        );
+        // points were reversed to restore the grid order
+        assert_eq!(
+            read_point_cloud.point_data.get_all_points()[0],
+            point_cloud.point_data.get_all_points()[11]
+        );
+    }
+
+    #[test]
+    fn test_read_pcl_ascii() {
+        let content = "# .PCD v.7 - Point Cloud Data file format
+VERSION .7
+FIELDS x y z rgb _ normal_x normal_y normal_z
+SIZE 4 4 4 4 1 4 4 4
+TYPE F F F F U F F F
+COUNT 1 1 1 1 1 1 1 1
+WIDTH 2
+HEIGHT 1
+VIEWPOINT 0 0 0 1 0 0 0
+POINTS 2
+DATA ascii
+0.93773 0.33763 0 4286611584 0 0 0 1
+0.90805 0.35641 0 4.2108e+06 0 0 1 0
+";
+
+        let point_cloud = PcdReader::new(Cursor::new(content)).finish().unwrap();
+
+        assert_eq!(point_cloud.size(), 2);
+        assert!(point_cloud.contains_normals());
+        assert_eq!(
+            point_cloud.point_data.get_all_colors().unwrap()[0],
+            Srgb::new(128 * 257, 128 * 257, 128 * 257)
+        );
+        assert!(point_cloud.point_data.data_frame.column("_").is_err());
+    }
+
+    #[test]
+    fn test_read_overstated_point_count() {
+        for (encoding, width, height) in [
+            ("binary", "100000000000000", "1"),
+            ("binary_compressed", "100000000000000", "1"),
+            ("ascii", "100000000000000", "1"),
+            ("binary", "18446744073709551615", "2"),
+        ] {
+            let content = format!(
+                "VERSION .7
+FIELDS x y z
+SIZE 4 4 4
+TYPE F F F
+COUNT 1 1 1
+WIDTH {width}
+HEIGHT {height}
+DATA {encoding}
+0 0 0
+"
+            );
+
+            let result = PcdReader::new(Cursor::new(content)).finish();
+
+            assert!(result.is_err());
+        }
+    }
+}
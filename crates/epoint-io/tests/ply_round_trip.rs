@@ -0,0 +1,263 @@
+#[cfg(test)]
+mod ply_round_trip_test {
+
+    use ecoord::TransformTree;
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumnType, PointDataColumns};
+    use epoint_io::{ColorDepth, PlyEncoding, PlyReader, PlyWriter};
+    use nalgebra::Point3;
+    use palette::Srgb;
+    use polars::prelude::{NamedFrom, Series};
+    use std::io::Cursor;
+
+    fn create_point_cloud() -> PointCloud {
+        let points = vec![
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(-4.5, 0.25, 1000.125),
+            Point3::new(6.0, 7.0, -8.0),
+        ];
+        let intensity = vec![0.5, 1.0, 12.0];
+        let color = vec![
+            Srgb::new(0, 257, 65535),
+            Srgb::new(514, 1028, 2056),
+            Srgb::new(65535, 0, 0),
+        ];
+
+        let point_data = PointDataColumns::new(
+            points,
+            Some(vec![0, 1, 2]),
+            None,
+            None,
+            Some(intensity),
+            None,
+            Some(color),
+        )
+        .unwrap();
+        let mut point_cloud = PointCloud::new(
+            point_data,
+            PointCloudInfo::new(Some("world".into())),
+            TransformTree::default(),
+        )
+        .unwrap();
+        point_cloud
+            .point_data
+            .add_f32_column("distance", vec![0.1, 0.2, 0.3])
+            .unwrap();
+
+        point_cloud
+    }
+
+    fn write_and_read(point_cloud: PointCloud, encoding: PlyEncoding) -> PointCloud {
+        let mut buffer: Vec<u8> = Vec::new();
+        PlyWriter::new(&mut buffer)
+            .with_encoding(encoding)
+            .finish(point_cloud)
+            .unwrap();
+
+        PlyReader::new(Cursor::new(buffer)).finish().unwrap()
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let point_cloud = create_point_cloud();
+
+        let read_point_cloud = write_and_read(point_cloud.clone(), PlyEncoding::BinaryLittleEndian);
+
+        assert_eq!(
+            read_point_cloud.point_data.get_all_points(),
+            point_cloud.point_data.get_all_points()
+        );
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_id_values()
+                .unwrap()
+                .to_vec(),
+            point_cloud.point_data.get_id_values().unwrap().to_vec()
+        );
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec(),
+            point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec()
+        );
+        assert_eq!(
+            read_point_cloud.point_data.get_all_colors().unwrap(),
+            point_cloud.point_data.get_all_colors().unwrap()
+        );
+        assert_eq!(
+            read_point_cloud
+                .point_data
+                .data_frame
+                .column("distance")
+                .unwrap(),
+            point_cloud
+                .point_data
+                .data_frame
+                .column("distance")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ascii_and_big_endian_round_trip() {
+        let point_cloud = create_point_cloud();
+
+        for encoding in [PlyEncoding::Ascii, PlyEncoding::BinaryBigEndian] {
+            let read_point_cloud = write_and_read(point_cloud.clone(), encoding);
+
+            assert_eq!(
+                read_point_cloud.point_data.get_all_points(),
+                point_cloud.point_data.get_all_points()
+            );
+            assert_eq!(
+                read_point_cloud
+                    .point_data
+                    .get_intensity_values()
+                    .unwrap()
+                    .to_vec(),
+                point_cloud
+                    .point_data
+                    .get_intensity_values()
+                    .unwrap()
+                    .to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn test_sixteen_bit_colors() {
+        let mut point_cloud = create_point_cloud();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                PointDataColumnType::ColorRed.into(),
+                vec![1u16, 2, 3],
+            ))
+            .unwrap();
+
+        let mut buffer: Vec<u8> = Vec::new();
+        PlyWriter::new(&mut buffer)
+            .with_color_depth(ColorDepth::SixteenBit)
+            .finish(point_cloud.clone())
+            .unwrap();
+        let read_point_cloud = PlyReader::new(Cursor::new(buffer)).finish().unwrap();
+
+        assert_eq!(
+            read_point_cloud.point_data.get_all_colors().unwrap(),
+            point_cloud.point_data.get_all_colors().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_mesh_with_faces_and_normals() {
+        let content = "ply
+format ascii 1.0
+comment exported by a mesh tool
+element vertex 3
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property uchar red
+property uchar green
+property uchar blue
+property float quality
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 1 255 0 0 0.5
+1 0 0 0 0 1 0 255 0 0.25
+0 1 0 0 0 1 0 0 255 1
+3 0 1 2
+";
+
+        let point_cloud = PlyReader::new(Cursor::new(content)).finish().unwrap();
+
+        assert_eq!(point_cloud.size(), 3);
+        assert!(point_cloud.contains_normals());
+        assert_eq!(
+            point_cloud.point_data.get_all_colors().unwrap()[0],
+            Srgb::new(65535, 0, 0)
+        );
+        assert!(point_cloud.point_data.data_frame.column("quality").is_ok());
+    }
+
+    #[test]
+    fn test_read_duplicate_mapped_properties() {
+        let content = "ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+property uchar diffuse_red
+property float intensity
+property float scalar_intensity
+end_header
+0 0 0 255 0 0 10 0.5 7
+1 0 0 0 255 0 20 0.25 8
+";
+
+        let point_cloud = PlyReader::new(Cursor::new(content)).finish().unwrap();
+
+        assert_eq!(
+            point_cloud.point_data.get_all_colors().unwrap()[0],
+            Srgb::new(65535, 0, 0)
+        );
+        assert_eq!(
+            point_cloud
+                .point_data
+                .get_intensity_values()
+                .unwrap()
+                .to_vec(),
+            vec![Some(0.5), Some(0.25)]
+        );
+    }
+
+    #[test]
+    fn test_read_overstated_vertex_count() {
+        let content = "ply
+format binary_little_endian 1.0
+element vertex 1000000000000
+property float x
+property float y
+property float z
+end_header
+";
+
+        let result = PlyReader::new(Cursor::new(content)).finish();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_reserved_property_out_of_range() {
+        let content = "ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property ushort classification
+end_header
+0 0 0 2
+1 0 0 300
+";
+
+        let result = PlyReader::new(Cursor::new(content)).finish();
+
+        assert!(result.is_err());
+    }
+}
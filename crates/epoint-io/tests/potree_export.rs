@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod potree_export_test {
+
+    use ecoord::TransformTree;
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumns};
+    use epoint_io::PotreeWriter;
+    use nalgebra::Point3;
+    use palette::Srgb;
+    use polars::prelude::{NamedFrom, Series};
+    use std::fs;
+    use std::path::PathBuf;
+
+    const POINT_COUNT: usize = 50;
+
+    fn create_point_cloud() -> PointCloud {
+        let mut point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                (0..POINT_COUNT)
+                    .map(|i| Point3::new(i as f64 * 0.5, (i % 7) as f64, (i % 3) as f64))
+                    .collect(),
+                None,
+                None,
+                None,
+                Some((0..POINT_COUNT).map(|i| i as f32 * 0.1).collect()),
+                None,
+                Some(
+                    (0..POINT_COUNT)
+                        .map(|i| Srgb::new(i as u16, 2 * i as u16, 3 * i as u16))
+                        .collect(),
+                ),
+            )
+            .unwrap(),
+            PointCloudInfo::new(None),
+            TransformTree::default(),
+        )
+        .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "classification".into(),
+                (0..POINT_COUNT).map(|i| (i % 4) as u8).collect::<Vec<u8>>(),
+            ))
+            .unwrap();
+
+        point_cloud
+    }
+
+    fn temp_directory_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("epoint_potree_export_{name}"))
+    }
+
+    #[test]
+    fn test_write_potree_dataset() {
+        let directory_path = temp_directory_path("dataset");
+        let _ = fs::remove_dir_all(&directory_path);
+        PotreeWriter::new(&directory_path)
+            .with_max_points_per_octant(8)
+            .finish(create_point_cloud())
+            .unwrap();
+
+        let metadata: serde_json::Value =
+            serde_json::from_slice(&fs::read(directory_path.join("metadata.json")).unwrap())
+                .unwrap();
+        assert_eq!(metadata["version"], "2.0");
+        assert_eq!(metadata["points"], POINT_COUNT);
+        assert_eq!(metadata["encoding"], "DEFAULT");
+        let attribute_names: Vec<&str> = metadata["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|a| a["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            attribute_names,
+            vec!["position", "intensity", "classification", "rgb"]
+        );
+        let bytes_per_point = 12 + 4 + 1 + 6;
+        let offset: Vec<f64> = metadata["offset"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_f64().unwrap())
+            .collect();
+
+        let hierarchy_data = fs::read(directory_path.join("hierarchy.bin")).unwrap();
+        assert_eq!(
+            metadata["hierarchy"]["firstChunkSize"],
+            hierarchy_data.len()
+        );
+        let octree_data = fs::read(directory_path.join("octree.bin")).unwrap();
+        let nodes: Vec<(u8, u8, u32, u64, u64)> = hierarchy_data
+            .chunks_exact(22)
+            .map(|n| {
+                (
+                    n[0],
+                    n[1],
+                    u32::from_le_bytes(n[2..6].try_into().unwrap()),
+                    u64::from_le_bytes(n[6..14].try_into().unwrap()),
+                    u64::from_le_bytes(n[14..22].try_into().unwrap()),
+                )
+            })
+            .collect();
+        assert!(nodes.len() > 1);
+        assert_eq!(nodes[0].0, 0);
+        assert_eq!(
+            nodes
+                .iter()
+                .map(|n| n.1.count_ones() as usize)
+                .sum::<usize>(),
+            nodes.len() - 1
+        );
+        assert!(nodes.iter().all(|n| n.2 <= 8));
+
+        let mut decoded_points: Vec<(i64, i64, i64, u8, u16)> = Vec::new();
+        for (_, _, point_count, byte_offset, byte_size) in nodes {
+            assert_eq!(byte_size as usize, point_count as usize * bytes_per_point);
+            let node_data = &octree_data[byte_offset as usize..(byte_offset + byte_size) as usize];
+            for point_data in node_data.chunks_exact(bytes_per_point) {
+                let coordinate = |i: usize, o: f64| {
+                    let value = i32::from_le_bytes(point_data[i..i + 4].try_into().unwrap());
+                    ((value as f64 * 0.001 + o) * 10.0).round() as i64
+                };
+                decoded_points.push((
+                    coordinate(0, offset[0]),
+                    coordinate(4, offset[1]),
+                    coordinate(8, offset[2]),
+                    point_data[16],
+                    u16::from_le_bytes(point_data[19..21].try_into().unwrap()),
+                ));
+            }
+        }
+        decoded_points.sort();
+        let expected_points: Vec<(i64, i64, i64, u8, u16)> = (0..POINT_COUNT)
+            .map(|i| {
+                (
+                    i as i64 * 5,
+                    (i % 7) as i64 * 10,
+                    (i % 3) as i64 * 10,
+                    (i % 4) as u8,
+                    2 * i as u16,
+                )
+            })
+            .collect();
+        assert_eq!(decoded_points, expected_points);
+
+        fs::remove_dir_all(&directory_path).unwrap();
+    }
+}
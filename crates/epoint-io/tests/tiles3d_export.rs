@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod tiles3d_export_test {
+
+    use ecoord::{FrameId, FrameInfo, TransformTree};
+    use epoint_core::{PointCloud, PointCloudInfo, PointDataColumns};
+    use epoint_io::{Error, Tiles3dWriter};
+    use nalgebra::{Matrix4, Point3};
+    use palette::Srgb;
+    use polars::prelude::{NamedFrom, Series};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn create_point_cloud(points: Vec<Point3<f64>>, crs_epsg: Option<u32>) -> PointCloud {
+        let point_count = points.len();
+        let mut point_cloud = PointCloud::new(
+            PointDataColumns::new(
+                points,
+                None,
+                None,
+                None,
+                Some((0..point_count).map(|i| i as f32).collect()),
+                None,
+                Some(
+                    (0..point_count)
+                        .map(|i| Srgb::new(256 * i as u16, 0, 0))
+                        .collect(),
+                ),
+            )
+            .unwrap(),
+            PointCloudInfo::new(Some(FrameId::global())),
+            TransformTree::new(
+                Vec::new(),
+                vec![FrameInfo::new(FrameId::global(), None, crs_epsg)],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        point_cloud
+            .point_data
+            .data_frame
+            .with_column(Series::new(
+                "classification".into(),
+                (0..point_count).map(|i| (i % 4) as u8).collect::<Vec<u8>>(),
+            ))
+            .unwrap();
+
+        point_cloud
+    }
+
+    fn temp_directory_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("epoint_tiles3d_export_{name}"))
+    }
+
+    /// Decodes the points of all tiles as (position, red, intensity, classification), with the
+    /// positions transformed by the root transform.
+    fn read_tileset_points(directory_path: &Path) -> Vec<(Point3<f64>, u8, f32, u8)> {
+        let tileset: serde_json::Value =
+            serde_json::from_slice(&fs::read(directory_path.join("tileset.json")).unwrap())
+                .unwrap();
+        assert_eq!(tileset["asset"]["version"], "1.0");
+        assert_eq!(tileset["root"]["refine"], "ADD");
+        let transform_values: Vec<f64> = tileset["root"]["transform"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_f64().unwrap())
+            .collect();
+        let transform = Matrix4::from_column_slice(&transform_values);
+
+        let mut pending_tiles = vec![&tileset["root"]];
+        let mut points = Vec::new();
+        while let Some(tile) = pending_tiles.pop() {
+            if let Some(children) = tile["children"].as_array() {
+                assert!(
+                    children
+                        .iter()
+                        .all(|c| c["geometricError"].as_f64() <= tile["geometricError"].as_f64())
+                );
+                pending_tiles.extend(children);
+            }
+            let Some(uri) = tile["content"]["uri"].as_str() else {
+                continue;
+            };
+
+            let data = fs::read(directory_path.join(uri)).unwrap();
+            let u32_at = |i: usize| u32::from_le_bytes(data[i..i + 4].try_into().unwrap()) as usize;
+            assert_eq!(&data[0..4], b"pnts");
+            assert_eq!(u32_at(8), data.len());
+            let (feature_json_length, feature_binary_length, batch_json_length) =
+                (u32_at(12), u32_at(16), u32_at(20));
+            assert_eq!((28 + feature_json_length) % 8, 0);
+
+            let feature_table: serde_json::Value =
+                serde_json::from_slice(&data[28..28 + feature_json_length]).unwrap();
+            let feature_binary = &data[28 + feature_json_length..];
+            let batch_offset = 28 + feature_json_length + feature_binary_length;
+            let batch_table: serde_json::Value =
+                serde_json::from_slice(&data[batch_offset..batch_offset + batch_json_length])
+                    .unwrap();
+            let batch_binary = &data[batch_offset + batch_json_length..];
+
+            let vector = |name: &str| -> Vec<f64> {
+                feature_table[name]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|v| v.as_f64().unwrap())
+                    .collect()
+            };
+            let (rtc_center, offset, scale) = (
+                vector("RTC_CENTER"),
+                vector("QUANTIZED_VOLUME_OFFSET"),
+                vector("QUANTIZED_VOLUME_SCALE"),
+            );
+            let point_count = feature_table["POINTS_LENGTH"].as_u64().unwrap() as usize;
+            let rgb_offset = feature_table["RGB"]["byteOffset"].as_u64().unwrap() as usize;
+            let intensity_offset =
+                batch_table["intensity"]["byteOffset"].as_u64().unwrap() as usize;
+            let classification_offset = batch_table["classification"]["byteOffset"]
+                .as_u64()
+                .unwrap() as usize;
+
+            for i in 0..point_count {
+                let coordinate = |j: usize| {
+                    let index = 6 * i + 2 * j;
+                    let value =
+                        u16::from_le_bytes(feature_binary[index..index + 2].try_into().unwrap());
+                    rtc_center[j] + offset[j] + value as f64 / u16::MAX as f64 * scale[j]
+                };
+                let local_position = Point3::new(coordinate(0), coordinate(1), coordinate(2));
+                let intensity_index = intensity_offset + 4 * i;
+                points.push((
+                    transform.transform_point(&local_position),
+                    feature_binary[rgb_offset + 3 * i],
+                    f32::from_le_bytes(
+                        batch_binary[intensity_index..intensity_index + 4]
+                            .try_into()
+                            .unwrap(),
+                    ),
+                    batch_binary[classification_offset + i],
+                ));
+            }
+        }
+
+        points
+    }
+
+    #[test]
+    fn test_write_tileset() {
+        let directory_path = temp_directory_path("local");
+        let _ = fs::remove_dir_all(&directory_path);
+        let points: Vec<Point3<f64>> = (0..40)
+            .map(|i| Point3::new(100.0 + i as f64, (i % 7) as f64, (i % 3) as f64))
+            .collect();
+        Tiles3dWriter::new(&directory_path)
+            .with_max_points_per_tile(6)
+            .finish(create_point_cloud(points.clone(), None))
+            .unwrap();
+
+        let mut tileset_points = read_tileset_points(&directory_path);
+        fs::remove_dir_all(&directory_path).unwrap();
+
+        assert_eq!(tileset_points.len(), points.len());
+        tileset_points.sort_by_key(|p| p.1);
+        for (i, (position, red, intensity, classification)) in tileset_points.iter().enumerate() {
+            assert!((position - points[i]).norm() < 0.01);
+            assert_eq!(*red as usize, i);
+            assert_eq!(*intensity, i as f32);
+            assert_eq!(*classification as usize, i % 4);
+        }
+    }
+
+    #[test]
+    fn test_write_georeferenced_tileset() {
+        let directory_path = temp_directory_path("utm");
+        let _ = fs::remove_dir_all(&directory_path);
+        // the first point is located on the equator at 3° longitude
+        let points: Vec<Point3<f64>> = (0..20)
+            .map(|i| {
+                Point3::new(
+                    500_000.0 + 10.0 * i as f64,
+                    10.0 * (i % 2) as f64,
+                    (i % 3) as f64,
+                )
+            })
+            .collect();
+        Tiles3dWriter::new(&directory_path)
+            .with_max_points_per_tile(6)
+            .finish(create_point_cloud(points, Some(32631)))
+            .unwrap();
+
+        let mut tileset_points = read_tileset_points(&directory_path);
+        fs::remove_dir_all(&directory_path).unwrap();
+        tileset_points.sort_by_key(|p| p.1);
+
+        let expected_first_point = Point3::new(
+            6_378_137.0 * 3.0f64.to_radians().cos(),
+            6_378_137.0 * 3.0f64.to_radians().sin(),
+            0.0,
+        );
+        assert!((tileset_points[0].0 - expected_first_point).norm() < 0.05);
+        // grid distances are scaled by 0.9996 close to the central meridian
+        let distance = (tileset_points[6].0 - tileset_points[0].0).norm();
+        assert!((distance - 60.0 / 0.9996).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_write_tileset_with_unsupported_crs() {
+        let directory_path = temp_directory_path("unsupported");
+        let point_cloud = create_point_cloud(vec![Point3::new(1.0, 2.0, 3.0)], Some(3857));
+
+        let result = Tiles3dWriter::new(&directory_path).finish(point_cloud);
+
+        assert!(matches!(result, Err(Error::UnsupportedCrs(3857))));
+        let _ = fs::remove_dir_all(&directory_path);
+    }
+}
@@ -8,12 +8,33 @@
 //!
 //! For serializing a point cloud, this data structure is used:
 //!
-//! - `point_cloud_name.tar` (uncompressed as [tarball](https://en.wikipedia.org/wiki/Tar_(computing))) or `point_cloud_name.epoint` (compressed)
-//!     - `point_data.xyz` (uncompressed) or `point_data.parquet` (compressed)
+//! - `point_cloud_name.epoint.tar` (uncompressed as [tarball](https://en.wikipedia.org/wiki/Tar_(computing))) or `point_cloud_name.epoint` (compressed), or the directory `point_cloud_name.epoint.d` with the same members as files
+//!     - `info.json` (uncompressed) or `info.json.zst` (compressed), which is the first member
 //!         - mandatory fields:
-//!             - `x` [f64]: X coordinate
-//!             - `y` [f64]: Y coordinate
-//!             - `z` [f64]: Z coordinate
+//!             - `format_version` [u32]: 2 for a single point data member, 3 for partitioned point data and 4 for quantized columns (missing in version 1, which only has the `frame_id`)
+//!             - `columns`: Schema of the point data with an entry per column
+//!                 - `name` [String]: Name of the column
+//!                 - `data_type` [String]: Data type of the column, such as `f64` or `u16`
+//!                 - `unit` [String]: Unit of the values (optional)
+//!                 - `description` [String]: Meaning of the values (optional)
+//!                 - `quantization`: `scale` [f64] and `offset` [f64] of a column stored as [i32] with `value = stored * scale + offset` (optional)
+//!             - `metadata`: Free-form metadata with [String] keys and values, such as the OGC WKT of the coordinate reference system as `crs_wkt`
+//!         - optional fields:
+//!             - `writer` [String]: Name and version of the software that wrote the archive
+//!             - `frame_id` [String]: Coordinate frame valid for all points (point data must not contain a frame_id column then)
+//!             - `summary`: Statistics of the point data, which are read without decoding it
+//!                 - `point_count` [usize]: Number of points
+//!                 - `bounding_box`: `lower_bound` [f64; 3] and `upper_bound` [f64; 3] of the coordinates
+//!                 - `time_range`: `start` and `end` with `sec` [i64] and `nanosec` [u32] (optional)
+//!     - `ecoord.json` (uncompressed) or `ecoord.json.zst` (compressed)
+//!         - contains a transformation tree with validity durations
+//!         - information: srid
+//!         - purpose: Translate and rotate the point cloud without reading/writing the point data (see `io::EpointEditor`)
+//!     - `point_data.xyz` (uncompressed) or `point_data.parquet` (compressed), unless the point data is partitioned
+//!         - mandatory fields:
+//!             - `x` [f64]: X coordinate, stored as [i32] if quantized
+//!             - `y` [f64]: Y coordinate, stored as [i32] if quantized
+//!             - `z` [f64]: Z coordinate, stored as [i32] if quantized
 //!         - optional fields: Timestamp, unique id, color
 //!             - `id` [u64]: Identifier for an individual point
 //!             - `frame_id` [String]: Coordinate frame the point is defined in
@@ -29,14 +50,31 @@
 //!             - `color_red` [u16]: Red image channel value
 //!             - `color_green` [u16]: Green image channel value
 //!             - `color_blue` [u16]: Blue image channel value
-//!     - `info.json` (uncompressed) or `info.json.zst` (compressed)
-//!         - mandatory fields:
-//!         - optional fields:
-//!             - `frame_id` [String]: Coordinate frame valid for all points (point data must not contain a frame_id column then)
-//!     - `ecoord.json` (uncompressed) or `ecoord.json.zst` (compressed)
-//!         - contains a transformation tree with validity durations
-//!         - information: srid
-//!         - purpose: Translate and rotate the point cloud without reading/writing the point data
+//!             - `normal_x` [f32]: X component of the surface normal
+//!             - `normal_y` [f32]: Y component of the surface normal
+//!             - `normal_z` [f32]: Z component of the surface normal
+//!             - `row_index` [u32]: Row index of the point within a structured scan grid
+//!             - `column_index` [u32]: Column index of the point within a structured scan grid
+//!             - `classification` [u8]: Class of the point as defined by the ASPRS LAS specification
+//!             - `return_number` [u8]: Pulse return number
+//!             - `number_of_returns` [u8]: Total number of returns of the pulse
+//!             - `scan_angle` [f32]: Scan angle in degrees
+//!             - `scan_direction_flag` [bool]: Scan direction, true if positive
+//!             - `edge_of_flight_line` [bool]: Last point of a scan line
+//!             - `synthetic_flag` [bool]: Point was not directly observed
+//!             - `key_point_flag` [bool]: Point is a model key-point
+//!             - `withheld_flag` [bool]: Point is withheld
+//!             - `overlap_flag` [bool]: Point is in a swath overlap
+//!             - `user_data` [u8]: Custom user data
+//!             - `scanner_channel` [u8]: Scanner channel
+//!             - `nir` [u16]: Near-infrared channel value
+//!         - custom fields: any other column name with any data type
+//!             - the names of the optional fields are reserved and require the given data type,
+//!               which includes `normal_*`, `row_index`, `column_index` and the LAS attributes from
+//!               `classification` to `nir`, previously stored as custom fields of any data type
+//!     - `point_data_index.json` (uncompressed) or `point_data_index.json.zst` (compressed), if the point data is partitioned
+//!         - `parts`: Entry per part with `file_name` [String], `point_count` [usize] and `bounding_box`
+//!     - `point_data/*.xyz` (uncompressed) or `point_data/*.parquet` (compressed): Parts of the partitioned point data, such as grid tiles or octree leaf octants, with the fields of `point_data`
 //!
 //! # Data structure
 //!